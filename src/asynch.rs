@@ -0,0 +1,269 @@
+use embedded_graphics_core::{geometry::Dimensions, primitives::Rectangle};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{AsyncInterface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: AsyncInterface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Waits until the interface is ready for the next transfer.
+    ///
+    /// The async drawing methods can return while the last pixels are still
+    /// being transmitted, which allows rendering the next chunk in the
+    /// meantime. They wait for the previous transfer before they start a new
+    /// one, but the blocking methods of the display don't. Await this method
+    /// before the blocking methods are used after an async drawing method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::pixelcolor::Rgb565;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # async {
+    /// let colors = core::iter::repeat(Rgb565::new(0, 63, 0)).take(20 * 10);
+    /// display.set_pixels_async(0, 0, 19, 9, colors).await.unwrap();
+    ///
+    /// display.ready().await.unwrap();
+    /// display.set_brightness(128).unwrap();
+    /// # };
+    /// ```
+    pub async fn ready(&mut self) -> Result<(), DI::Error> {
+        self.di.ready().await
+    }
+
+    /// Sets pixel colors in a rectangular region asynchronously.
+    ///
+    /// Async variant of [`set_pixels`](Self::set_pixels). The address window
+    /// is set by using the blocking interface methods, the pixel data is sent
+    /// by using the [`AsyncInterface`] methods.
+    ///
+    /// The async drawing methods can return before all pixels are
+    /// transmitted, see [`ready`](Self::ready).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::pixelcolor::Rgb565;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # async {
+    /// let colors = core::iter::repeat(Rgb565::new(0, 63, 0)).take(20 * 10);
+    /// display.set_pixels_async(0, 0, 19, 9, colors).await.unwrap();
+    /// # };
+    /// ```
+    pub async fn set_pixels_async<T>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: T,
+    ) -> Result<(), DI::Error>
+    where
+        T: IntoIterator<Item = M::ColorFormat>,
+    {
+        self.di.ready().await?;
+        self.set_address_window(sx, sy, ex, ey)?;
+
+        M::write_memory_start(&mut self.di)?;
+
+        M::ColorFormat::send_pixels_async(&mut self.di, colors).await
+    }
+
+    /// Sets pixel data which is already in the format of the display interface
+    /// asynchronously.
+    ///
+    /// Async variant of [`set_pixels_raw`](Self::set_pixels_raw).
+    pub async fn set_pixels_raw_async(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        data: &[DI::Word],
+    ) -> Result<(), DI::Error> {
+        self.di.ready().await?;
+        self.set_address_window(sx, sy, ex, ey)?;
+
+        M::write_memory_start(&mut self.di)?;
+
+        self.di.send_raw_pixels_async(data).await
+    }
+
+    /// Fills an area with a single color asynchronously.
+    ///
+    /// The area is clipped to the display bounds.
+    pub async fn fill_solid_async(
+        &mut self,
+        area: &Rectangle,
+        color: M::ColorFormat,
+    ) -> Result<(), DI::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        self.di.ready().await?;
+        self.set_address_window(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        )?;
+
+        M::write_memory_start(&mut self.di)?;
+
+        let count = area.size.width * area.size.height;
+        M::ColorFormat::send_repeated_pixel_async(&mut self.di, color, count).await
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::{
+        geometry::{Point, Size},
+        pixelcolor::{Rgb565, RgbColor},
+    };
+
+    use crate::{
+        _mock::MockDelay,
+        interface::{block_on, Interface, InterfaceKind, RecordingInterface, Transfer},
+        models::ILI9341Rgb565,
+        Builder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn async_drawing() {
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        unsafe { display.dcs() }.clear();
+
+        block_on(display.set_pixels_async(0, 0, 1, 0, [Rgb565::RED, Rgb565::BLUE])).unwrap();
+        block_on(display.fill_solid_async(
+            &Rectangle::new(Point::new(230, 0), Size::new(20, 1)),
+            Rgb565::GREEN,
+        ))
+        .unwrap();
+
+        let transfers = display.release().0.take_transfers();
+        assert_eq!(
+            transfers[3],
+            Transfer::Pixels(alloc::vec![0xF8, 0x00, 0x00, 0x1F])
+        );
+        assert_eq!(
+            transfers[4],
+            Transfer::Command {
+                instruction: 0x2A,
+                params: alloc::vec![0, 230, 0, 239],
+            }
+        );
+        assert_eq!(transfers[7], Transfer::Pixels([0x07, 0xE0].repeat(10)));
+    }
+
+    /// Interface which simulates transfers in the background.
+    ///
+    /// Pixels are sent in the background and the interface panics if a
+    /// command is sent before the transfer is complete.
+    #[derive(Default)]
+    struct BackgroundInterface {
+        busy: bool,
+        transfers: usize,
+    }
+
+    impl Interface for BackgroundInterface {
+        type Word = u8;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            assert!(!self.busy, "command sent during a background transfer");
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            _pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            unreachable!()
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            unreachable!()
+        }
+    }
+
+    impl AsyncInterface for BackgroundInterface {
+        async fn ready(&mut self) -> Result<(), Self::Error> {
+            self.busy = false;
+            Ok(())
+        }
+
+        async fn send_command_async(
+            &mut self,
+            command: u8,
+            args: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.ready().await?;
+            self.send_command(command, args)
+        }
+
+        async fn send_pixels_async<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            self.ready().await?;
+            pixels.into_iter().for_each(drop);
+            self.busy = true;
+            self.transfers += 1;
+            Ok(())
+        }
+
+        async fn send_repeated_pixel_async<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            self.ready().await?;
+            self.busy = true;
+            self.transfers += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn waits_for_background_transfers() {
+        let mut display = Builder::new(ILI9341Rgb565, BackgroundInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+
+        block_on(display.set_pixels_async(0, 0, 1, 0, [Rgb565::RED, Rgb565::BLUE])).unwrap();
+        block_on(display.set_pixels_raw_async(0, 0, 0, 0, &[0xF8, 0x00])).unwrap();
+        block_on(display.fill_solid_async(
+            &Rectangle::new(Point::zero(), Size::new(10, 10)),
+            Rgb565::GREEN,
+        ))
+        .unwrap();
+        assert!(display.di.busy);
+
+        block_on(display.ready()).unwrap();
+        display.set_brightness(128).unwrap();
+        assert_eq!(display.release().0.transfers, 3);
+    }
+}
@@ -6,11 +6,16 @@ use embedded_hal::{
 };
 
 use crate::{
-    dcs::InterfaceExt,
-    interface::{Interface, InterfacePixelFormat},
+    dcs,
+    dcs::SequenceEntry,
+    interface::{Interface, InterfacePixelFormat, ReadInterface},
     models::{Model, ModelInitError},
-    options::{ColorInversion, ColorOrder, ModelOptions, Orientation, RefreshOrder},
-    Display,
+    options::{
+        ColorInversion, ColorOrder, CommandDelays, InitTimings, ModelOptions, Orientation,
+        RefreshOrder, TearingEffect,
+    },
+    self_test::PowerMode,
+    Display, Reset,
 };
 
 /// Builder for [Display] instances.
@@ -41,6 +46,7 @@ where
     model: MODEL,
     rst: Option<RST>,
     options: ModelOptions,
+    warm_start: bool,
 }
 
 impl<DI, MODEL> Builder<DI, MODEL, NoResetPin>
@@ -54,11 +60,14 @@ where
     ///
     #[must_use]
     pub fn new(model: MODEL, di: DI) -> Self {
+        let options = model.default_options();
+
         Self {
             di,
             model,
             rst: None,
-            options: ModelOptions::full_size::<MODEL>(),
+            options,
+            warm_start: false,
         }
     }
 }
@@ -124,6 +133,77 @@ where
         self
     }
 
+    ///
+    /// Sets extra delays after commands sent during initialization.
+    ///
+    /// See [CommandDelays] for more information.
+    ///
+    #[must_use]
+    pub fn command_delays(mut self, command_delays: CommandDelays) -> Self {
+        self.options.command_delays = command_delays;
+        self
+    }
+
+    ///
+    /// Overrides the model's built-in delays after reset, SLPOUT and DISPON.
+    ///
+    /// See [InitTimings] for more information.
+    ///
+    #[must_use]
+    pub fn init_timings(mut self, init_timings: InitTimings) -> Self {
+        self.options.init_timings = init_timings;
+        self
+    }
+
+    ///
+    /// Sets additional commands which are sent after the model's init sequence.
+    ///
+    /// This can be used to adjust vendor specific settings, like gamma or power
+    /// control registers, without implementing a new model.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{dcs_seq, models::ST7789, Builder};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ST7789, di)
+    ///     .init_sequence(dcs_seq![cmd(0xBB, 0x35), delay_ms(10)])
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn init_sequence(mut self, init_sequence: &'static [SequenceEntry]) -> Self {
+        self.options.init_sequence = init_sequence;
+        self
+    }
+
+    ///
+    /// Enables the tearing effect output after initialization.
+    ///
+    /// The TE pin of the controller signals the blanking period, which can be
+    /// waited for with [`Display::wait_for_tearing_effect`] before updating
+    /// the framebuffer. The output is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{models::ST7789, options::TearingEffect, Builder};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ST7789, di)
+    ///     .tearing_effect(TearingEffect::Vertical)
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn tearing_effect(mut self, tearing_effect: TearingEffect) -> Self {
+        self.options.tearing_effect = tearing_effect;
+        self
+    }
+
     /// Sets the reset pin.
     ///
     /// ### WARNING
@@ -137,9 +217,30 @@ where
             model: self.model,
             rst: Some(rst),
             options: self.options,
+            warm_start: self.warm_start,
         }
     }
 
+    ///
+    /// Sets the warm start flag.
+    ///
+    /// If set, [`init`](Self::init) assumes that the controller was already
+    /// initialized, e.g. by a bootloader, and skips the reset and the init
+    /// sequence. The configuration is still checked, e.g. unsupported
+    /// interfaces are rejected, and only the pixel format, address mode,
+    /// color inversion and tearing effect settings are written, which doesn't
+    /// change the framebuffer content. This preserves a splash screen across a
+    /// firmware handoff.
+    ///
+    /// Other settings of the init sequence, like the power settings, must
+    /// match the settings used by the previous initialization.
+    ///
+    #[must_use]
+    pub fn warm_start(mut self, warm_start: bool) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
     ///
     /// Consumes the builder to create a new [Display] with an optional reset [OutputPin].
     /// Blocks using the provided [DelayNs] `delay_source` to perform the display initialization.
@@ -148,13 +249,13 @@ where
     /// Returns [InitError] if the area defined by the [`display_size`](Self::display_size)
     /// and [`display_offset`](Self::display_offset) settings is (partially) outside the framebuffer.
     pub fn init(
-        mut self,
+        self,
         delay_source: &mut impl DelayNs,
     ) -> Result<Display<DI, MODEL, RST>, InitError<DI::Error, RST::Error>> {
         let to_u32 = |(a, b)| (u32::from(a), u32::from(b));
         let (width, height) = to_u32(self.options.display_size);
         let (offset_x, offset_y) = to_u32(self.options.display_offset);
-        let (max_width, max_height) = to_u32(MODEL::FRAMEBUFFER_SIZE);
+        let (max_width, max_height) = to_u32(self.model.framebuffer_size());
 
         if width == 0 || height == 0 || width > max_width || height > max_height {
             return Err(InitError::InvalidConfiguration(
@@ -174,33 +275,74 @@ where
             ));
         }
 
-        match self.rst {
-            Some(ref mut rst) => {
-                rst.set_low().map_err(InitError::ResetPin)?;
-                delay_source.delay_us(MODEL::RESET_DURATION);
-                rst.set_high().map_err(InitError::ResetPin)?;
-            }
-            None => self
-                .di
-                .write_command(crate::dcs::SoftReset)
-                .map_err(InitError::Interface)?,
-        }
-
-        let madctl = self.model.init(&mut self.di, delay_source, &self.options)?;
-
-        let display = Display {
+        let mut display = Display {
             di: self.di,
             model: self.model,
             rst: self.rst,
             options: self.options,
-            madctl,
             sleeping: false, // TODO: init should lock state
+            brightness: None,
+            frame_rate: None,
+            idle: false,
+            partial_area: None,
+            aod: None,
+            scroll_region: None,
+            scroll_offset: None,
         };
 
+        if self.warm_start {
+            display.warm_start()?;
+        } else {
+            display.reset_and_init(delay_source, Reset::Auto)?;
+        }
+
         Ok(display)
     }
 }
 
+impl<DI, MODEL, RST> Builder<DI, MODEL, RST>
+where
+    DI: ReadInterface,
+    MODEL: Model,
+    MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    ///
+    /// Consumes the builder to create a new [Display], skipping the
+    /// initialization if the controller is already running.
+    ///
+    /// The power mode of the controller is read to detect if it is awake and
+    /// the display is on. In that case the display is created like a
+    /// [`warm_start`](Self::warm_start), otherwise [`init`](Self::init) is
+    /// used to initialize it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{models::ILI9341Rgb565, Builder};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ILI9341Rgb565, di)
+    ///     .init_or_resume(&mut delay)
+    ///     .unwrap();
+    /// ```
+    pub fn init_or_resume(
+        mut self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<Display<DI, MODEL, RST>, InitError<DI::Error, RST::Error>> {
+        let mut power_mode = [0];
+        let dummy_cycles = self.model.read_dummy_cycles(dcs::GET_POWER_MODE, 1);
+        self.di
+            .read_command_with_dummy_cycles(dcs::GET_POWER_MODE, dummy_cycles, &mut power_mode)
+            .map_err(InitError::Interface)?;
+
+        let power_mode = PowerMode(power_mode[0]);
+        self.warm_start = power_mode.sleep_out() && power_mode.display_on();
+        self.init(delay_source)
+    }
+}
+
 /// Error returned by [`Builder::init`].
 #[derive(Debug)]
 pub enum InitError<DI, P> {
@@ -241,6 +383,11 @@ pub enum ConfigurationError {
     /// the controller's framebuffer. To resolve this, reduce the offset to a maximum value of
     /// [`FRAMEBUFFER_SIZE`](Model::FRAMEBUFFER_SIZE) minus [`display_size`](Builder::display_size).
     InvalidDisplayOffset,
+    /// Missing reset pin.
+    ///
+    /// A hardware reset was requested by [`Display::hard_reset`], but no reset
+    /// pin was provided by using [`reset_pin`](Builder::reset_pin).
+    MissingResetPin,
 }
 
 impl<DiError, P> From<ModelInitError<DiError>> for InitError<DiError, P> {
@@ -269,7 +416,7 @@ impl digital::ErrorType for NoResetPin {
     type Error = core::convert::Infallible;
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "ili9341"))]
 mod tests {
     use crate::{
         _mock::{MockDelay, MockDisplayInterface, MockOutputPin},
@@ -285,6 +432,233 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn tearing_effect_is_enabled_after_init() {
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .tearing_effect(TearingEffect::HorizontalAndVertical)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert_eq!(
+            display.release().0.transfers().last(),
+            Some(&Transfer::Command {
+                instruction: 0x35,
+                params: alloc::vec![0x01],
+            })
+        );
+    }
+
+    #[test]
+    fn reinit_restores_scroll_state() {
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_vertical_scroll_region(0, 20).unwrap();
+        display.set_vertical_scroll_offset(40).unwrap();
+        unsafe { display.dcs() }.clear();
+
+        display.reinit(&mut MockDelay).unwrap();
+
+        let transfers = display.release().0.take_transfers();
+        assert_eq!(
+            transfers[transfers.len() - 2..],
+            [
+                Transfer::Command {
+                    instruction: 0x33,
+                    params: alloc::vec![0, 0, 0x01, 0x2C, 0, 20],
+                },
+                Transfer::Command {
+                    instruction: 0x37,
+                    params: alloc::vec![0, 40],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn scroll_offset_wraps_inside_scroll_region() {
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        // 320 rows with a 300 row scroll region from row 10 to 309
+        display.set_vertical_scroll_region(10, 10).unwrap();
+        unsafe { display.dcs() }.clear();
+
+        for offset in [0, 299, 300, 310] {
+            display.set_vertical_scroll_offset(offset).unwrap();
+        }
+        // the whole framebuffer is fixed
+        display.set_vertical_scroll_region(200, 200).unwrap();
+        display.set_vertical_scroll_offset(50).unwrap();
+
+        let start_rows: alloc::vec::Vec<_> = display
+            .release()
+            .0
+            .take_transfers()
+            .into_iter()
+            .filter_map(|transfer| match transfer {
+                Transfer::Command {
+                    instruction: 0x37,
+                    params,
+                } => Some(u16::from_be_bytes([params[0], params[1]])),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(start_rows, [10, 309, 10, 20, 0]);
+    }
+
+    #[test]
+    fn reset_methods() {
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let soft_reset = Transfer::Command {
+            instruction: 0x01,
+            params: alloc::vec![],
+        };
+
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_brightness(10).unwrap();
+        assert_eq!(unsafe { display.dcs() }.take_transfers()[0], soft_reset);
+
+        display.soft_reset(&mut MockDelay).unwrap();
+        let transfers = unsafe { display.dcs() }.take_transfers();
+        assert_eq!(transfers[0], soft_reset);
+        assert_eq!(
+            transfers.last(),
+            Some(&Transfer::Command {
+                instruction: 0x51,
+                params: alloc::vec![10],
+            })
+        );
+
+        display.reinit(&mut MockDelay).unwrap();
+        let transfers = unsafe { display.dcs() }.take_transfers();
+        assert_ne!(transfers[0], soft_reset);
+        assert_eq!(
+            transfers.last(),
+            Some(&Transfer::Command {
+                instruction: 0x51,
+                params: alloc::vec![10],
+            })
+        );
+    }
+
+    #[cfg(feature = "ssd1331")]
+    #[test]
+    fn gamma_tables_unsupported() {
+        use crate::{gamma::GammaTablesError, models::SSD1331};
+
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(display.set_gamma_tables(&[0; 15], &[0; 15]), Ok(()));
+
+        let mut display = Builder::new(SSD1331, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(
+            display.set_gamma_tables(&[0; 15], &[0; 15]),
+            Err(GammaTablesError::Unsupported)
+        );
+    }
+
+    #[cfg(feature = "ssd1331")]
+    #[test]
+    fn aod_uses_model_hooks() {
+        use crate::{interface::RecordingInterface, models::SSD1331, options::AodMode};
+
+        let mut display = Builder::new(SSD1331, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        unsafe { display.dcs() }.clear();
+
+        let aod = AodMode::new(0, 15).idle(true).brightness(64);
+        display.enter_aod(aod, &mut MockDelay).unwrap();
+        display.exit_aod(&mut MockDelay).unwrap();
+
+        // the SSD1331 has no partial, idle or DCS brightness commands
+        assert_eq!(display.release().0.take_transfers(), []);
+    }
+
+    #[test]
+    fn aod_restores_previous_state() {
+        use crate::{interface::RecordingInterface, models::Model, options::AodMode};
+
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_brightness(200).unwrap();
+        display.set_frame_rate(70).unwrap();
+        display.set_partial_area(10, 29).unwrap();
+        unsafe { display.dcs() }.clear();
+
+        let aod = AodMode::new(100, 139)
+            .idle(true)
+            .brightness(64)
+            .frame_rate(30);
+        display.enter_aod(aod, &mut MockDelay).unwrap();
+        display.exit_aod(&mut MockDelay).unwrap();
+
+        let mut expected = RecordingInterface::new();
+        ILI9341Rgb565::set_partial_area(&mut expected, 100, 139).unwrap();
+        ILI9341Rgb565::set_idle_mode(&mut expected, true).unwrap();
+        ILI9341Rgb565::set_brightness(&mut expected, 64).unwrap();
+        ILI9341Rgb565.set_frame_rate(&mut expected, 30).unwrap();
+        ILI9341Rgb565.set_frame_rate(&mut expected, 70).unwrap();
+        ILI9341Rgb565::set_brightness(&mut expected, 200).unwrap();
+        ILI9341Rgb565::set_idle_mode(&mut expected, false).unwrap();
+        ILI9341Rgb565::set_partial_area(&mut expected, 10, 29).unwrap();
+        assert_eq!(display.release().0.transfers(), expected.transfers());
+    }
+
+    #[test]
+    fn aod_keeps_unknown_state() {
+        use crate::{interface::RecordingInterface, models::Model, options::AodMode};
+
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_idle_mode(true).unwrap();
+        unsafe { display.dcs() }.clear();
+
+        // brightness and frame rate weren't set, idle mode is already enabled
+        let aod = AodMode::new(100, 139)
+            .idle(true)
+            .brightness(64)
+            .frame_rate(30);
+        display.enter_aod(aod, &mut MockDelay).unwrap();
+        display.exit_aod(&mut MockDelay).unwrap();
+
+        let mut expected = RecordingInterface::new();
+        ILI9341Rgb565::set_partial_area(&mut expected, 100, 139).unwrap();
+        ILI9341Rgb565::exit_partial_mode(&mut expected).unwrap();
+        assert_eq!(display.release().0.transfers(), expected.transfers());
+    }
+
+    #[cfg(feature = "gc9a01")]
+    #[test]
+    fn model_default_options() {
+        use crate::models::GC9A01;
+
+        let builder = Builder::new(GC9A01, MockDisplayInterface);
+        assert_eq!(builder.options.color_order, ColorOrder::Bgr);
+        assert_eq!(builder.options.invert_colors, ColorInversion::Inverted);
+
+        let builder = builder.color_order(ColorOrder::Rgb);
+        assert_eq!(builder.options.color_order, ColorOrder::Rgb);
+
+        let builder = Builder::new(ILI9341Rgb565, MockDisplayInterface);
+        assert_eq!(builder.options.color_order, ColorOrder::Rgb);
+    }
+
     #[test]
     fn init_reset_pin() {
         let _: Display<_, _, MockOutputPin> = Builder::new(ILI9341Rgb565, MockDisplayInterface)
@@ -293,6 +667,143 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn hard_reset_requires_reset_pin() {
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert!(matches!(
+            display.hard_reset(&mut MockDelay),
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::MissingResetPin
+            ))
+        ));
+
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .reset_pin(MockOutputPin)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert!(display.has_reset_pin());
+        display.hard_reset(&mut MockDelay).unwrap();
+    }
+
+    /// Interface which counts commands and returns a fixed power mode.
+    struct CountingInterface {
+        commands: usize,
+        power_mode: u8,
+    }
+
+    impl Interface for CountingInterface {
+        type Word = u8;
+        type Error = core::convert::Infallible;
+
+        const KIND: crate::interface::InterfaceKind = crate::interface::InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            self.commands += 1;
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            _pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl ReadInterface for CountingInterface {
+        fn read_command(&mut self, _command: u8, params: &mut [u8]) -> Result<(), Self::Error> {
+            params.fill(self.power_mode);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn warm_start_skips_init() {
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .warm_start(true)
+            .orientation(Orientation::new().rotate(crate::options::Rotation::Deg90))
+            .invert_colors(ColorInversion::Inverted)
+            .tearing_effect(TearingEffect::Vertical)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert_eq!(
+            display.release().0.take_transfers(),
+            [
+                // COLMOD: 16 bit
+                Transfer::Command {
+                    instruction: 0x3A,
+                    params: alloc::vec![0x55],
+                },
+                // MADCTL: MX, MV
+                Transfer::Command {
+                    instruction: 0x36,
+                    params: alloc::vec![0x60],
+                },
+                // INVON
+                Transfer::Command {
+                    instruction: 0x21,
+                    params: alloc::vec![],
+                },
+                // TEON: V-blank only
+                Transfer::Command {
+                    instruction: 0x35,
+                    params: alloc::vec![0x00],
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "ili9488")]
+    #[test]
+    fn warm_start_checks_interface() {
+        use crate::models::ILI9488Rgb565;
+
+        // the serial interface of the ILI9488 doesn't support 16 bit colors
+        assert!(matches!(
+            Builder::new(ILI9488Rgb565, MockDisplayInterface)
+                .warm_start(true)
+                .init(&mut MockDelay),
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface
+            ))
+        ));
+    }
+
+    #[test]
+    fn init_or_resume_detects_running_controller() {
+        let di = CountingInterface {
+            commands: 0,
+            power_mode: 0x94,
+        };
+        let display = Builder::new(ILI9341Rgb565, di)
+            .init_or_resume(&mut MockDelay)
+            .unwrap();
+        // COLMOD, MADCTL and INVOFF
+        assert_eq!(display.release().0.commands, 3);
+
+        let di = CountingInterface {
+            commands: 0,
+            power_mode: 0x08,
+        };
+        let display = Builder::new(ILI9341Rgb565, di)
+            .init_or_resume(&mut MockDelay)
+            .unwrap();
+        assert!(display.release().0.commands > 1);
+    }
+
     #[test]
     fn error_too_wide() {
         assert!(matches!(
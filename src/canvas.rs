@@ -0,0 +1,380 @@
+//! Virtual canvases larger than the panel.
+//!
+//! A virtual canvas holds a scene which is larger than the panel. Only the
+//! part of the canvas inside the viewport is visible and the viewport can be
+//! moved without redrawing the scene, which makes smooth panning of maps or
+//! long menus possible.
+//!
+//! Two kinds of canvases are available:
+//!
+//! - [`VirtualCanvas`] stores the scene in a host buffer and can be panned in
+//!   both directions. Moving the viewport retransmits the visible pixels.
+//! - [`GramCanvas`] stores the scene in the unused part of the controller's
+//!   framebuffer and is panned vertically with the scroll start address,
+//!   which doesn't transmit any pixels.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+//! use mipidsi::canvas::VirtualCanvas;
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! let mut buffer = [Rgb565::BLACK; 320 * 400];
+//! let mut canvas = VirtualCanvas::new(&mut buffer, Size::new(320, 400));
+//! canvas.clear(Rgb565::BLUE).unwrap();
+//!
+//! canvas.set_viewport(40, 60);
+//! canvas.present(&mut display).unwrap();
+//! ```
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::MemoryMapping,
+    Display, Page,
+};
+
+/// Virtual canvas backed by a host buffer.
+///
+/// See the [module level documentation](self) for more information.
+pub struct VirtualCanvas<'a, C> {
+    buffer: &'a mut [C],
+    size: Size,
+    viewport: Point,
+}
+
+impl<'a, C> VirtualCanvas<'a, C>
+where
+    C: PixelColor,
+{
+    /// Creates a new virtual canvas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is smaller than `size.width * size.height` pixels.
+    pub fn new(buffer: &'a mut [C], size: Size) -> Self {
+        assert!(buffer.len() >= (size.width * size.height) as usize);
+
+        Self {
+            buffer,
+            size,
+            viewport: Point::zero(),
+        }
+    }
+
+    /// Creates a new virtual canvas.
+    ///
+    /// Returns the buffer if it is smaller than `size.width * size.height`
+    /// pixels.
+    pub fn try_new(buffer: &'a mut [C], size: Size) -> Result<Self, &'a mut [C]> {
+        if buffer.len() < (size.width * size.height) as usize {
+            return Err(buffer);
+        }
+
+        Ok(Self::new(buffer, size))
+    }
+
+    /// Sets the top left corner of the viewport in canvas coordinates.
+    ///
+    /// The viewport is clamped to the canvas when it is
+    /// [presented](Self::present).
+    pub fn set_viewport(&mut self, x: u32, y: u32) {
+        self.viewport = Point::new(x as i32, y as i32);
+    }
+
+    /// Returns the top left corner of the viewport in canvas coordinates.
+    pub fn viewport(&self) -> Point {
+        self.viewport
+    }
+
+    /// Returns the color at the given position.
+    pub fn pixel(&self, point: Point) -> Option<C> {
+        self.offset(point).map(|i| self.buffer[i])
+    }
+
+    /// Sends the part of the canvas inside the viewport to the display.
+    ///
+    /// The viewport has the size of the display and is clamped so that it
+    /// doesn't extend past the canvas. Canvases which are smaller than the
+    /// display are drawn in the top left corner.
+    pub fn present<DI, M, RST>(&self, display: &mut Display<DI, M, RST>) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model<ColorFormat = C>,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+    {
+        let display_size = display.size();
+        let width = self.size.width.min(display_size.width);
+        let height = self.size.height.min(display_size.height);
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let max_x = (self.size.width - width) as i32;
+        let max_y = (self.size.height - height) as i32;
+        let skip_x = self.viewport.x.clamp(0, max_x) as usize;
+        let skip_y = self.viewport.y.clamp(0, max_y) as usize;
+        let stride = self.size.width as usize;
+        let width = width as usize;
+
+        let colors = (0..height as usize).flat_map(|y| {
+            let start = (y + skip_y) * stride + skip_x;
+            self.buffer[start..start + width].iter().copied()
+        });
+        display.set_pixels(0, 0, width as u16 - 1, height as u16 - 1, colors)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        if !self.bounding_box().contains(point) {
+            return None;
+        }
+
+        Some(point.y as usize * self.size.width as usize + point.x as usize)
+    }
+}
+
+impl<C> DrawTarget for VirtualCanvas<'_, C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(i) = self.offset(point) {
+                self.buffer[i] = color;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let width = area.size.width as usize;
+        for y in 0..area.size.height as i32 {
+            if let Some(start) = self.offset(area.top_left + Point::new(0, y)) {
+                self.buffer[start..start + width].fill(color);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> OriginDimensions for VirtualCanvas<'_, C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// Virtual canvas backed by the controller's framebuffer.
+///
+/// The canvas covers all framebuffer rows below the display offset, which
+/// makes it taller than the panel on controllers with a larger framebuffer.
+/// The viewport is moved with the vertical scroll start address. Horizontal
+/// panning isn't supported by the controllers.
+///
+/// Gram canvases are created by [`Display::gram_canvas`].
+pub struct GramCanvas<'a, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    page: Page<'a, DI, M, RST>,
+    viewport: u16,
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Returns a virtual canvas in the controller's framebuffer.
+    ///
+    /// Returns `None` if the orientation swaps rows and columns or reverses
+    /// the row order, because the scroll start address can't be used to pan
+    /// the canvas in these orientations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::{models::ST7789, Builder};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// // 240x320 framebuffer with a 240x135 panel
+    /// let mut display = Builder::new(ST7789, di)
+    ///     .display_size(240, 135)
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    ///
+    /// let mut canvas = display.gram_canvas().unwrap();
+    /// assert_eq!(canvas.size(), Size::new(240, 320));
+    /// canvas.clear(Rgb565::BLUE).unwrap();
+    /// canvas.set_viewport(100).unwrap();
+    /// ```
+    pub fn gram_canvas(&mut self) -> Option<GramCanvas<'_, DI, M, RST>> {
+        let mapping = MemoryMapping::from(self.options.orientation);
+        if mapping.swap_rows_and_columns || mapping.reverse_rows {
+            return None;
+        }
+
+        let offset = self.address_offset();
+        let rows = self.model.framebuffer_size().1 - self.options.display_offset.1;
+        let size = Size::new(self.size().width, u32::from(rows));
+
+        Some(GramCanvas {
+            page: Page {
+                display: self,
+                offset,
+                size,
+            },
+            viewport: 0,
+        })
+    }
+}
+
+impl<DI, M, RST> GramCanvas<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Moves the viewport to the given canvas row.
+    ///
+    /// The row is clamped so that the viewport doesn't extend past the
+    /// canvas.
+    pub fn set_viewport(&mut self, y: u16) -> Result<(), DI::Error> {
+        let max = self.page.size.height - self.page.display.size().height;
+        self.viewport = y.min(max as u16);
+
+        self.page.display.set_vertical_scroll_region(0, 0)?;
+        self.page.display.set_vertical_scroll_offset(self.viewport)
+    }
+
+    /// Returns the canvas row at the top of the viewport.
+    pub fn viewport(&self) -> u16 {
+        self.viewport
+    }
+}
+
+impl<DI, M, RST> DrawTarget for GramCanvas<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.page.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.page.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.page.fill_solid(area, color)
+    }
+}
+
+impl<DI, M, RST> OriginDimensions for GramCanvas<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.page.size
+    }
+}
+
+#[cfg(all(test, feature = "ili9341", feature = "st7789"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _mock::{new_mock_display, MockDelay, MockDisplayInterface},
+        models::ST7789,
+        options::{Orientation, Rotation},
+        Builder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn virtual_canvas() {
+        let mut buffer = [Rgb565::BLACK; 4 * 3];
+        let mut canvas = VirtualCanvas::new(&mut buffer, Size::new(4, 3));
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(2, 1), Size::new(5, 5)),
+                Rgb565::RED,
+            )
+            .unwrap();
+
+        assert_eq!(canvas.pixel(Point::new(1, 1)), Some(Rgb565::BLACK));
+        assert_eq!(canvas.pixel(Point::new(3, 2)), Some(Rgb565::RED));
+        assert_eq!(canvas.pixel(Point::new(4, 2)), None);
+
+        canvas.set_viewport(1, 100);
+        canvas.present(&mut new_mock_display()).unwrap();
+        assert_eq!(canvas.viewport(), Point::new(1, 100));
+
+        assert!(VirtualCanvas::try_new(&mut [Rgb565::BLACK; 11], Size::new(4, 3)).is_err());
+    }
+
+    #[test]
+    fn gram_canvas() {
+        let mut display = Builder::new(ST7789, MockDisplayInterface)
+            .display_size(240, 135)
+            .display_offset(0, 20)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        let mut canvas = display.gram_canvas().unwrap();
+        assert_eq!(canvas.size(), Size::new(240, 300));
+        canvas.set_viewport(500).unwrap();
+        assert_eq!(canvas.viewport(), 300 - 135);
+        assert_eq!(display.scroll_offset, Some(300 - 135));
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg90))
+            .unwrap();
+        assert!(display.gram_canvas().is_none());
+    }
+}
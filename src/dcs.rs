@@ -1,5 +1,7 @@
 //! MIPI DCS commands.
 
+use embedded_hal::delay::DelayNs;
+
 use crate::interface::Interface;
 
 #[macro_use]
@@ -19,8 +21,22 @@ mod set_scroll_start;
 pub use set_scroll_start::*;
 mod set_tearing_effect;
 pub use set_tearing_effect::*;
+mod set_tear_scanline;
+pub use set_tear_scanline::*;
 mod set_invert_mode;
 pub use set_invert_mode::*;
+mod set_partial_area;
+pub use set_partial_area::*;
+mod set_display_brightness;
+pub use set_display_brightness::*;
+mod set_display_control;
+pub use set_display_control::*;
+mod set_adaptive_brightness;
+pub use set_adaptive_brightness::*;
+mod set_gamma_curve;
+pub use set_gamma_curve::*;
+mod sequence;
+pub use sequence::*;
 
 /// Common trait for DCS commands.
 ///
@@ -61,10 +77,90 @@ pub trait InterfaceExt: Interface {
     fn write_raw(&mut self, instruction: u8, param_bytes: &[u8]) -> Result<(), Self::Error> {
         self.send_command(instruction, param_bytes)
     }
+
+    /// Sends a DCS command to a display interface with 16 bit register addresses.
+    ///
+    /// The instruction code is sent as the high byte of the register address,
+    /// which is the mapping used by controllers like the NT35510.
+    fn write_wide_command(&mut self, command: impl DcsCommand) -> Result<(), Self::Error> {
+        let mut param_bytes: [u8; 16] = [0; 16];
+        let n = command.fill_params_buf(&mut param_bytes);
+        self.write_wide_raw(u16::from(command.instruction()) << 8, &param_bytes[..n])
+    }
+
+    /// Sends a raw command with a 16 bit register `address` to the display interface.
+    ///
+    /// Each byte in `param_bytes` is written to its own register, starting at
+    /// `address`.
+    fn write_wide_raw(&mut self, address: u16, param_bytes: &[u8]) -> Result<(), Self::Error> {
+        self.send_wide_command(address, param_bytes)
+    }
+
+    /// Writes a 16 bit `value` to the register with the 16 bit `index`.
+    ///
+    /// See [`Interface::send_register`] for more information.
+    fn write_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        self.send_register(index, value)
+    }
+
+    /// Writes values to consecutive 8 bit registers, starting at register `index`.
+    ///
+    /// Controllers with register index addressing, like the HX8347D, only
+    /// accept a single parameter byte per register. Larger values are split
+    /// into multiple registers, which can be written by using this method.
+    fn write_registers(&mut self, index: u8, values: &[u8]) -> Result<(), Self::Error> {
+        for (register, value) in (index..=u8::MAX).zip(values) {
+            self.write_raw(register, core::slice::from_ref(value))?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends a command sequence to the display interface.
+    ///
+    /// Command sequences can be created by using the [`dcs_seq`](crate::dcs_seq) macro.
+    fn write_sequence<D: DelayNs>(
+        &mut self,
+        sequence: &[SequenceEntry],
+        delay: &mut D,
+    ) -> Result<(), Self::Error> {
+        for entry in sequence {
+            match *entry {
+                SequenceEntry::Command {
+                    instruction,
+                    params,
+                } => self.write_raw(instruction, params)?,
+                SequenceEntry::Delay(us) => delay.delay_us(us),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: Interface> InterfaceExt for T {}
 
+/// Read Display Identification Information command.
+pub(crate) const GET_DISPLAY_ID: u8 = 0x04;
+
+/// Read Display Status command.
+pub(crate) const GET_DISPLAY_STATUS: u8 = 0x09;
+
+/// Get Scanline read command.
+pub(crate) const GET_SCANLINE: u8 = 0x45;
+
+/// Get Power Mode read command.
+pub(crate) const GET_POWER_MODE: u8 = 0x0A;
+
+/// Memory Read command.
+pub(crate) const READ_MEMORY_START: u8 = 0x2E;
+
+/// Memory Read Continue command.
+pub(crate) const READ_MEMORY_CONTINUE: u8 = 0x3E;
+
+/// Read Display Self-Diagnostic Result command.
+pub(crate) const GET_SELF_DIAGNOSTIC: u8 = 0x0F;
+
 // DCS commands that don't use any parameters
 
 dcs_basic_command!(
@@ -92,6 +188,16 @@ dcs_basic_command!(
     EnterNormalMode,
     0x13
 );
+dcs_basic_command!(
+    /// All Pixels Off
+    AllPixelsOff,
+    0x22
+);
+dcs_basic_command!(
+    /// All Pixels On
+    AllPixelsOn,
+    0x23
+);
 dcs_basic_command!(
     /// Turn Display Off
     SetDisplayOff,
@@ -128,3 +234,8 @@ dcs_basic_command!(
     WriteMemoryStart,
     0x2C
 );
+dcs_basic_command!(
+    /// Continue Framebuffer Memory Write
+    WriteMemoryContinue,
+    0x3C
+);
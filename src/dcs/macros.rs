@@ -7,7 +7,7 @@ macro_rules! dcs_basic_command {
         #[doc = $tt]
         pub struct $instr_name;
 
-        impl DcsCommand for $instr_name {
+        impl $crate::dcs::DcsCommand for $instr_name {
             fn instruction(&self) -> u8 {
                 $instr
             }
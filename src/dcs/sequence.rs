@@ -0,0 +1,92 @@
+//! Module for command sequences
+
+/// Entry in a command sequence.
+///
+/// Command sequences are usually created by using the [`dcs_seq`](crate::dcs_seq)
+/// macro and sent to the display by using
+/// [`write_sequence`](super::InterfaceExt::write_sequence).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceEntry {
+    /// Command with parameters.
+    Command {
+        /// Instruction code.
+        instruction: u8,
+        /// Parameter bytes.
+        params: &'static [u8],
+    },
+    /// Delay in µs.
+    Delay(u32),
+}
+
+/// Creates a static command sequence.
+///
+/// Each entry in the sequence is either a command with optional parameters,
+/// written as `cmd(instruction, params...)`, or a delay, written as
+/// `delay_us(duration)` or `delay_ms(duration)`. The macro expands to a
+/// `&'static [SequenceEntry]`.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{dcs::SequenceEntry, dcs_seq};
+///
+/// const INIT: &[SequenceEntry] = dcs_seq![
+///     cmd(0x11),             // SLPOUT
+///     delay_ms(120),
+///     cmd(0x3A, 0x55),       // COLMOD: 16 bit/pixel
+///     cmd(0xB2, 0x0C, 0x0C, 0x00, 0x33, 0x33),
+///     cmd(0x29),             // DISPON
+/// ];
+///
+/// assert_eq!(INIT.len(), 5);
+/// assert_eq!(INIT[1], SequenceEntry::Delay(120_000));
+/// ```
+#[macro_export]
+macro_rules! dcs_seq {
+    (@entry cmd($instruction:expr $(, $param:expr)* $(,)?)) => {
+        $crate::dcs::SequenceEntry::Command {
+            instruction: $instruction,
+            params: &[$($param),*],
+        }
+    };
+    (@entry delay_us($us:expr)) => {
+        $crate::dcs::SequenceEntry::Delay($us)
+    };
+    (@entry delay_ms($ms:expr)) => {
+        $crate::dcs::SequenceEntry::Delay(($ms) * 1_000)
+    };
+    ($($kind:ident($($arg:tt)*)),* $(,)?) => {
+        &[$($crate::dcs_seq!(@entry $kind($($arg)*))),*]
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dcs_seq_creates_entries() {
+        const SEQ: &[SequenceEntry] = dcs_seq![cmd(0x01), delay_us(5), cmd(0x2A, 0, 0, 0, 239,)];
+
+        assert_eq!(
+            SEQ,
+            &[
+                SequenceEntry::Command {
+                    instruction: 0x01,
+                    params: &[]
+                },
+                SequenceEntry::Delay(5),
+                SequenceEntry::Command {
+                    instruction: 0x2A,
+                    params: &[0, 0, 0, 239]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn dcs_seq_empty() {
+        const SEQ: &[SequenceEntry] = dcs_seq![];
+        assert!(SEQ.is_empty());
+    }
+}
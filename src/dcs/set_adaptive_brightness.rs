@@ -0,0 +1,43 @@
+//! Module for the WRCABC content adaptive brightness control instruction constructors
+
+use crate::options::AdaptiveBrightness;
+
+use super::DcsCommand;
+
+/// Set Content Adaptive Brightness Control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetAdaptiveBrightness(AdaptiveBrightness);
+
+impl SetAdaptiveBrightness {
+    /// Creates a new Set Content Adaptive Brightness Control command.
+    pub const fn new(mode: AdaptiveBrightness) -> Self {
+        Self(mode)
+    }
+}
+
+impl DcsCommand for SetAdaptiveBrightness {
+    fn instruction(&self) -> u8 {
+        0x55
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.0 as u8;
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrcabc_fills_mode() {
+        let wrcabc = SetAdaptiveBrightness::new(AdaptiveBrightness::MovingImage);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrcabc.instruction(), 0x55);
+        assert_eq!(wrcabc.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x03]);
+    }
+}
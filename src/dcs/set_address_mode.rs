@@ -71,6 +71,14 @@ impl SetAddressMode {
 
         result
     }
+
+    /// Returns the raw MADCTL value.
+    ///
+    /// Can be used by models with a non DCS command set to derive their own
+    /// address mode registers.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
 }
 
 impl DcsCommand for SetAddressMode {
@@ -0,0 +1,41 @@
+//! Module for the WRDISBV display brightness instruction constructors
+
+use super::DcsCommand;
+
+/// Set Display Brightness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetDisplayBrightness(u8);
+
+impl SetDisplayBrightness {
+    /// Creates a new Set Display Brightness command.
+    pub const fn new(brightness: u8) -> Self {
+        Self(brightness)
+    }
+}
+
+impl DcsCommand for SetDisplayBrightness {
+    fn instruction(&self) -> u8 {
+        0x51
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.0;
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrdisbv_fills_brightness() {
+        let wrdisbv = SetDisplayBrightness::new(0x80);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrdisbv.instruction(), 0x51);
+        assert_eq!(wrdisbv.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x80]);
+    }
+}
@@ -0,0 +1,47 @@
+//! Module for the WRCTRLD display control instruction constructors
+
+use crate::options::BacklightMode;
+
+use super::DcsCommand;
+
+/// Set Display Control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetDisplayControl(BacklightMode);
+
+impl SetDisplayControl {
+    /// Creates a new Set Display Control command.
+    pub const fn new(mode: BacklightMode) -> Self {
+        Self(mode)
+    }
+}
+
+impl DcsCommand for SetDisplayControl {
+    fn instruction(&self) -> u8 {
+        0x53
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = match self.0 {
+            BacklightMode::Off => 0x00,
+            BacklightMode::On => 0x24,
+            BacklightMode::Dimmed => 0x2C,
+        };
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrctrld_fills_control_bits() {
+        let wrctrld = SetDisplayControl::new(BacklightMode::Dimmed);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrctrld.instruction(), 0x53);
+        assert_eq!(wrctrld.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x2C]);
+    }
+}
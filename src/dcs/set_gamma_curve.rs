@@ -0,0 +1,43 @@
+//! Module for the GAMSET gamma curve instruction constructors
+
+use crate::options::GammaCurve;
+
+use super::DcsCommand;
+
+/// Set Gamma Curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetGammaCurve(GammaCurve);
+
+impl SetGammaCurve {
+    /// Creates a new Set Gamma Curve command.
+    pub const fn new(curve: GammaCurve) -> Self {
+        Self(curve)
+    }
+}
+
+impl DcsCommand for SetGammaCurve {
+    fn instruction(&self) -> u8 {
+        0x26
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.0 as u8;
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamset_fills_curve_bit() {
+        let gamset = SetGammaCurve::new(GammaCurve::Curve2);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(gamset.instruction(), 0x26);
+        assert_eq!(gamset.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x04]);
+    }
+}
@@ -0,0 +1,467 @@
+//! Helpers to insert extra delays after commands and to check the model
+//! configuration without running the init sequence.
+
+use core::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+};
+
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{DcsCommand, ExitSleepMode, SetDisplayOn},
+    interface::{Interface, InterfaceKind},
+    options::{CommandDelays, InitTimings},
+};
+
+/// Delay source which can be shared by multiple users.
+pub(crate) struct SharedDelay<'a, D>(&'a RefCell<&'a mut D>);
+
+impl<'a, D> SharedDelay<'a, D> {
+    pub fn new(delay: &'a RefCell<&'a mut D>) -> Self {
+        Self(delay)
+    }
+}
+
+impl<D: DelayNs> DelayNs for SharedDelay<'_, D> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.borrow_mut().delay_ns(ns)
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.0.borrow_mut().delay_us(us)
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.0.borrow_mut().delay_ms(ms)
+    }
+}
+
+/// Point of the init sequence which was reached last.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum InitPhase {
+    /// No command was sent since the reset.
+    Reset,
+    /// The given command was sent without a delay afterwards.
+    Command(u8),
+    /// The given 16 bit command was sent without a delay afterwards.
+    WideCommand(u16),
+    /// The given register was written without a delay afterwards.
+    Register(u16),
+    /// The model waited after the last command.
+    Waited,
+}
+
+impl InitPhase {
+    // Returns the DCS instruction which was sent last.
+    //
+    // Controllers with 16 bit commands, e.g. the NT35510, use the DCS
+    // instruction as the high byte of the command address.
+    fn instruction(self) -> Option<u8> {
+        match self {
+            InitPhase::Command(instruction) => Some(instruction),
+            InitPhase::WideCommand(address) if address & 0xFF == 0 => Some((address >> 8) as u8),
+            _ => None,
+        }
+    }
+}
+
+impl InitTimings {
+    // Returns the overridden delay for the given phase.
+    fn delay_for(&self, phase: InitPhase) -> Option<u32> {
+        if phase == InitPhase::Reset {
+            return self.post_reset_us;
+        }
+
+        match phase.instruction() {
+            Some(instruction) if instruction == ExitSleepMode.instruction() => {
+                self.post_sleep_out_us
+            }
+            Some(instruction) if instruction == SetDisplayOn.instruction() => {
+                self.post_display_on_us
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Delay source used by models during init, which applies [`InitTimings`]
+/// overrides.
+pub(crate) struct InitDelay<'a, D> {
+    delay: SharedDelay<'a, D>,
+    phase: &'a Cell<InitPhase>,
+    timings: InitTimings,
+}
+
+impl<'a, D: DelayNs> InitDelay<'a, D> {
+    pub fn new(
+        delay: SharedDelay<'a, D>,
+        phase: &'a Cell<InitPhase>,
+        timings: InitTimings,
+    ) -> Self {
+        Self {
+            delay,
+            phase,
+            timings,
+        }
+    }
+
+    /// Waits for an override which wasn't applied yet, because the model
+    /// didn't wait at that point.
+    pub fn finish(&mut self) {
+        if let Some(us) = self.timings.delay_for(self.phase.get()) {
+            self.delay.delay_us(us);
+        }
+        self.phase.set(InitPhase::Waited);
+    }
+
+    fn wait(&mut self, f: impl FnOnce(&mut SharedDelay<'a, D>)) {
+        match self.timings.delay_for(self.phase.get()) {
+            Some(us) => self.delay.delay_us(us),
+            None => f(&mut self.delay),
+        }
+        self.phase.set(InitPhase::Waited);
+    }
+}
+
+impl<D: DelayNs> DelayNs for InitDelay<'_, D> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.wait(|delay| delay.delay_ns(ns))
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.wait(|delay| delay.delay_us(us))
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.wait(|delay| delay.delay_ms(ms))
+    }
+}
+
+/// Interface wrapper which waits after every command.
+///
+/// The wrapper also tracks the [`InitPhase`], which is used by
+/// [`InitDelay`] to apply [`InitTimings`] overrides.
+pub(crate) struct DelayedInterface<'a, DI, D> {
+    di: &'a mut DI,
+    delay: InitDelay<'a, D>,
+    delays: CommandDelays,
+}
+
+impl<'a, DI, D> DelayedInterface<'a, DI, D> {
+    pub fn new(di: &'a mut DI, delay: InitDelay<'a, D>, delays: CommandDelays) -> Self {
+        Self { di, delay, delays }
+    }
+}
+
+impl<DI, D> Interface for DelayedInterface<'_, DI, D>
+where
+    DI: Interface,
+    D: DelayNs,
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.delay.finish();
+        self.di.send_command(command, args)?;
+
+        let delay = self.delays.delay_after(command);
+        if delay > 0 {
+            self.delay.delay.delay_us(delay);
+        }
+        self.delay.phase.set(InitPhase::Command(command));
+
+        Ok(())
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        self.delay.finish();
+        self.di.send_wide_command(command, args)?;
+
+        let delay = self.delays.delay_after_wide(command);
+        if delay > 0 {
+            self.delay.delay.delay_us(delay);
+        }
+        self.delay.phase.set(InitPhase::WideCommand(command));
+
+        Ok(())
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        self.delay.finish();
+        self.di.send_register(index, value)?;
+
+        let delay = self.delays.delay_after_wide(index);
+        if delay > 0 {
+            self.delay.delay.delay_us(delay);
+        }
+        self.delay.phase.set(InitPhase::Register(index));
+
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.di.send_pixels(pixels)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.di.send_repeated_pixel(pixel, count)
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.di.send_raw_pixels(data)
+    }
+}
+
+/// Interface which discards all transfers.
+///
+/// Has the same word type and kind as `DI`, which allows running the model
+/// init sequence to check the configuration, without sending it.
+pub(crate) struct DryRunInterface<DI>(PhantomData<DI>);
+
+impl<DI> DryRunInterface<DI> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<DI: Interface> Interface for DryRunInterface<DI> {
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_wide_command(&mut self, _command: u16, _args: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_register(&mut self, _index: u16, _value: u16) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        _pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        _pixel: [Self::Word; N],
+        _count: u32,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_raw_pixels(&mut self, _data: &[Self::Word]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Delay source which doesn't wait.
+pub(crate) struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::_mock::MockDisplayInterface;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct CountingDelay(u32);
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0 += ns / 1000;
+        }
+    }
+
+    #[test]
+    fn delays_after_commands() {
+        let mut di = MockDisplayInterface;
+        let mut delay = CountingDelay::default();
+        let cell = RefCell::new(&mut delay);
+
+        let phase = Cell::new(InitPhase::Reset);
+        let init_delay = InitDelay::new(SharedDelay::new(&cell), &phase, InitTimings::new());
+
+        let delays = CommandDelays::new(10).after(&[(0x11, 100)]);
+        let mut delayed = DelayedInterface::new(&mut di, init_delay, delays);
+        delayed.send_command(0x2A, &[]).unwrap();
+        delayed.send_command(0x11, &[]).unwrap();
+        delayed.send_repeated_pixel([0u8; 2], 10).unwrap();
+
+        assert_eq!(cell.borrow().0, 120);
+    }
+
+    #[test]
+    fn delays_after_wide_commands_and_registers() {
+        let mut di = MockDisplayInterface;
+        let mut delay = CountingDelay::default();
+        let cell = RefCell::new(&mut delay);
+
+        let phase = Cell::new(InitPhase::Reset);
+        let init_delay = InitDelay::new(SharedDelay::new(&cell), &phase, InitTimings::new());
+
+        let delays = CommandDelays::new(10)
+            .after(&[(0x11, 1_000)])
+            .after_wide(&[(0x1100, 100), (0x0007, 200)]);
+        let mut delayed = DelayedInterface::new(&mut di, init_delay, delays);
+        delayed.send_wide_command(0x2A00, &[]).unwrap();
+        assert_eq!(phase.get(), InitPhase::WideCommand(0x2A00));
+        delayed.send_wide_command(0x1100, &[]).unwrap();
+        delayed.send_register(0x0007, 0x0133).unwrap();
+        assert_eq!(phase.get(), InitPhase::Register(0x0007));
+
+        assert_eq!(cell.borrow().0, 10 + 110 + 210);
+    }
+
+    /// 16 bit bus which counts the writes to a register.
+    #[cfg(any(feature = "ili9328", feature = "nt35510"))]
+    struct RegisterCountingBus {
+        index: u16,
+        count: u32,
+    }
+
+    #[cfg(any(feature = "ili9328", feature = "nt35510"))]
+    impl crate::interface::ParallelBus for RegisterCountingBus {
+        type Word = u16;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+
+        fn write_command(&mut self, index: u16) -> Result<(), Self::Error> {
+            if index == self.index {
+                self.count += 1;
+            }
+            Ok(())
+        }
+
+        fn write_data(&mut self, _word: u16) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "ili9328")]
+    #[test]
+    fn command_delays_apply_to_register_writes() {
+        use crate::{interface::BusInterface, models::ILI9328, Builder};
+
+        let init = |delays: CommandDelays| {
+            let bus = RegisterCountingBus {
+                index: 0x07,
+                count: 0,
+            };
+            let mut delay = CountingDelay::default();
+            let display = Builder::new(ILI9328, BusInterface::new(bus))
+                .command_delays(delays)
+                .init(&mut delay)
+                .unwrap();
+            (delay.0, display.release().0.release().count)
+        };
+
+        let (base, _) = init(CommandDelays::default());
+        let (delayed, count) = init(CommandDelays::new(0).after_wide(&[(0x07, 1_000)]));
+        assert!(count > 0);
+        assert_eq!(delayed, base + count * 1_000);
+    }
+
+    #[test]
+    fn init_timings_override_model_delays() {
+        let mut di = MockDisplayInterface;
+        let mut delay = CountingDelay::default();
+        let cell = RefCell::new(&mut delay);
+        let phase = Cell::new(InitPhase::Reset);
+
+        let timings = InitTimings::new()
+            .post_reset_us(1_000)
+            .post_sleep_out_us(2_000)
+            .post_display_on_us(3_000);
+        let mut delayed = DelayedInterface::new(
+            &mut di,
+            InitDelay::new(SharedDelay::new(&cell), &phase, timings),
+            CommandDelays::default(),
+        );
+        let mut model_delay = InitDelay::new(SharedDelay::new(&cell), &phase, timings);
+
+        // replaced by the post reset override
+        model_delay.delay_us(5);
+        // not affected by any override
+        model_delay.delay_us(7);
+        delayed.send_command(0x11, &[]).unwrap();
+        // replaced by the post SLPOUT override
+        model_delay.delay_us(120_000);
+        delayed.send_command(0x29, &[]).unwrap();
+        // the post DISPON override is inserted, because the model didn't wait
+        model_delay.finish();
+
+        assert_eq!(cell.borrow().0, 1_000 + 7 + 2_000 + 3_000);
+    }
+
+    #[test]
+    fn init_timings_apply_to_wide_commands() {
+        let mut di = MockDisplayInterface;
+        let mut delay = CountingDelay::default();
+        let cell = RefCell::new(&mut delay);
+        let phase = Cell::new(InitPhase::Waited);
+
+        let timings = InitTimings::new()
+            .post_sleep_out_us(2_000)
+            .post_display_on_us(3_000);
+        let mut delayed = DelayedInterface::new(
+            &mut di,
+            InitDelay::new(SharedDelay::new(&cell), &phase, timings),
+            CommandDelays::default(),
+        );
+        let mut model_delay = InitDelay::new(SharedDelay::new(&cell), &phase, timings);
+
+        delayed.send_wide_command(0x1100, &[]).unwrap();
+        model_delay.delay_us(120_000);
+        // only the DCS instruction in the high byte is mapped
+        delayed.send_wide_command(0x1101, &[]).unwrap();
+        model_delay.delay_us(5);
+        delayed.send_wide_command(0x2900, &[]).unwrap();
+        model_delay.finish();
+
+        assert_eq!(cell.borrow().0, 2_000 + 5 + 3_000);
+    }
+
+    #[cfg(feature = "nt35510")]
+    #[test]
+    fn init_timings_override_nt35510_delays() {
+        use crate::{interface::BusInterface, models::NT35510, Builder};
+
+        let bus = RegisterCountingBus { index: 0, count: 0 };
+        let mut delay = CountingDelay::default();
+        Builder::new(NT35510, BusInterface::new(bus))
+            .init_timings(
+                InitTimings::new()
+                    .post_reset_us(1_000)
+                    .post_sleep_out_us(2_000)
+                    .post_display_on_us(3_000),
+            )
+            .init(&mut delay)
+            .unwrap();
+
+        assert_eq!(delay.0, 1_000 + 2_000 + 3_000);
+    }
+}
@@ -0,0 +1,619 @@
+//! Framebuffer support.
+//!
+//! A [`Framebuffered`] display redirects all drawing operations into a
+//! framebuffer in RAM, which is only sent to the display when
+//! [`flush`](Framebuffered::flush) or
+//! [`flush_dirty`](Framebuffered::flush_dirty) is called. This is the retained
+//! mode workflow many GUI stacks expect and prevents partially drawn frames
+//! from becoming visible.
+//!
+//! The framebuffer can be any buffer which implements `AsRef<[C]>` and
+//! `AsMut<[C]>`, e.g. a `&'static mut [C; N]` array or a `Vec<C>`.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{
+//!     pixelcolor::Rgb565,
+//!     prelude::*,
+//!     primitives::{Circle, PrimitiveStyle},
+//! };
+//!
+//! # let display = mipidsi::_mock::new_mock_display();
+//! let mut buffer = [Rgb565::BLACK; 240 * 320];
+//! let mut display = display.with_framebuffer(&mut buffer);
+//!
+//! Circle::new(Point::new(10, 10), 50)
+//!     .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+//!     .draw(&mut display)
+//!     .unwrap();
+//!
+//! // only sends the area which contains the circle
+//! display.flush_dirty().unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::RgbColor,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Display with a framebuffer.
+///
+/// See the [module level documentation](self) for more information.
+pub struct Framebuffered<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    display: Display<DI, M, RST>,
+    buffer: B,
+    dirty: Option<Rectangle>,
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Redirects all drawing operations into a framebuffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is smaller than the number of pixels of the
+    /// display.
+    pub fn with_framebuffer<B>(self, buffer: B) -> Framebuffered<DI, M, RST, B>
+    where
+        B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+    {
+        let len = self.framebuffer_len();
+        assert!(buffer.as_ref().len() >= len);
+
+        Framebuffered {
+            display: self,
+            buffer,
+            dirty: None,
+        }
+    }
+
+    /// Redirects all drawing operations into a framebuffer.
+    ///
+    /// Returns the display and the buffer if the buffer is smaller than the
+    /// number of pixels of the display.
+    #[allow(clippy::type_complexity, clippy::result_large_err)]
+    pub fn try_with_framebuffer<B>(
+        self,
+        buffer: B,
+    ) -> Result<Framebuffered<DI, M, RST, B>, (Self, B)>
+    where
+        B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+    {
+        if buffer.as_ref().len() < self.framebuffer_len() {
+            return Err((self, buffer));
+        }
+
+        Ok(self.with_framebuffer(buffer))
+    }
+
+    fn framebuffer_len(&self) -> usize {
+        let size = self.size();
+        (size.width * size.height) as usize
+    }
+}
+
+impl<DI, M, RST, B> Framebuffered<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+{
+    /// Sends the whole framebuffer to the display.
+    pub fn flush(&mut self) -> Result<(), DI::Error> {
+        self.dirty = Some(self.bounding_box());
+        self.flush_dirty()
+    }
+
+    /// Sends the area of the framebuffer which was changed since the last
+    /// flush to the display.
+    ///
+    /// The changed area is tracked as a single bounding rectangle.
+    pub fn flush_dirty(&mut self) -> Result<(), DI::Error> {
+        let Some((sx, sy, ex, ey)) = self.take_dirty_window() else {
+            return Ok(());
+        };
+
+        let colors = rows(self.buffer.as_ref(), self.size().width, sx, sy, ex, ey);
+        self.display.set_pixels(sx, sy, ex, ey, colors)
+    }
+
+    // Takes the dirty area and returns it as an inclusive address window.
+    fn take_dirty_window(&mut self) -> Option<(u16, u16, u16, u16)> {
+        let area = self.dirty.take()?;
+        let bottom_right = area.bottom_right()?;
+
+        // Unchecked casting to u16 cannot fail here because the dirty area
+        // is inside the display bounds.
+        Some((
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        ))
+    }
+
+    /// Sends all tiles of the framebuffer which changed since the last call.
+    ///
+    /// The framebuffer is divided into tiles and a hash of each tile is
+    /// compared to the hash stored in `tiles`. Only tiles with a different
+    /// hash are sent to the display, which doesn't require any bookkeeping of
+    /// changed areas and also skips areas which were redrawn with identical
+    /// content. Returns the number of sent tiles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::framebuffer::TileHashes;
+    ///
+    /// # let display = mipidsi::_mock::new_mock_display();
+    /// let mut buffer = [Rgb565::BLACK; 240 * 320];
+    /// let mut display = display.with_framebuffer(&mut buffer);
+    ///
+    /// let mut hashes = [0; 8 * 10];
+    /// let mut tiles = TileHashes::new(Size::new(30, 32), &mut hashes);
+    ///
+    /// // the first update sends all tiles
+    /// assert_eq!(display.flush_tiles(&mut tiles).unwrap(), 80);
+    ///
+    /// Pixel(Point::new(100, 100), Rgb565::RED).draw(&mut display).unwrap();
+    /// assert_eq!(display.flush_tiles(&mut tiles).unwrap(), 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tiles` doesn't contain enough hashes for the display size.
+    /// If the `no-panic` feature is enabled,
+    /// [`FlushTilesError::TooFewHashes`] is returned instead.
+    pub fn flush_tiles(
+        &mut self,
+        tiles: &mut TileHashes<'_>,
+    ) -> Result<usize, FlushTilesError<DI::Error>> {
+        let size = self.size();
+        let columns = size.width.div_ceil(tiles.tile_size.width) as usize;
+        let rows = size.height.div_ceil(tiles.tile_size.height) as usize;
+        ensure!(
+            tiles.hashes.len() >= columns * rows,
+            FlushTilesError::TooFewHashes
+        );
+
+        let mut sent = 0;
+        for row in 0..rows {
+            for column in 0..columns {
+                let top_left = Point::new(
+                    (column as u32 * tiles.tile_size.width) as i32,
+                    (row as u32 * tiles.tile_size.height) as i32,
+                );
+                let tile =
+                    Rectangle::new(top_left, tiles.tile_size).intersection(&self.bounding_box());
+
+                let hash = self.hash(&tile);
+                let stored = &mut tiles.hashes[row * columns + column];
+                if tiles.valid && *stored == hash {
+                    continue;
+                }
+                *stored = hash;
+
+                self.dirty = Some(tile);
+                self.flush_dirty().map_err(FlushTilesError::Interface)?;
+                sent += 1;
+            }
+        }
+
+        tiles.valid = true;
+        self.dirty = None;
+        Ok(sent)
+    }
+
+    /// Returns the area which was changed since the last flush.
+    pub fn dirty_area(&self) -> Option<Rectangle> {
+        self.dirty
+    }
+
+    /// Returns the color at the given position in the framebuffer.
+    pub fn pixel(&self, point: Point) -> Option<M::ColorFormat> {
+        self.offset(point).map(|i| self.buffer.as_ref()[i])
+    }
+
+    /// Returns a reference to the display.
+    pub fn display(&self) -> &Display<DI, M, RST> {
+        &self.display
+    }
+
+    /// Returns a mutable reference to the display.
+    ///
+    /// Drawing to the display directly bypasses the framebuffer and will be
+    /// overwritten by the next flush of the same area.
+    pub fn display_mut(&mut self) -> &mut Display<DI, M, RST> {
+        &mut self.display
+    }
+
+    /// Releases the display and the framebuffer.
+    pub fn release(self) -> (Display<DI, M, RST>, B) {
+        (self.display, self.buffer)
+    }
+
+    // Calculates the FNV-1a hash of the given area.
+    fn hash(&self, area: &Rectangle) -> u32 {
+        let Some(bottom_right) = area.bottom_right() else {
+            return FNV_OFFSET_BASIS;
+        };
+
+        let stride = self.size().width as usize;
+        let width = area.size.width as usize;
+        let buffer = self.buffer.as_ref();
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for y in area.top_left.y..=bottom_right.y {
+            let start = y as usize * stride + area.top_left.x as usize;
+            for color in &buffer[start..start + width] {
+                for byte in [color.r(), color.g(), color.b()] {
+                    hash = (hash ^ u32::from(byte)).wrapping_mul(FNV_PRIME);
+                }
+            }
+        }
+
+        hash
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        let size = self.size();
+        if point.x < 0
+            || point.y < 0
+            || point.x as u32 >= size.width
+            || point.y as u32 >= size.height
+        {
+            return None;
+        }
+
+        Some(point.y as usize * size.width as usize + point.x as usize)
+    }
+
+    fn mark_dirty(&mut self, area: &Rectangle) {
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+
+        // The dirty area is never zero sized, because only areas with a
+        // bottom right corner are added.
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => Rectangle::with_corners(
+                dirty.top_left.component_min(area.top_left),
+                dirty
+                    .bottom_right()
+                    .unwrap_or(dirty.top_left)
+                    .component_max(bottom_right),
+            ),
+            None => *area,
+        });
+    }
+}
+
+#[cfg(feature = "async")]
+impl<DI, M, RST, B> Framebuffered<DI, M, RST, B>
+where
+    DI: crate::interface::AsyncInterface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+{
+    /// Sends the whole framebuffer to the display asynchronously.
+    ///
+    /// Async variant of [`flush`](Self::flush).
+    pub async fn flush_async(&mut self) -> Result<(), DI::Error> {
+        self.dirty = Some(self.bounding_box());
+        self.flush_dirty_async().await
+    }
+
+    /// Sends the area of the framebuffer which was changed since the last
+    /// flush to the display asynchronously.
+    ///
+    /// Async variant of [`flush_dirty`](Self::flush_dirty).
+    pub async fn flush_dirty_async(&mut self) -> Result<(), DI::Error> {
+        let Some((sx, sy, ex, ey)) = self.take_dirty_window() else {
+            return Ok(());
+        };
+
+        let colors = rows(self.buffer.as_ref(), self.size().width, sx, sy, ex, ey);
+        self.display.set_pixels_async(sx, sy, ex, ey, colors).await
+    }
+}
+
+// Returns the colors inside the inclusive window in row major order.
+fn rows<C: Copy>(
+    buffer: &[C],
+    stride: u32,
+    sx: u16,
+    sy: u16,
+    ex: u16,
+    ey: u16,
+) -> impl Iterator<Item = C> + '_ {
+    let stride = stride as usize;
+    let width = usize::from(ex - sx) + 1;
+    (sy..=ey).flat_map(move |y| {
+        let start = usize::from(y) * stride + usize::from(sx);
+        buffer[start..start + width].iter().copied()
+    })
+}
+
+const FNV_OFFSET_BASIS: u32 = 0x811C_9DC5;
+const FNV_PRIME: u32 = 0x0100_0193;
+
+/// Error returned by [`Framebuffered::flush_tiles`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushTilesError<DI> {
+    /// Interface error.
+    Interface(DI),
+    /// The tile hashes don't contain a hash for every tile of the display.
+    TooFewHashes,
+}
+
+/// Tile hashes used by [`Framebuffered::flush_tiles`].
+///
+/// Stores one hash for each tile of the display. The number of required
+/// hashes is the number of tile columns multiplied by the number of tile
+/// rows, where partial tiles at the right and bottom edge are included.
+#[derive(Debug)]
+pub struct TileHashes<'a> {
+    tile_size: Size,
+    hashes: &'a mut [u32],
+    valid: bool,
+}
+
+impl<'a> TileHashes<'a> {
+    /// Creates new tile hashes.
+    ///
+    /// The first [`flush_tiles`](Framebuffered::flush_tiles) call sends all
+    /// tiles, because the display content is unknown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tile size is zero.
+    pub fn new(tile_size: Size, hashes: &'a mut [u32]) -> Self {
+        assert!(tile_size.width > 0 && tile_size.height > 0);
+
+        Self {
+            tile_size,
+            hashes,
+            valid: false,
+        }
+    }
+
+    /// Creates new tile hashes.
+    ///
+    /// Returns the hashes buffer if the tile size is zero.
+    pub fn try_new(tile_size: Size, hashes: &'a mut [u32]) -> Result<Self, &'a mut [u32]> {
+        if tile_size.width == 0 || tile_size.height == 0 {
+            return Err(hashes);
+        }
+
+        Ok(Self::new(tile_size, hashes))
+    }
+
+    /// Returns the tile size.
+    pub fn tile_size(&self) -> Size {
+        self.tile_size
+    }
+
+    /// Invalidates the hashes, which causes the next
+    /// [`flush_tiles`](Framebuffered::flush_tiles) call to send all tiles.
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+}
+
+impl<DI, M, RST, B> DrawTarget for Framebuffered<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+{
+    type Color = M::ColorFormat;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(i) = self.offset(point) {
+                self.buffer.as_mut()[i] = color;
+                self.mark_dirty(&Rectangle::new(point, Size::new(1, 1)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        let stride = self.size().width as usize;
+        let buffer = self.buffer.as_mut();
+        for y in area.top_left.y..=bottom_right.y {
+            let start = y as usize * stride + area.top_left.x as usize;
+            buffer[start..=start + (bottom_right.x - area.top_left.x) as usize].fill(color);
+        }
+        self.mark_dirty(&area);
+
+        Ok(())
+    }
+}
+
+impl<DI, M, RST, B> OriginDimensions for Framebuffered<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.display.size()
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::_mock::new_mock_display;
+
+    use super::*;
+
+    #[test]
+    fn dirty_area_is_tracked() {
+        let mut buffer = [Rgb565::BLACK; 240 * 320];
+        let mut display = new_mock_display().with_framebuffer(&mut buffer);
+        assert_eq!(display.dirty_area(), None);
+
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 20), Size::new(5, 5)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        display
+            .draw_iter([Pixel(Point::new(30, 2), Rgb565::GREEN)])
+            .unwrap();
+        assert_eq!(
+            display.dirty_area(),
+            Some(Rectangle::with_corners(
+                Point::new(10, 2),
+                Point::new(30, 24)
+            ))
+        );
+        assert_eq!(display.pixel(Point::new(12, 22)), Some(Rgb565::RED));
+        assert_eq!(display.pixel(Point::new(30, 2)), Some(Rgb565::GREEN));
+
+        display.flush_dirty().unwrap();
+        assert_eq!(display.dirty_area(), None);
+    }
+
+    #[test]
+    fn unchanged_tiles_are_skipped() {
+        let mut buffer = [Rgb565::BLACK; 240 * 320];
+        let mut display = new_mock_display().with_framebuffer(&mut buffer);
+        let mut hashes = [0; 5 * 7];
+        let mut tiles = TileHashes::new(Size::new(50, 50), &mut hashes);
+
+        assert_eq!(display.flush_tiles(&mut tiles).unwrap(), 35);
+        assert_eq!(display.flush_tiles(&mut tiles).unwrap(), 0);
+
+        // redrawing identical content doesn't send the tile
+        display.clear(Rgb565::BLACK).unwrap();
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(45, 310), Size::new(10, 10)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        assert_eq!(display.flush_tiles(&mut tiles).unwrap(), 2);
+        assert_eq!(display.dirty_area(), None);
+
+        tiles.invalidate();
+        assert_eq!(display.flush_tiles(&mut tiles).unwrap(), 35);
+    }
+
+    #[test]
+    fn zero_tile_size() {
+        let mut hashes = [0; 4];
+        assert!(TileHashes::try_new(Size::new(0, 10), &mut hashes).is_err());
+        assert!(TileHashes::try_new(Size::new(10, 10), &mut hashes).is_ok());
+    }
+
+    #[cfg(feature = "no-panic")]
+    #[test]
+    fn too_few_hashes_is_error() {
+        let mut buffer = [Rgb565::BLACK; 240 * 320];
+        let mut display = new_mock_display().with_framebuffer(&mut buffer);
+        let mut hashes = [0; 5 * 7 - 1];
+        let mut tiles = TileHashes::new(Size::new(50, 50), &mut hashes);
+
+        assert_eq!(
+            display.flush_tiles(&mut tiles),
+            Err(FlushTilesError::TooFewHashes)
+        );
+    }
+
+    #[cfg(not(feature = "no-panic"))]
+    #[test]
+    #[should_panic]
+    fn too_few_hashes_panics() {
+        let mut buffer = [Rgb565::BLACK; 240 * 320];
+        let mut display = new_mock_display().with_framebuffer(&mut buffer);
+        let mut hashes = [0; 5 * 7 - 1];
+        let mut tiles = TileHashes::new(Size::new(50, 50), &mut hashes);
+
+        let _ = display.flush_tiles(&mut tiles);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn flush_dirty_async() {
+        use crate::{
+            _mock::MockDelay,
+            interface::{block_on, RecordingInterface, Transfer},
+            models::ILI9341Rgb565,
+            Builder,
+        };
+
+        let display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        let mut buffer = [Rgb565::BLACK; 240 * 320];
+        let mut display = display.with_framebuffer(&mut buffer);
+        unsafe { display.display_mut().dcs() }.clear();
+
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 20), Size::new(2, 1)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        block_on(display.flush_dirty_async()).unwrap();
+        assert_eq!(display.dirty_area(), None);
+
+        let (display, _) = display.release();
+        assert_eq!(
+            display.release().0.take_transfers().last(),
+            Some(&Transfer::Pixels(alloc::vec![0xF8, 0x00, 0xF8, 0x00]))
+        );
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let buffer = [Rgb565::BLACK; 100];
+        assert!(new_mock_display().try_with_framebuffer(buffer).is_err());
+    }
+}
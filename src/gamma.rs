@@ -0,0 +1,271 @@
+//! Gamma precompensation.
+//!
+//! Images created with desktop tools are encoded for sRGB displays with a
+//! gamma of about 2.2. Many small TFT panels have a different native transfer
+//! curve, which makes sRGB content look washed out. A [`Gamma`] lookup table
+//! precompensates the colors for the panel before they are sent to the
+//! display.
+//!
+//! The correction is applied by drawing to the [`GammaCorrected`] draw target
+//! returned by [`Gamma::correct`] instead of drawing to the display directly.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{
+//!     pixelcolor::Rgb565,
+//!     prelude::*,
+//!     primitives::{PrimitiveStyle, Rectangle},
+//! };
+//! use mipidsi::gamma::Gamma;
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! // panel with a native gamma of 1.8
+//! let gamma = Gamma::srgb_to_panel(1.8);
+//!
+//! Rectangle::new(Point::zero(), Size::new(100, 100))
+//!     .into_styled(PrimitiveStyle::with_fill(Rgb565::new(16, 32, 16)))
+//!     .draw(&mut gamma.correct(&mut display))
+//!     .unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Dimensions,
+    pixelcolor::{Rgb565, Rgb666, Rgb888, RgbColor},
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// Error returned by [`Display::set_gamma_tables`](crate::Display::set_gamma_tables).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GammaTablesError<DI> {
+    /// Interface error.
+    Interface(DI),
+    /// The controller doesn't support gamma correction tables.
+    Unsupported,
+}
+
+/// Gamma of sRGB encoded content.
+pub const SRGB_GAMMA: f32 = 2.2;
+
+/// Gamma lookup table.
+///
+/// The table maps 8 bit channel values to precompensated 8 bit channel
+/// values. Colors with a different channel depth are scaled to 8 bits before
+/// the lookup and back to their channel depth afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gamma {
+    lut: [u8; 256],
+}
+
+impl Gamma {
+    /// Creates a gamma correction from a lookup table.
+    pub const fn from_lut(lut: [u8; 256]) -> Self {
+        Self { lut }
+    }
+
+    /// Creates a gamma correction which raises the normalized channel values
+    /// to the power of `exponent`.
+    ///
+    /// Values of `exponent` which aren't positive are treated like `1.0`,
+    /// which doesn't change the colors.
+    pub fn from_exponent(exponent: f32) -> Self {
+        let exponent = if exponent > 0.0 { exponent } else { 1.0 };
+
+        let mut lut = [0; 256];
+        for (i, value) in lut.iter_mut().enumerate() {
+            let x = i as f32 / 255.0;
+            *value = (powf(x, exponent) * 255.0 + 0.5) as u8;
+        }
+
+        Self { lut }
+    }
+
+    /// Creates a gamma correction which precompensates sRGB content for a
+    /// panel with the given native gamma.
+    pub fn srgb_to_panel(panel_gamma: f32) -> Self {
+        Self::from_exponent(SRGB_GAMMA / panel_gamma)
+    }
+
+    /// Returns the lookup table.
+    pub fn lut(&self) -> &[u8; 256] {
+        &self.lut
+    }
+
+    /// Applies the gamma correction to a color.
+    pub fn apply<C: FromRgb>(&self, color: C) -> C {
+        C::from_rgb(
+            self.apply_channel(color.r(), C::MAX_R),
+            self.apply_channel(color.g(), C::MAX_G),
+            self.apply_channel(color.b(), C::MAX_B),
+        )
+    }
+
+    /// Returns a draw target which applies the gamma correction to all
+    /// colors drawn to `target`.
+    pub fn correct<'a, T>(&'a self, target: &'a mut T) -> GammaCorrected<'a, T>
+    where
+        T: DrawTarget,
+        T::Color: FromRgb,
+    {
+        GammaCorrected {
+            target,
+            gamma: self,
+        }
+    }
+
+    fn apply_channel(&self, value: u8, max: u8) -> u8 {
+        let max = u16::from(max);
+        let value = (u16::from(value) * 255 + max / 2) / max;
+        let value = u16::from(self.lut[usize::from(value)]);
+        ((value * max + 127) / 255) as u8
+    }
+}
+
+/// Color which can be created from its channel values.
+pub trait FromRgb: RgbColor {
+    /// Creates a color from the given channel values.
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self;
+}
+
+macro_rules! impl_gamma_color {
+    ($($color:ty),*) => {
+        $(
+            impl FromRgb for $color {
+                fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+                    Self::new(r, g, b)
+                }
+            }
+        )*
+    };
+}
+
+impl_gamma_color!(Rgb565, Rgb666, Rgb888);
+
+/// Draw target which applies a gamma correction.
+///
+/// Created by [`Gamma::correct`].
+pub struct GammaCorrected<'a, T> {
+    target: &'a mut T,
+    gamma: &'a Gamma,
+}
+
+impl<T> DrawTarget for GammaCorrected<'_, T>
+where
+    T: DrawTarget,
+    T::Color: FromRgb,
+{
+    type Color = T::Color;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let gamma = self.gamma;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, gamma.apply(color))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let gamma = self.gamma;
+        self.target
+            .fill_contiguous(area, colors.into_iter().map(|color| gamma.apply(color)))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, self.gamma.apply(color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(self.gamma.apply(color))
+    }
+}
+
+impl<T: Dimensions> Dimensions for GammaCorrected<'_, T> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+// `f32::powf` isn't available in `no_std`. The fractional part of the
+// exponent is approximated by repeated square roots, which is precise enough
+// for 8 bit lookup tables.
+fn powf(x: f32, exponent: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    let int = exponent as u32;
+    let mut frac = exponent - int as f32;
+
+    let mut result = 1.0;
+    for _ in 0..int {
+        result *= x;
+    }
+
+    let mut root = x;
+    for _ in 0..16 {
+        root = sqrt(root);
+        frac *= 2.0;
+        if frac >= 1.0 {
+            result *= root;
+            frac -= 1.0;
+        }
+    }
+
+    result
+}
+
+fn sqrt(x: f32) -> f32 {
+    let mut y = if x < 1.0 { 1.0 } else { x };
+    for _ in 0..16 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponent_lut() {
+        let identity = Gamma::from_exponent(1.0);
+        assert!(identity
+            .lut()
+            .iter()
+            .enumerate()
+            .all(|(i, v)| i == *v as usize));
+
+        let square = Gamma::from_exponent(2.0);
+        assert_eq!(square.lut()[128], 64);
+        assert_eq!(square.lut()[255], 255);
+
+        let root = Gamma::from_exponent(0.5);
+        assert_eq!(root.lut()[64], 128);
+        assert_eq!(root.lut()[0], 0);
+    }
+
+    #[test]
+    fn apply_scales_channels() {
+        let gamma = Gamma::from_exponent(2.0);
+
+        assert_eq!(gamma.apply(Rgb565::WHITE), Rgb565::WHITE);
+        assert_eq!(gamma.apply(Rgb565::BLACK), Rgb565::BLACK);
+        // half intensity is reduced to a quarter
+        assert_eq!(gamma.apply(Rgb565::new(16, 32, 16)), Rgb565::new(8, 16, 8));
+        assert_eq!(
+            gamma.apply(Rgb888::new(128, 0, 255)),
+            Rgb888::new(64, 0, 255)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Anti-aliased glyph drawing.
+//!
+//! Displays driven by this crate are usually write-only, which means that
+//! anti-aliased text can't be blended with the current framebuffer content.
+//! The types in this module blend coverage based glyph bitmaps with a known
+//! background color instead, while the pixels are streamed to the display.
+//!
+//! Glyph bitmaps store one 8 bit coverage value per pixel, in row-major
+//! order. A coverage of 0 results in the background color and a coverage of
+//! 255 in the foreground color.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+//! use mipidsi::glyph::{Glyph, GlyphBitmap};
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! const DOT: GlyphBitmap = GlyphBitmap {
+//!     size: Size::new(3, 3),
+//!     advance: 4,
+//!     coverage: &[
+//!         64, 192, 64, //
+//!         192, 255, 192, //
+//!         64, 192, 64, //
+//!     ],
+//! };
+//!
+//! Glyph::new(&DOT, Point::new(10, 10), Rgb565::WHITE, Rgb565::BLUE)
+//!     .draw(&mut display)
+//!     .unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    primitives::Rectangle,
+    Drawable,
+};
+
+use crate::gamma::FromRgb;
+
+/// Glyph bitmap with 8 bit coverage values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphBitmap<'a> {
+    /// Size of the bitmap.
+    pub size: Size,
+    /// Horizontal distance to the next glyph in pixels.
+    pub advance: u32,
+    /// Coverage values in row-major order.
+    pub coverage: &'a [u8],
+}
+
+/// Source of glyph bitmaps.
+pub trait GlyphSource {
+    /// Returns the glyph for the given character.
+    ///
+    /// Returns `None` if the font doesn't contain the character.
+    fn glyph(&self, c: char) -> Option<GlyphBitmap<'_>>;
+}
+
+/// Blends the foreground and background color.
+///
+/// A `coverage` of 0 returns the background color and a `coverage` of 255
+/// the foreground color.
+pub fn blend<C: FromRgb>(foreground: C, background: C, coverage: u8) -> C {
+    let alpha = u16::from(coverage);
+    let mix = |fg: u8, bg: u8| {
+        ((u16::from(fg) * alpha + u16::from(bg) * (255 - alpha) + 127) / 255) as u8
+    };
+
+    C::from_rgb(
+        mix(foreground.r(), background.r()),
+        mix(foreground.g(), background.g()),
+        mix(foreground.b(), background.b()),
+    )
+}
+
+/// Anti-aliased glyph.
+///
+/// The whole glyph bitmap is drawn, pixels which aren't covered by the glyph
+/// are filled with the background color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph<'a, C> {
+    bitmap: &'a GlyphBitmap<'a>,
+    position: Point,
+    foreground: C,
+    background: C,
+}
+
+impl<'a, C: FromRgb> Glyph<'a, C> {
+    /// Creates a new glyph with the top left corner at `position`.
+    pub const fn new(
+        bitmap: &'a GlyphBitmap<'a>,
+        position: Point,
+        foreground: C,
+        background: C,
+    ) -> Self {
+        Self {
+            bitmap,
+            position,
+            foreground,
+            background,
+        }
+    }
+}
+
+impl<C: FromRgb> Drawable for Glyph<'_, C> {
+    type Color = C;
+    type Output = Point;
+
+    /// Draws the glyph and returns the position of the next glyph.
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        draw_bitmap(
+            target,
+            self.bitmap,
+            self.position,
+            self.foreground,
+            self.background,
+        )
+    }
+}
+
+/// Anti-aliased text.
+///
+/// Characters which aren't contained in the font are skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Text<'a, F, C> {
+    text: &'a str,
+    font: &'a F,
+    position: Point,
+    foreground: C,
+    background: C,
+}
+
+impl<'a, F: GlyphSource, C: FromRgb> Text<'a, F, C> {
+    /// Creates a new text with the top left corner at `position`.
+    pub const fn new(
+        text: &'a str,
+        font: &'a F,
+        position: Point,
+        foreground: C,
+        background: C,
+    ) -> Self {
+        Self {
+            text,
+            font,
+            position,
+            foreground,
+            background,
+        }
+    }
+}
+
+impl<F: GlyphSource, C: FromRgb> Drawable for Text<'_, F, C> {
+    type Color = C;
+    type Output = Point;
+
+    /// Draws the text and returns the position after the last glyph.
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut position = self.position;
+        for c in self.text.chars() {
+            if let Some(bitmap) = self.font.glyph(c) {
+                position =
+                    draw_bitmap(target, &bitmap, position, self.foreground, self.background)?;
+            }
+        }
+
+        Ok(position)
+    }
+}
+
+// Draws a glyph bitmap and returns the position of the next glyph.
+fn draw_bitmap<D, C>(
+    target: &mut D,
+    bitmap: &GlyphBitmap<'_>,
+    position: Point,
+    foreground: C,
+    background: C,
+) -> Result<Point, D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: FromRgb,
+{
+    target.fill_contiguous(
+        &Rectangle::new(position, bitmap.size),
+        bitmap
+            .coverage
+            .iter()
+            .map(|coverage| blend(foreground, background, *coverage)),
+    )?;
+
+    Ok(position + Point::new(bitmap.advance as i32, 0))
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, Rgb888, RgbColor};
+
+    use crate::_mock::new_mock_display;
+
+    use super::*;
+
+    #[test]
+    fn blend_colors() {
+        assert_eq!(blend(Rgb565::WHITE, Rgb565::BLUE, 255), Rgb565::WHITE);
+        assert_eq!(blend(Rgb565::WHITE, Rgb565::BLUE, 0), Rgb565::BLUE);
+        assert_eq!(
+            blend(Rgb888::WHITE, Rgb888::BLACK, 128),
+            Rgb888::new(128, 128, 128)
+        );
+    }
+
+    struct Font;
+
+    impl GlyphSource for Font {
+        fn glyph(&self, c: char) -> Option<GlyphBitmap<'_>> {
+            (c == 'a').then_some(GlyphBitmap {
+                size: Size::new(2, 2),
+                advance: 3,
+                coverage: &[0, 128, 255, 128],
+            })
+        }
+    }
+
+    #[test]
+    fn text_advances() {
+        let mut display = new_mock_display();
+
+        let text = Text::new("a?a", &Font, Point::new(1, 2), Rgb565::WHITE, Rgb565::BLACK);
+        assert_eq!(text.draw(&mut display).unwrap(), Point::new(7, 2));
+    }
+}
@@ -133,17 +133,34 @@ where
 
 impl BitsPerPixel {
     /// Returns the bits per pixel for a embedded-graphics [`RgbColor`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the color format isn't supported by the MIPI DCS pixel
+    /// format, use [`try_from_rgb_color`](Self::try_from_rgb_color) to handle
+    /// unsupported color formats.
     pub const fn from_rgb_color<C: RgbColor>() -> Self {
+        match Self::try_from_rgb_color::<C>() {
+            Some(bpp) => bpp,
+            None => panic!("invalid RgbColor bits per pixel"),
+        }
+    }
+
+    /// Returns the bits per pixel for a embedded-graphics [`RgbColor`].
+    ///
+    /// Returns `None` if the color format isn't supported by the MIPI DCS
+    /// pixel format.
+    pub const fn try_from_rgb_color<C: RgbColor>() -> Option<Self> {
         let bpp = C::MAX_R.trailing_ones() + C::MAX_G.trailing_ones() + C::MAX_B.trailing_ones();
 
         match bpp {
-            3 => Self::Three,
-            8 => Self::Eight,
-            12 => Self::Twelve,
-            16 => Self::Sixteen,
-            18 => Self::Eighteen,
-            24 => Self::TwentyFour,
-            _ => panic!("invalid RgbColor bits per pixel"),
+            3 => Some(Self::Three),
+            8 => Some(Self::Eight),
+            12 => Some(Self::Twelve),
+            16 => Some(Self::Sixteen),
+            18 => Some(Self::Eighteen),
+            24 => Some(Self::TwentyFour),
+            _ => None,
         }
     }
 }
@@ -239,6 +256,15 @@ mod test {
         BitsPerPixel::from_rgb_color::<Rgb555>();
     }
 
+    #[test]
+    fn bpp_try_from_rgb_color() {
+        assert_eq!(
+            BitsPerPixel::try_from_rgb_color::<Rgb565>(),
+            Some(BitsPerPixel::Sixteen)
+        );
+        assert_eq!(BitsPerPixel::try_from_rgb_color::<Rgb555>(), None);
+    }
+
     #[test]
     fn take_skip_iter() {
         let mut iter = TakeSkip::new(0..11, 3, 2);
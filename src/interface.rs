@@ -1,12 +1,89 @@
 //! Interface traits and implementations
 
 mod spi;
-use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666, RgbColor};
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666, Rgb888, RgbColor};
 pub use spi::*;
 
+mod spi_16bit;
+pub use spi_16bit::*;
+
+mod spi_bus;
+pub use spi_bus::*;
+
+mod spi_3wire;
+pub use spi_3wire::*;
+
+#[cfg(feature = "async")]
+mod spi_async;
+#[cfg(feature = "async")]
+pub use spi_async::*;
+
+#[cfg(feature = "display-interface")]
+mod adapter;
+#[cfg(feature = "display-interface")]
+pub use adapter::*;
+
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "async")]
+pub use asynch::*;
+
+mod bus;
+pub use bus::*;
+
+#[cfg(feature = "async")]
+mod bus_async;
+#[cfg(feature = "async")]
+pub use bus_async::*;
+
+mod chunked;
+pub use chunked::*;
+
+mod crc;
+pub use crc::*;
+
+#[cfg(feature = "fsmc")]
+mod fsmc;
+#[cfg(feature = "fsmc")]
+pub use fsmc::*;
+
 mod parallel;
 pub use parallel::*;
 
+mod ping_pong;
+pub use ping_pong::*;
+
+mod port;
+pub use port::*;
+
+mod qspi;
+pub use qspi::*;
+
+#[cfg(feature = "rp2040")]
+mod rp2040_pio;
+#[cfg(feature = "rp2040")]
+pub use rp2040_pio::*;
+
+#[cfg(any(test, feature = "test-util"))]
+mod recording;
+#[cfg(any(test, feature = "test-util"))]
+pub use recording::*;
+
+mod sc18is602;
+pub use sc18is602::*;
+
+mod shift_register;
+pub use shift_register::*;
+
+mod ssd2825;
+pub use ssd2825::*;
+
+mod stats;
+pub use stats::*;
+
+mod tee;
+pub use tee::*;
+
 /// Command and pixel interface
 pub trait Interface {
     /// The native width of the interface
@@ -25,6 +102,45 @@ pub trait Interface {
     /// Send a command with optional parameters
     fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error>;
 
+    /// Send a command with a 16 bit register address
+    ///
+    /// Controllers like the NT35510 use 16 bit register addresses and each
+    /// parameter is written to its own register. The parameters are written
+    /// to consecutive addresses starting with `command`.
+    ///
+    /// The default implementation sends each address as two 8 bit command
+    /// words, high byte first. Interfaces with 16 bit words send the address
+    /// in a single command word.
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        let [high, low] = command.to_be_bytes();
+        if args.is_empty() {
+            self.send_command(high, &[])?;
+            return self.send_command(low, &[]);
+        }
+
+        for (address, arg) in (command..).zip(args) {
+            let [high, low] = address.to_be_bytes();
+            self.send_command(high, &[])?;
+            self.send_command(low, &[*arg])?;
+        }
+        Ok(())
+    }
+
+    /// Write a 16 bit value to a register with a 16 bit index
+    ///
+    /// Legacy controllers like the ILI9328 or SPFD5408 don't use DCS commands,
+    /// instead each register is selected by a 16 bit index and holds a
+    /// single 16 bit value.
+    ///
+    /// The default implementation sends the index as two 8 bit command words
+    /// and the value as two 8 bit data words, high byte first. Interfaces with
+    /// 16 bit words send the index and value in a single word each.
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        let [high, low] = index.to_be_bytes();
+        self.send_command(high, &[])?;
+        self.send_command(low, &value.to_be_bytes())
+    }
+
     /// Send a sequence of pixels
     ///
     /// `WriteMemoryStart` must be sent before calling this function
@@ -41,6 +157,19 @@ pub trait Interface {
         pixel: [Self::Word; N],
         count: u32,
     ) -> Result<(), Self::Error>;
+
+    /// Send pixel data which is already in the format of the interface
+    ///
+    /// `WriteMemoryStart` must be sent before calling this function.
+    ///
+    /// The `data` isn't converted and must contain the pixels in the format
+    /// which is sent to the display, e.g. Rgb565 pixels as big endian byte
+    /// pairs for 8 bit interfaces. Interfaces which can send a slice
+    /// directly, like [`SpiInterface`], override this method to skip copying
+    /// the data into their buffer.
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.send_pixels(data.iter().map(|word| [*word]))
+    }
 }
 
 impl<T: Interface> Interface for &mut T {
@@ -53,6 +182,14 @@ impl<T: Interface> Interface for &mut T {
         T::send_command(self, command, args)
     }
 
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        T::send_wide_command(self, command, args)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        T::send_register(self, index, value)
+    }
+
     fn send_pixels<const N: usize>(
         &mut self,
         pixels: impl IntoIterator<Item = [Self::Word; N]>,
@@ -67,6 +204,76 @@ impl<T: Interface> Interface for &mut T {
     ) -> Result<(), Self::Error> {
         T::send_repeated_pixel(self, pixel, count)
     }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        T::send_raw_pixels(self, data)
+    }
+}
+
+/// Read capable interface
+///
+/// Implemented by interfaces which can read data back from the display
+/// controller, e.g. to read status registers.
+pub trait ReadInterface: Interface {
+    /// Sends a read command and reads the returned parameters into `params`.
+    ///
+    /// Dummy reads or dummy clock cycles required by the physical interface
+    /// are handled by the implementation and aren't included in `params`.
+    /// Serial interfaces use the MIPI DCS default of a single dummy clock
+    /// cycle for reads of more than one byte, see
+    /// [`read_command_with_dummy_cycles`](Self::read_command_with_dummy_cycles).
+    fn read_command(&mut self, command: u8, params: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Sends a read command with `dummy_cycles` dummy clock cycles before the
+    /// returned parameters and reads the parameters into `params`.
+    ///
+    /// Serial interfaces skip the dummy clock cycles and realign the
+    /// parameters. The number of dummy cycles is controller and command
+    /// specific, see [`Model::read_dummy_cycles`](crate::models::Model::read_dummy_cycles).
+    ///
+    /// The default implementation ignores `dummy_cycles` and uses
+    /// `read_command`, which is correct for interfaces without dummy clock
+    /// cycles, like parallel interfaces.
+    fn read_command_with_dummy_cycles(
+        &mut self,
+        command: u8,
+        dummy_cycles: u8,
+        params: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let _ = dummy_cycles;
+        self.read_command(command, params)
+    }
+
+    /// Sends a memory read command and reads the returned data into `data`.
+    ///
+    /// Unlike [`read_command`](Self::read_command) the data isn't realigned
+    /// for the dummy cycles of register reads. Memory reads start with
+    /// controller specific dummy bytes, which are included in `data`.
+    ///
+    /// The default implementation uses `read_command`, interfaces which
+    /// insert dummy cycles for register reads override this method.
+    fn read_memory(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.read_command(command, data)
+    }
+}
+
+impl<T: ReadInterface> ReadInterface for &mut T {
+    fn read_command(&mut self, command: u8, params: &mut [u8]) -> Result<(), Self::Error> {
+        T::read_command(self, command, params)
+    }
+
+    fn read_command_with_dummy_cycles(
+        &mut self,
+        command: u8,
+        dummy_cycles: u8,
+        params: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        T::read_command_with_dummy_cycles(self, command, dummy_cycles, params)
+    }
+
+    fn read_memory(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        T::read_memory(self, command, data)
+    }
 }
 
 fn rgb565_to_bytes(pixel: Rgb565) -> [u8; 2] {
@@ -80,6 +287,9 @@ fn rgb565_to_u16(pixel: Rgb565) -> [u16; 1] {
 fn rgb666_to_bytes(pixel: Rgb666) -> [u8; 3] {
     [pixel.r(), pixel.g(), pixel.b()].map(|x| x << 2)
 }
+fn rgb888_to_bytes(pixel: Rgb888) -> [u8; 3] {
+    [pixel.r(), pixel.g(), pixel.b()]
+}
 
 /// This is an implementation detail, it should not be implemented or used outside this crate
 pub trait InterfacePixelFormat<Word> {
@@ -100,6 +310,23 @@ pub trait InterfacePixelFormat<Word> {
         pixel: Self,
         count: u32,
     ) -> Result<(), DI::Error>;
+
+    #[cfg(feature = "async")]
+    #[doc(hidden)]
+    #[allow(async_fn_in_trait)]
+    async fn send_pixels_async<DI: AsyncInterface<Word = Word>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error>;
+
+    #[cfg(feature = "async")]
+    #[doc(hidden)]
+    #[allow(async_fn_in_trait)]
+    async fn send_repeated_pixel_async<DI: AsyncInterface<Word = Word>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error>;
 }
 
 impl InterfacePixelFormat<u8> for Rgb565 {
@@ -117,6 +344,25 @@ impl InterfacePixelFormat<u8> for Rgb565 {
     ) -> Result<(), DI::Error> {
         di.send_repeated_pixel(rgb565_to_bytes(pixel), count)
     }
+
+    #[cfg(feature = "async")]
+    async fn send_pixels_async<DI: AsyncInterface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_async(pixels.into_iter().map(rgb565_to_bytes))
+            .await
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_repeated_pixel_async<DI: AsyncInterface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel_async(rgb565_to_bytes(pixel), count)
+            .await
+    }
 }
 
 impl InterfacePixelFormat<u8> for Rgb666 {
@@ -134,6 +380,61 @@ impl InterfacePixelFormat<u8> for Rgb666 {
     ) -> Result<(), DI::Error> {
         di.send_repeated_pixel(rgb666_to_bytes(pixel), count)
     }
+
+    #[cfg(feature = "async")]
+    async fn send_pixels_async<DI: AsyncInterface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_async(pixels.into_iter().map(rgb666_to_bytes))
+            .await
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_repeated_pixel_async<DI: AsyncInterface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel_async(rgb666_to_bytes(pixel), count)
+            .await
+    }
+}
+
+impl InterfacePixelFormat<u8> for Rgb888 {
+    fn send_pixels<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(pixels.into_iter().map(rgb888_to_bytes))
+    }
+
+    fn send_repeated_pixel<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb888_to_bytes(pixel), count)
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_pixels_async<DI: AsyncInterface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_async(pixels.into_iter().map(rgb888_to_bytes))
+            .await
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_repeated_pixel_async<DI: AsyncInterface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel_async(rgb888_to_bytes(pixel), count)
+            .await
+    }
 }
 
 impl InterfacePixelFormat<u16> for Rgb565 {
@@ -151,6 +452,25 @@ impl InterfacePixelFormat<u16> for Rgb565 {
     ) -> Result<(), DI::Error> {
         di.send_repeated_pixel(rgb565_to_u16(pixel), count)
     }
+
+    #[cfg(feature = "async")]
+    async fn send_pixels_async<DI: AsyncInterface<Word = u16>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_async(pixels.into_iter().map(rgb565_to_u16))
+            .await
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_repeated_pixel_async<DI: AsyncInterface<Word = u16>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel_async(rgb565_to_u16(pixel), count)
+            .await
+    }
 }
 
 /// Interface kind.
@@ -166,6 +486,12 @@ pub enum InterfaceKind {
     /// distinguish between data and command words.
     Serial4Line,
 
+    /// Serial interface without data/command pin.
+    ///
+    /// SPI style interface with 9 bits per word, the first bit of each word
+    /// distinguishes between data and command words.
+    Serial3Line,
+
     /// 8 bit parallel interface.
     ///
     /// 8080 style parallel interface with 8 data pins and chip select, write enable,
@@ -0,0 +1,351 @@
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+use super::{Interface, InterfaceKind};
+
+/// Adapter which uses a `display-interface` implementation as [`Interface`].
+///
+/// Allows existing [`WriteOnlyDataCommand`] implementations, e.g. interface
+/// drivers of HALs or of other display driver crates, to be used with this
+/// crate. The wrapped implementation must send 8 bit words, the interface
+/// reports [`InterfaceKind::Serial4Line`].
+///
+/// This adapter is only available if the `display-interface` feature is
+/// enabled.
+///
+/// # Examples
+///
+/// ```ignore
+/// use display_interface_spi::SPIInterface;
+/// use mipidsi::{interface::DisplayInterfaceAdapter, models::ST7789, Builder};
+///
+/// let di = DisplayInterfaceAdapter::new(SPIInterface::new(spi, dc));
+/// let mut display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// ```
+pub struct DisplayInterfaceAdapter<DI> {
+    di: DI,
+}
+
+impl<DI: WriteOnlyDataCommand> DisplayInterfaceAdapter<DI> {
+    /// Creates a new adapter.
+    pub fn new(di: DI) -> Self {
+        Self { di }
+    }
+
+    /// Consumes the adapter and returns the wrapped interface.
+    pub fn release(self) -> DI {
+        self.di
+    }
+}
+
+impl<DI: WriteOnlyDataCommand> Interface for DisplayInterfaceAdapter<DI> {
+    type Word = u8;
+    type Error = DisplayError;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.di.send_commands(DataFormat::U8(&[command]))?;
+        self.di.send_data(DataFormat::U8(args))
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut bytes = pixels.into_iter().flatten();
+        self.di.send_data(DataFormat::U8Iter(&mut bytes))
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.send_pixels((0..count).map(|_| pixel))
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.di.send_data(DataFormat::U8(data))
+    }
+}
+
+/// Memory write commands, which are followed by pixel data.
+const MEMORY_WRITE_COMMANDS: [u8; 2] = [0x2C, 0x3C];
+
+/// Maximum number of command parameters sent by [`InterfaceAdapter`].
+pub const MAX_COMMAND_PARAMS: usize = 64;
+
+/// Adapter which uses an [`Interface`] as `display-interface` implementation.
+///
+/// Allows the interfaces of this crate to be used with display drivers which
+/// are based on [`WriteOnlyDataCommand`]. These drivers send a command and
+/// its parameters in separate calls, while [`Interface::send_command`] sends
+/// both in one transfer, which is required by interfaces like
+/// [`QspiInterface`](super::QspiInterface) or
+/// [`Ssd2825Interface`](super::Ssd2825Interface).
+///
+/// The adapter therefore buffers the last command and sends the data of the
+/// following [`send_data`](WriteOnlyDataCommand::send_data) call as its
+/// parameters, which must not exceed [`MAX_COMMAND_PARAMS`] bytes. Data which
+/// follows a memory write command (`RAMWR` or `RAMWRC`) or other data is sent
+/// as pixel data. A buffered command is sent without parameters before the
+/// next command, or by [`flush`](Self::flush). 16 bit data is split into
+/// bytes in the requested byte order, 16 bit commands aren't supported.
+///
+/// Errors of the wrapped interface are reported as
+/// [`DisplayError::BusWriteError`].
+///
+/// This adapter is only available if the `display-interface` feature is
+/// enabled.
+pub struct InterfaceAdapter<DI> {
+    di: DI,
+    command: Option<u8>,
+}
+
+impl<DI: Interface<Word = u8>> InterfaceAdapter<DI> {
+    /// Creates a new adapter.
+    pub fn new(di: DI) -> Self {
+        Self { di, command: None }
+    }
+
+    /// Sends the buffered command without parameters.
+    ///
+    /// Must be called after the last command of a sequence if it has no
+    /// parameters, e.g. after `DISPON`, because the adapter can't know that
+    /// no parameters follow.
+    pub fn flush(&mut self) -> Result<(), DisplayError> {
+        match self.command.take() {
+            Some(command) => self.send_command(command, &[]),
+            None => Ok(()),
+        }
+    }
+
+    /// Consumes the adapter and returns the wrapped interface.
+    ///
+    /// A buffered command is discarded, call [`flush`](Self::flush) before
+    /// to send it.
+    pub fn release(self) -> DI {
+        self.di
+    }
+
+    fn send_command(&mut self, command: u8, params: &[u8]) -> Result<(), DisplayError> {
+        self.di
+            .send_command(command, params)
+            .map_err(|_| DisplayError::BusWriteError)
+    }
+
+    fn send_params(&mut self, command: u8, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let mut params = [0; MAX_COMMAND_PARAMS];
+        let mut len = 0;
+        let mut push = |bytes: &[u8]| {
+            let end = len + bytes.len();
+            params
+                .get_mut(len..end)
+                .ok_or(DisplayError::OutOfBoundsError)?
+                .copy_from_slice(bytes);
+            len = end;
+            Ok(())
+        };
+
+        match buf {
+            DataFormat::U8(data) => return self.send_command(command, data),
+            DataFormat::U8Iter(mut data) => Iterator::try_for_each(&mut data, |byte| push(&[byte])),
+            DataFormat::U16(data) => data.iter().try_for_each(|word| push(&word.to_ne_bytes())),
+            DataFormat::U16BE(data) => data.iter().try_for_each(|word| push(&word.to_be_bytes())),
+            DataFormat::U16LE(data) => data.iter().try_for_each(|word| push(&word.to_le_bytes())),
+            DataFormat::U16BEIter(mut data) => {
+                Iterator::try_for_each(&mut data, |word| push(&word.to_be_bytes()))
+            }
+            DataFormat::U16LEIter(mut data) => {
+                Iterator::try_for_each(&mut data, |word| push(&word.to_le_bytes()))
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }?;
+
+        self.send_command(command, &params[..len])
+    }
+
+    fn send_pixels(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            DataFormat::U8(data) => self.di.send_raw_pixels(data),
+            DataFormat::U8Iter(data) => self.di.send_pixels(data.map(|byte| [byte])),
+            DataFormat::U16(data) => self
+                .di
+                .send_pixels(data.iter().map(|word| word.to_ne_bytes())),
+            DataFormat::U16BE(data) => self
+                .di
+                .send_pixels(data.iter().map(|word| word.to_be_bytes())),
+            DataFormat::U16LE(data) => self
+                .di
+                .send_pixels(data.iter().map(|word| word.to_le_bytes())),
+            DataFormat::U16BEIter(data) => self.di.send_pixels(data.map(u16::to_be_bytes)),
+            DataFormat::U16LEIter(data) => self.di.send_pixels(data.map(u16::to_le_bytes)),
+            _ => return Err(DisplayError::DataFormatNotImplemented),
+        }
+        .map_err(|_| DisplayError::BusWriteError)
+    }
+}
+
+impl<DI: Interface<Word = u8>> WriteOnlyDataCommand for InterfaceAdapter<DI> {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        let commands: &mut dyn Iterator<Item = u8> = match cmd {
+            DataFormat::U8(commands) => &mut commands.iter().copied(),
+            DataFormat::U8Iter(commands) => commands,
+            _ => return Err(DisplayError::DataFormatNotImplemented),
+        };
+        for command in commands {
+            self.flush()?;
+            self.command = Some(command);
+        }
+        Ok(())
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match self.command.take() {
+            Some(command) if MEMORY_WRITE_COMMANDS.contains(&command) => {
+                self.send_command(command, &[])?;
+                self.send_pixels(buf)
+            }
+            Some(command) => self.send_params(command, buf),
+            None => self.send_pixels(buf),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _mock::MockDelay,
+        interface::{RecordingInterface, Transfer},
+        models::ILI9341Rgb565,
+        Builder,
+    };
+
+    use super::*;
+
+    /// `display-interface` implementation which records all writes.
+    #[derive(Default)]
+    struct RecordingDisplayInterface {
+        commands: Vec<u8>,
+        data: Vec<u8>,
+    }
+
+    impl WriteOnlyDataCommand for RecordingDisplayInterface {
+        fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+            match cmd {
+                DataFormat::U8(commands) => self.commands.extend_from_slice(commands),
+                _ => return Err(DisplayError::DataFormatNotImplemented),
+            }
+            Ok(())
+        }
+
+        fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+            match buf {
+                DataFormat::U8(data) => self.data.extend_from_slice(data),
+                DataFormat::U8Iter(data) => self.data.extend(data),
+                _ => return Err(DisplayError::DataFormatNotImplemented),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn display_interface_as_interface() {
+        let di = DisplayInterfaceAdapter::new(RecordingDisplayInterface::default());
+        let mut display = Builder::new(ILI9341Rgb565, di)
+            .init(&mut MockDelay)
+            .unwrap();
+        *unsafe { display.dcs() } =
+            DisplayInterfaceAdapter::new(RecordingDisplayInterface::default());
+
+        display
+            .set_pixels(0, 0, 1, 0, [Rgb565::RED, Rgb565::BLUE])
+            .unwrap();
+        display.set_pixels_raw(0, 0, 0, 0, &[0x07, 0xE0]).unwrap();
+
+        let di = display.release().0.release();
+        assert_eq!(di.commands, [0x2A, 0x2B, 0x2C, 0x2A, 0x2B, 0x2C]);
+        assert_eq!(
+            di.data,
+            [
+                0, 0, 0, 1, 0, 0, 0, 0, 0xF8, 0x00, 0x00, 0x1F, //
+                0, 0, 0, 0, 0, 0, 0, 0, 0x07, 0xE0,
+            ]
+        );
+    }
+
+    #[test]
+    fn interface_as_display_interface() {
+        let mut di = InterfaceAdapter::new(RecordingInterface::new());
+
+        di.send_commands(DataFormat::U8(&[0x11, 0x36])).unwrap();
+        di.send_data(DataFormat::U8(&[0x60])).unwrap();
+        di.send_commands(DataFormat::U8(&[0x2A])).unwrap();
+        di.send_data(DataFormat::U16BE(&mut [0x0010, 0x00EF]))
+            .unwrap();
+        di.send_commands(DataFormat::U8(&[0x2C])).unwrap();
+        di.send_data(DataFormat::U8(&[1, 2])).unwrap();
+        di.send_data(DataFormat::U8Iter(&mut [3, 4].into_iter()))
+            .unwrap();
+        di.send_data(DataFormat::U16LE(&mut [0x1234])).unwrap();
+        di.send_commands(DataFormat::U8(&[0x3C])).unwrap();
+        di.send_data(DataFormat::U16BEIter(&mut [0xABCD].into_iter()))
+            .unwrap();
+        di.send_commands(DataFormat::U8(&[0x29])).unwrap();
+        di.flush().unwrap();
+        assert!(matches!(
+            di.send_commands(DataFormat::U16(&[0x11])),
+            Err(DisplayError::DataFormatNotImplemented)
+        ));
+
+        assert_eq!(
+            di.release().take_transfers(),
+            [
+                Transfer::Command {
+                    instruction: 0x11,
+                    params: Vec::new(),
+                },
+                Transfer::Command {
+                    instruction: 0x36,
+                    params: alloc::vec![0x60],
+                },
+                Transfer::Command {
+                    instruction: 0x2A,
+                    params: alloc::vec![0x00, 0x10, 0x00, 0xEF],
+                },
+                Transfer::Command {
+                    instruction: 0x2C,
+                    params: Vec::new(),
+                },
+                Transfer::Pixels(alloc::vec![1, 2]),
+                Transfer::Pixels(alloc::vec![3, 4]),
+                Transfer::Pixels(alloc::vec![0x34, 0x12]),
+                Transfer::Command {
+                    instruction: 0x3C,
+                    params: Vec::new(),
+                },
+                Transfer::Pixels(alloc::vec![0xAB, 0xCD]),
+                Transfer::Command {
+                    instruction: 0x29,
+                    params: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn too_many_command_params() {
+        let mut di = InterfaceAdapter::new(RecordingInterface::new());
+
+        di.send_commands(DataFormat::U8(&[0xE0])).unwrap();
+        assert!(matches!(
+            di.send_data(DataFormat::U8Iter(&mut (0..=MAX_COMMAND_PARAMS as u8))),
+            Err(DisplayError::OutOfBoundsError)
+        ));
+        assert_eq!(di.release().take_transfers(), []);
+    }
+}
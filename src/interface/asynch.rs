@@ -0,0 +1,122 @@
+use super::Interface;
+
+/// Async command and pixel interface
+///
+/// Async variant of the [`Interface`] methods, which is used by the async
+/// drawing methods like [`Display::set_pixels_async`](crate::Display::set_pixels_async).
+/// Interfaces backed by DMA can transfer the data without blocking the
+/// executor.
+///
+/// The display is initialized and configured by the blocking [`Interface`]
+/// methods, which only transfer a few bytes. Only the pixel data, which makes
+/// up almost all of the transferred data, is sent asynchronously.
+///
+/// # Backpressure
+///
+/// The async send methods may return before the data is transmitted, e.g.
+/// after a DMA transfer was started. This allows the caller to render the
+/// next chunk of pixels while the previous chunk is still being transmitted.
+/// [`ready`](Self::ready) waits until the interface can accept the next
+/// transfer. Implementations must wait for the previous transfer before
+/// they reuse a buffer and must not keep using data borrowed by a send
+/// method after it returned. The blocking [`Interface`] methods must not be
+/// called before `ready` returned.
+#[allow(async_fn_in_trait)]
+pub trait AsyncInterface: Interface {
+    /// Waits until the interface is ready for the next transfer.
+    ///
+    /// The default implementation returns immediately, which is correct for
+    /// interfaces whose async send methods only return after the transfer is
+    /// complete.
+    async fn ready(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Send a command with optional parameters
+    async fn send_command_async(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error>;
+
+    /// Send a sequence of pixels
+    ///
+    /// `WriteMemoryStart` must be sent before calling this function
+    async fn send_pixels_async<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error>;
+
+    /// Send the same pixel value multiple times
+    ///
+    /// `WriteMemoryStart` must be sent before calling this function
+    async fn send_repeated_pixel_async<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Send pixel data which is already in the format of the interface
+    ///
+    /// `WriteMemoryStart` must be sent before calling this function.
+    ///
+    /// See [`Interface::send_raw_pixels`] for the format of the data.
+    async fn send_raw_pixels_async(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.send_pixels_async(data.iter().map(|word| [*word]))
+            .await
+    }
+}
+
+impl<T: AsyncInterface> AsyncInterface for &mut T {
+    async fn ready(&mut self) -> Result<(), Self::Error> {
+        T::ready(self).await
+    }
+
+    async fn send_command_async(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        T::send_command_async(self, command, args).await
+    }
+
+    async fn send_pixels_async<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        T::send_pixels_async(self, pixels).await
+    }
+
+    async fn send_repeated_pixel_async<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        T::send_repeated_pixel_async(self, pixel, count).await
+    }
+
+    async fn send_raw_pixels_async(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        T::send_raw_pixels_async(self, data).await
+    }
+}
+
+/// Runs a future to completion.
+///
+/// The futures in the tests never return `Poll::Pending`, which makes an
+/// executor unnecessary.
+#[cfg(test)]
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use core::{
+        pin::pin,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(core::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+
+    // SAFETY: the vtable functions don't use the data pointer
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use super::{Interface, InterfaceKind};
+
+/// Parallel bus with a separate command and data phase.
+///
+/// A parallel bus writes words to the display controller and selects between
+/// the command and data register. Implement this trait to use MCU specific
+/// fast paths, like memory-mapped LCD access over an FSMC/FMC or EBI
+/// peripheral, while reusing all windowing and model logic through
+/// [`BusInterface`].
+///
+/// [`ParallelInterface`](super::ParallelInterface) implements this trait for
+/// a GPIO based bus with D/C and write-enable pins.
+pub trait ParallelBus {
+    /// [u8] for 8-bit buses, [u16] for 16-bit buses, etc.
+    type Word: Copy;
+
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Interface kind.
+    const KIND: InterfaceKind;
+
+    /// Writes a word to the command register.
+    fn write_command(&mut self, command: Self::Word) -> Result<(), Self::Error>;
+
+    /// Writes a word to the data register.
+    fn write_data(&mut self, word: Self::Word) -> Result<(), Self::Error>;
+
+    /// Writes a block of words to the data register.
+    fn write_data_block(&mut self, words: &[Self::Word]) -> Result<(), Self::Error> {
+        for &word in words {
+            self.write_data(word)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the same word `count` times to the data register.
+    fn write_data_repeated(&mut self, word: Self::Word, count: u32) -> Result<(), Self::Error> {
+        for _ in 0..count {
+            self.write_data(word)?;
+        }
+        Ok(())
+    }
+}
+
+/// Interface for any [`ParallelBus`] implementation.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::interface::{BusInterface, InterfaceKind, ParallelBus};
+///
+/// struct Fsmc;
+///
+/// impl ParallelBus for Fsmc {
+///     type Word = u16;
+///     type Error = core::convert::Infallible;
+///
+///     const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+///
+///     fn write_command(&mut self, command: u16) -> Result<(), Self::Error> {
+///         // write to the command address
+///         Ok(())
+///     }
+///
+///     fn write_data(&mut self, word: u16) -> Result<(), Self::Error> {
+///         // write to the data address
+///         Ok(())
+///     }
+/// }
+///
+/// let di = BusInterface::new(Fsmc);
+/// ```
+pub struct BusInterface<BUS> {
+    bus: BUS,
+}
+
+impl<BUS> BusInterface<BUS>
+where
+    BUS: ParallelBus,
+    BUS::Word: From<u8> + Eq,
+{
+    /// Creates a new interface for the given bus.
+    pub fn new(bus: BUS) -> Self {
+        Self { bus }
+    }
+
+    /// Consumes the interface and returns the bus.
+    pub fn release(self) -> BUS {
+        self.bus
+    }
+}
+
+impl<BUS> Interface for BusInterface<BUS>
+where
+    BUS: ParallelBus,
+    BUS::Word: From<u8> + TryFrom<u16> + Eq,
+{
+    type Word = BUS::Word;
+    type Error = BUS::Error;
+
+    const KIND: InterfaceKind = BUS::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        send_command(&mut self.bus, command, args)
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        send_wide_command(&mut self.bus, command, args)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        send_register(&mut self.bus, index, value)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        send_pixels(&mut self.bus, pixels)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        send_repeated_pixel(&mut self.bus, pixel, count)
+    }
+}
+
+// Shared `Interface` implementation for all parallel buses.
+
+pub(crate) fn send_command<BUS>(bus: &mut BUS, command: u8, args: &[u8]) -> Result<(), BUS::Error>
+where
+    BUS: ParallelBus,
+    BUS::Word: From<u8>,
+{
+    bus.write_command(BUS::Word::from(command))?;
+    for arg in args {
+        bus.write_data(BUS::Word::from(*arg))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn send_wide_command<BUS>(
+    bus: &mut BUS,
+    command: u16,
+    args: &[u8],
+) -> Result<(), BUS::Error>
+where
+    BUS: ParallelBus,
+    BUS::Word: From<u8> + TryFrom<u16>,
+{
+    let write_address = |bus: &mut BUS, address: u16| match BUS::Word::try_from(address) {
+        Ok(word) if BUS::KIND == InterfaceKind::Parallel16Bit => bus.write_command(word),
+        _ => {
+            let [high, low] = address.to_be_bytes();
+            bus.write_command(BUS::Word::from(high))?;
+            bus.write_command(BUS::Word::from(low))
+        }
+    };
+
+    if args.is_empty() {
+        return write_address(bus, command);
+    }
+
+    for (address, arg) in (command..).zip(args) {
+        write_address(bus, address)?;
+        bus.write_data(BUS::Word::from(*arg))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn send_register<BUS>(bus: &mut BUS, index: u16, value: u16) -> Result<(), BUS::Error>
+where
+    BUS: ParallelBus,
+    BUS::Word: From<u8> + TryFrom<u16>,
+{
+    if BUS::KIND == InterfaceKind::Parallel16Bit {
+        if let (Ok(index), Ok(value)) = (BUS::Word::try_from(index), BUS::Word::try_from(value)) {
+            bus.write_command(index)?;
+            return bus.write_data(value);
+        }
+    }
+
+    let [index_high, index_low] = index.to_be_bytes();
+    let [value_high, value_low] = value.to_be_bytes();
+    bus.write_command(BUS::Word::from(index_high))?;
+    bus.write_command(BUS::Word::from(index_low))?;
+    bus.write_data(BUS::Word::from(value_high))?;
+    bus.write_data(BUS::Word::from(value_low))
+}
+
+pub(crate) fn send_pixels<BUS, const N: usize>(
+    bus: &mut BUS,
+    pixels: impl IntoIterator<Item = [BUS::Word; N]>,
+) -> Result<(), BUS::Error>
+where
+    BUS: ParallelBus,
+{
+    for pixel in pixels {
+        bus.write_data_block(&pixel)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn send_repeated_pixel<BUS, const N: usize>(
+    bus: &mut BUS,
+    pixel: [BUS::Word; N],
+    count: u32,
+) -> Result<(), BUS::Error>
+where
+    BUS: ParallelBus,
+    BUS::Word: Eq,
+{
+    if count == 0 || N == 0 {
+        return Ok(());
+    }
+
+    if let Some(word) = is_same(pixel) {
+        bus.write_data_repeated(word, count * N as u32)
+    } else {
+        send_pixels(bus, (0..count).map(|_| pixel))
+    }
+}
+
+fn is_same<const N: usize, T: Copy + Eq>(array: [T; N]) -> Option<T> {
+    let (&first, rest) = array.split_first()?;
+    for &x in rest {
+        if x != first {
+            return None;
+        }
+    }
+    Some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bus which counts the written command and data words.
+    #[derive(Default)]
+    struct CountingBus {
+        commands: u32,
+        data: u32,
+        blocks: u32,
+    }
+
+    impl ParallelBus for CountingBus {
+        type Word = u16;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+
+        fn write_command(&mut self, _command: u16) -> Result<(), Self::Error> {
+            self.commands += 1;
+            Ok(())
+        }
+
+        fn write_data(&mut self, _word: u16) -> Result<(), Self::Error> {
+            self.data += 1;
+            Ok(())
+        }
+
+        fn write_data_block(&mut self, words: &[u16]) -> Result<(), Self::Error> {
+            self.blocks += 1;
+            self.data += words.len() as u32;
+            Ok(())
+        }
+    }
+
+    /// Bus which records the written words and whether they are commands.
+    struct RecordingBus<W> {
+        words: [(bool, W); 8],
+        len: usize,
+    }
+
+    impl<W: Copy + Default> Default for RecordingBus<W> {
+        fn default() -> Self {
+            Self {
+                words: [(false, W::default()); 8],
+                len: 0,
+            }
+        }
+    }
+
+    macro_rules! impl_recording_bus {
+        ($word:ty, $kind:expr) => {
+            impl ParallelBus for RecordingBus<$word> {
+                type Word = $word;
+                type Error = core::convert::Infallible;
+
+                const KIND: InterfaceKind = $kind;
+
+                fn write_command(&mut self, command: $word) -> Result<(), Self::Error> {
+                    self.words[self.len] = (true, command);
+                    self.len += 1;
+                    Ok(())
+                }
+
+                fn write_data(&mut self, word: $word) -> Result<(), Self::Error> {
+                    self.words[self.len] = (false, word);
+                    self.len += 1;
+                    Ok(())
+                }
+            }
+        };
+    }
+
+    impl_recording_bus!(u8, InterfaceKind::Parallel8Bit);
+    impl_recording_bus!(u16, InterfaceKind::Parallel16Bit);
+
+    #[test]
+    fn wide_command() {
+        let mut di = BusInterface::new(RecordingBus::<u16>::default());
+        di.send_wide_command(0x2A00, &[0x01, 0xDF]).unwrap();
+        let bus = di.release();
+        assert_eq!(
+            bus.words[..bus.len],
+            [(true, 0x2A00), (false, 0x01), (true, 0x2A01), (false, 0xDF)]
+        );
+
+        let mut di = BusInterface::new(RecordingBus::<u8>::default());
+        di.send_wide_command(0x2A00, &[0x01, 0xDF]).unwrap();
+        di.send_wide_command(0x1100, &[]).unwrap();
+        let bus = di.release();
+        assert_eq!(
+            bus.words[..bus.len],
+            [
+                (true, 0x2A),
+                (true, 0x00),
+                (false, 0x01),
+                (true, 0x2A),
+                (true, 0x01),
+                (false, 0xDF),
+                (true, 0x11),
+                (true, 0x00),
+            ]
+        );
+    }
+
+    #[test]
+    fn register() {
+        let mut di = BusInterface::new(RecordingBus::<u16>::default());
+        di.send_register(0x0003, 0x1030).unwrap();
+        let bus = di.release();
+        assert_eq!(bus.words[..bus.len], [(true, 0x0003), (false, 0x1030)]);
+
+        let mut di = BusInterface::new(RecordingBus::<u8>::default());
+        di.send_register(0x0003, 0x1030).unwrap();
+        let bus = di.release();
+        assert_eq!(
+            bus.words[..bus.len],
+            [(true, 0x00), (true, 0x03), (false, 0x10), (false, 0x30)]
+        );
+    }
+
+    #[test]
+    fn bus_interface() {
+        let mut di = BusInterface::new(CountingBus::default());
+
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        di.send_pixels([[1u16, 2], [3, 4]]).unwrap();
+        di.send_repeated_pixel([5u16, 6], 3).unwrap();
+        di.send_repeated_pixel([7u16, 7], 10).unwrap();
+
+        let bus = di.release();
+        assert_eq!(bus.commands, 1);
+        assert_eq!(bus.data, 4 + 4 + 6 + 20);
+        // repeated pixels with identical words don't use blocks
+        assert_eq!(bus.blocks, 2 + 3);
+    }
+}
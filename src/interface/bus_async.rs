@@ -0,0 +1,348 @@
+use super::{
+    bus::{send_command, send_pixels, send_register, send_repeated_pixel, send_wide_command},
+    AsyncInterface, Interface, InterfaceKind, ParallelBus,
+};
+
+/// Parallel bus with background block transfers.
+///
+/// Used by [`AsyncBusInterface`] to hand whole scanlines to a DMA driven
+/// peripheral, e.g. a DMA channel which writes to the data register of a
+/// memory-mapped FSMC/FMC bank or an LCD peripheral with a DMA engine. The
+/// blocking [`ParallelBus`] methods are used for commands.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::interface::{AsyncParallelBus, InterfaceKind, ParallelBus};
+///
+/// struct FsmcDma;
+///
+/// impl ParallelBus for FsmcDma {
+///     type Word = u16;
+///     type Error = core::convert::Infallible;
+///
+///     const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+///
+///     fn write_command(&mut self, command: u16) -> Result<(), Self::Error> {
+///         // write to the command address
+///         Ok(())
+///     }
+///
+///     fn write_data(&mut self, word: u16) -> Result<(), Self::Error> {
+///         // write to the data address
+///         Ok(())
+///     }
+/// }
+///
+/// impl AsyncParallelBus for FsmcDma {
+///     unsafe fn start_write_data_block(&mut self, words: &[u16]) -> Result<(), Self::Error> {
+///         // start a memory to memory DMA transfer from `words` to the data address
+///         Ok(())
+///     }
+///
+///     async fn wait(&mut self) -> Result<(), Self::Error> {
+///         // await the DMA transfer complete interrupt
+///         Ok(())
+///     }
+/// }
+/// ```
+#[allow(async_fn_in_trait)]
+pub trait AsyncParallelBus: ParallelBus {
+    /// Starts writing a block of words to the data register in the
+    /// background.
+    ///
+    /// The write doesn't need to be finished when this method returns. Use
+    /// [`wait`](Self::wait) to wait for the write to complete.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `words` isn't modified or dropped until
+    /// [`wait`](Self::wait) has returned.
+    unsafe fn start_write_data_block(&mut self, words: &[Self::Word]) -> Result<(), Self::Error>;
+
+    /// Waits until the last write started by
+    /// [`start_write_data_block`](Self::start_write_data_block) is complete.
+    ///
+    /// Returns immediately if no write is in progress.
+    async fn wait(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Async interface for any [`AsyncParallelBus`] implementation.
+///
+/// The buffer is split into two halves. The pixels are converted into one
+/// half while the other half is written by the bus in the background. The
+/// async send methods return as soon as the last block was handed to the
+/// bus, which allows rendering the next scanlines while the previous ones
+/// are still being written, see [`AsyncInterface::ready`].
+///
+/// Each half of the buffer should hold at least one scanline, e.g.
+/// `2 * 320` words for a 320 pixel wide Rgb565 display on a 16 bit bus. The
+/// buffer must be `'static`, because a background write can outlive the
+/// interface.
+///
+/// Commands and the blocking [`Interface`] methods use the blocking
+/// [`ParallelBus`] methods, like [`BusInterface`](super::BusInterface).
+pub struct AsyncBusInterface<BUS: ParallelBus + 'static> {
+    bus: BUS,
+    buffer: &'static mut [BUS::Word],
+    // Half of the buffer which is filled next. A background write can only
+    // use the other half.
+    next: usize,
+}
+
+/// Async bus interface error
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum AsyncBusError<BUS> {
+    /// Bus error
+    Bus(BUS),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+impl<BUS> AsyncBusInterface<BUS>
+where
+    BUS: AsyncParallelBus,
+    BUS::Word: From<u8> + Eq,
+{
+    /// Creates a new interface for the given bus.
+    pub fn new(bus: BUS, buffer: &'static mut [BUS::Word]) -> Self {
+        Self {
+            bus,
+            buffer,
+            next: 0,
+        }
+    }
+
+    /// Consumes the interface and returns the bus.
+    ///
+    /// Await [`ready`](AsyncInterface::ready) first to make sure that no
+    /// background write is in progress.
+    pub fn release(self) -> BUS {
+        self.bus
+    }
+
+    // Returns the half of the buffer which is filled next.
+    fn next_half(&mut self) -> &mut [BUS::Word] {
+        let half = self.buffer.len() / 2;
+        let start = self.next * half;
+        &mut self.buffer[start..start + half]
+    }
+
+    // Waits for the previous write and starts writing the first `len` words
+    // of the next half.
+    async fn start_next(&mut self, len: usize) -> Result<(), AsyncBusError<BUS::Error>> {
+        self.bus.wait().await.map_err(AsyncBusError::Bus)?;
+
+        let half = self.buffer.len() / 2;
+        let start = self.next * half;
+        // SAFETY: the half isn't modified until the write is complete,
+        // because the other half is filled next and `wait` is called before
+        // a new write is started. The buffer is `'static` and is never
+        // released.
+        unsafe {
+            self.bus
+                .start_write_data_block(&self.buffer[start..start + len])
+        }
+        .map_err(AsyncBusError::Bus)?;
+        self.next = 1 - self.next;
+        Ok(())
+    }
+}
+
+impl<BUS> Interface for AsyncBusInterface<BUS>
+where
+    BUS: AsyncParallelBus,
+    BUS::Word: From<u8> + TryFrom<u16> + Eq,
+{
+    type Word = BUS::Word;
+    type Error = AsyncBusError<BUS::Error>;
+
+    const KIND: InterfaceKind = BUS::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        send_command(&mut self.bus, command, args).map_err(AsyncBusError::Bus)
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        send_wide_command(&mut self.bus, command, args).map_err(AsyncBusError::Bus)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        send_register(&mut self.bus, index, value).map_err(AsyncBusError::Bus)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        send_pixels(&mut self.bus, pixels).map_err(AsyncBusError::Bus)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        send_repeated_pixel(&mut self.bus, pixel, count).map_err(AsyncBusError::Bus)
+    }
+}
+
+impl<BUS> AsyncInterface for AsyncBusInterface<BUS>
+where
+    BUS: AsyncParallelBus,
+    BUS::Word: From<u8> + TryFrom<u16> + Eq,
+{
+    async fn ready(&mut self) -> Result<(), Self::Error> {
+        self.bus.wait().await.map_err(AsyncBusError::Bus)
+    }
+
+    async fn send_command_async(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.ready().await?;
+        self.send_command(command, args)
+    }
+
+    async fn send_pixels_async<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() / 2 >= N, AsyncBusError::BufferTooSmall);
+
+        let mut pixels = pixels.into_iter();
+        loop {
+            let mut len = 0;
+            for (chunk, pixel) in self.next_half().chunks_exact_mut(N).zip(&mut pixels) {
+                chunk.copy_from_slice(&pixel);
+                len += N;
+            }
+            if len == 0 {
+                return Ok(());
+            }
+
+            self.start_next(len).await?;
+        }
+    }
+
+    async fn send_repeated_pixel_async<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() / 2 >= N, AsyncBusError::BufferTooSmall);
+
+        let half = self.next_half();
+        let filled = core::cmp::min(count, (half.len() / N) as u32);
+        for chunk in half.chunks_exact_mut(N).take(filled as usize) {
+            chunk.copy_from_slice(&pixel);
+        }
+
+        // The filled half is written repeatedly and is never modified,
+        // because the other half is filled next.
+        let filled_half = self.next;
+        let mut count = count;
+        while count > 0 {
+            let pixels = count.min(filled);
+            self.next = filled_half;
+            self.start_next(pixels as usize * N).await?;
+            count -= pixels;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec::Vec};
+
+    use crate::interface::block_on;
+
+    use super::*;
+
+    /// Bus which records background writes and commands.
+    #[derive(Default)]
+    struct DmaBus {
+        commands: Vec<u16>,
+        blocks: Vec<Vec<u16>>,
+        busy: bool,
+    }
+
+    impl ParallelBus for DmaBus {
+        type Word = u16;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+
+        fn write_command(&mut self, command: u16) -> Result<(), Self::Error> {
+            assert!(!self.busy);
+            self.commands.push(command);
+            Ok(())
+        }
+
+        fn write_data(&mut self, _word: u16) -> Result<(), Self::Error> {
+            assert!(!self.busy);
+            Ok(())
+        }
+    }
+
+    impl AsyncParallelBus for DmaBus {
+        unsafe fn start_write_data_block(&mut self, words: &[u16]) -> Result<(), Self::Error> {
+            assert!(!self.busy);
+            self.blocks.push(words.to_vec());
+            self.busy = true;
+            Ok(())
+        }
+
+        async fn wait(&mut self) -> Result<(), Self::Error> {
+            self.busy = false;
+            Ok(())
+        }
+    }
+
+    fn buffer(len: usize) -> &'static mut [u16] {
+        Box::leak(alloc::vec![0; len].into_boxed_slice())
+    }
+
+    #[test]
+    fn pixels_are_written_in_the_background() {
+        let mut di = AsyncBusInterface::new(DmaBus::default(), buffer(8));
+
+        block_on(di.send_command_async(0x2C, &[])).unwrap();
+        block_on(di.send_pixels_async((1..=10).map(|word| [word]))).unwrap();
+        assert!(di.bus.busy);
+        block_on(di.send_command_async(0x2C, &[])).unwrap();
+        block_on(di.send_repeated_pixel_async([7, 8], 5)).unwrap();
+        block_on(di.ready()).unwrap();
+
+        let bus = di.release();
+        assert_eq!(bus.commands, [0x2C, 0x2C]);
+        assert_eq!(
+            bus.blocks,
+            [
+                alloc::vec![1, 2, 3, 4],
+                alloc::vec![5, 6, 7, 8],
+                alloc::vec![9, 10],
+                alloc::vec![7, 8, 7, 8],
+                alloc::vec![7, 8, 7, 8],
+                alloc::vec![7, 8],
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "no-panic")]
+    fn buffer_too_small_is_error() {
+        let mut di = AsyncBusInterface::new(DmaBus::default(), buffer(2));
+        assert!(matches!(
+            block_on(di.send_pixels_async([[1, 2]])),
+            Err(AsyncBusError::BufferTooSmall)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic]
+    fn buffer_too_small_panics() {
+        let mut di = AsyncBusInterface::new(DmaBus::default(), buffer(2));
+        let _ = block_on(di.send_pixels_async([[1, 2]]));
+    }
+}
@@ -0,0 +1,223 @@
+use super::{Interface, InterfaceKind, ReadInterface};
+
+/// Interface wrapper which splits pixel transfers into bounded chunks.
+///
+/// Large fills and blits are forwarded to the wrapped interface as a single
+/// call, which can hold the bus, or a critical section around it, for a long
+/// time. This wrapper splits every pixel transfer into chunks of at most
+/// `max_pixels` pixels and calls the `flush_point` closure between two
+/// chunks. The closure can be used to service pending work, e.g. to
+/// temporarily release a shared bus or to leave a critical section, which
+/// bounds the interrupt latency caused by the display path.
+///
+/// The maximum duration of an uninterruptible transfer is `max_pixels`
+/// multiplied by the time it takes to transfer one pixel. Commands aren't
+/// split, because they are always short.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::ChunkedInterface, models::ILI9341Rgb565, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// // at most 256 pixels per transfer
+/// let di = ChunkedInterface::new(di, 256, || {
+///     // service the motor control loop
+/// });
+///
+/// let mut display = Builder::new(ILI9341Rgb565, di)
+///     .init(&mut delay)
+///     .unwrap();
+/// ```
+pub struct ChunkedInterface<DI, F> {
+    di: DI,
+    max_pixels: u32,
+    flush_point: F,
+}
+
+impl<DI, F> ChunkedInterface<DI, F>
+where
+    DI: Interface,
+    F: FnMut(),
+{
+    /// Creates a new chunked wrapper for the given interface.
+    ///
+    /// A `max_pixels` value of 0 is treated like 1.
+    pub fn new(di: DI, max_pixels: u32, flush_point: F) -> Self {
+        Self {
+            di,
+            max_pixels: max_pixels.max(1),
+            flush_point,
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped interface.
+    pub fn release(self) -> DI {
+        self.di
+    }
+}
+
+impl<DI, F> Interface for ChunkedInterface<DI, F>
+where
+    DI: Interface,
+    F: FnMut(),
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.di.send_command(command, args)
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        self.di.send_wide_command(command, args)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        self.di.send_register(index, value)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut pixels = pixels.into_iter().peekable();
+
+        while pixels.peek().is_some() {
+            self.di
+                .send_pixels(pixels.by_ref().take(self.max_pixels as usize))?;
+            if pixels.peek().is_some() {
+                (self.flush_point)();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let mut remaining = count;
+
+        while remaining > 0 {
+            let chunk = remaining.min(self.max_pixels);
+            self.di.send_repeated_pixel(pixel, chunk)?;
+            remaining -= chunk;
+            if remaining > 0 {
+                (self.flush_point)();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        // raw data isn't split into pixels, bound the number of words instead
+        let mut chunks = data.chunks(self.max_pixels as usize).peekable();
+
+        while let Some(chunk) = chunks.next() {
+            self.di.send_raw_pixels(chunk)?;
+            if chunks.peek().is_some() {
+                (self.flush_point)();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, F> ReadInterface for ChunkedInterface<DI, F>
+where
+    DI: ReadInterface,
+    F: FnMut(),
+{
+    fn read_command(&mut self, command: u8, params: &mut [u8]) -> Result<(), Self::Error> {
+        self.di.read_command(command, params)
+    }
+
+    fn read_command_with_dummy_cycles(
+        &mut self,
+        command: u8,
+        dummy_cycles: u8,
+        params: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.di
+            .read_command_with_dummy_cycles(command, dummy_cycles, params)
+    }
+
+    fn read_memory(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.di.read_memory(command, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+
+    /// Interface which records the largest pixel transfer.
+    #[derive(Default)]
+    struct MaxTransferInterface {
+        pixels: u32,
+        max_transfer: u32,
+    }
+
+    impl Interface for MaxTransferInterface {
+        type Word = u8;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            let count = pixels.into_iter().count() as u32;
+            self.pixels += count;
+            self.max_transfer = self.max_transfer.max(count);
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            self.pixels += count;
+            self.max_transfer = self.max_transfer.max(count);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transfers_are_chunked() {
+        let flushes = Cell::new(0);
+        let mut di = ChunkedInterface::new(MaxTransferInterface::default(), 10, || {
+            flushes.set(flushes.get() + 1)
+        });
+
+        di.send_pixels((0..25u8).map(|i| [i, i])).unwrap();
+        assert_eq!(flushes.get(), 2);
+
+        di.send_repeated_pixel([0, 0], 30).unwrap();
+        assert_eq!(flushes.get(), 4);
+
+        // no flush point after the last chunk
+        di.send_pixels((0..10u8).map(|i| [i, i])).unwrap();
+        assert_eq!(flushes.get(), 4);
+
+        let di = di.release();
+        assert_eq!(di.pixels, 65);
+        assert_eq!(di.max_transfer, 10);
+    }
+}
@@ -0,0 +1,215 @@
+use core::mem::size_of;
+
+use super::{Interface, InterfaceKind, ReadInterface};
+
+/// Interface wrapper which computes a CRC of all outgoing data.
+///
+/// The wrapper computes a rolling CRC-32 (IEEE 802.3) of every command byte,
+/// parameter byte and pixel word sent through the wrapped interface. Pixel
+/// words wider than 8 bits are included in little endian byte order.
+///
+/// This is intended for hardware-in-the-loop tests: comparing the CRC after a
+/// frame was drawn makes it possible to check that two firmware revisions
+/// produced bit-identical output without capturing the bus.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::CrcInterface, models::ILI9341Rgb565, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(ILI9341Rgb565, CrcInterface::new(di))
+///     .init(&mut delay)
+///     .unwrap();
+///
+/// // draw the frame
+///
+/// // SAFETY: the CRC doesn't depend on the interface state.
+/// let crc = unsafe { display.dcs() }.take_crc();
+/// # let _ = crc;
+/// ```
+pub struct CrcInterface<DI> {
+    di: DI,
+    crc: Crc32,
+}
+
+impl<DI> CrcInterface<DI>
+where
+    DI: Interface,
+    DI::Word: Into<u32>,
+{
+    /// Creates a new CRC wrapper for the given interface.
+    pub fn new(di: DI) -> Self {
+        Self {
+            di,
+            crc: Crc32::new(),
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped interface.
+    pub fn release(self) -> DI {
+        self.di
+    }
+
+    /// Returns the CRC of all data sent since the last reset.
+    pub fn crc(&self) -> u32 {
+        self.crc.value()
+    }
+
+    /// Resets the CRC, e.g. at the start of a frame.
+    pub fn reset_crc(&mut self) {
+        self.crc = Crc32::new();
+    }
+
+    /// Returns the CRC of all data sent since the last reset and resets it.
+    pub fn take_crc(&mut self) -> u32 {
+        let crc = self.crc();
+        self.reset_crc();
+        crc
+    }
+}
+
+impl<DI> Interface for CrcInterface<DI>
+where
+    DI: Interface,
+    DI::Word: Into<u32>,
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.crc.update(&[command]);
+        self.crc.update(args);
+        self.di.send_command(command, args)
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        self.crc.update(&command.to_be_bytes());
+        self.crc.update(args);
+        self.di.send_wide_command(command, args)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        self.crc.update(&index.to_be_bytes());
+        self.crc.update(&value.to_be_bytes());
+        self.di.send_register(index, value)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let crc = &mut self.crc;
+        self.di
+            .send_pixels(pixels.into_iter().inspect(|pixel| crc.update_words(pixel)))
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        for _ in 0..count {
+            self.crc.update_words(&pixel);
+        }
+        self.di.send_repeated_pixel(pixel, count)
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.crc.update_words(data);
+        self.di.send_raw_pixels(data)
+    }
+}
+
+impl<DI> ReadInterface for CrcInterface<DI>
+where
+    DI: ReadInterface,
+    DI::Word: Into<u32>,
+{
+    fn read_command(&mut self, command: u8, params: &mut [u8]) -> Result<(), Self::Error> {
+        self.crc.update(&[command]);
+        self.di.read_command(command, params)
+    }
+
+    fn read_command_with_dummy_cycles(
+        &mut self,
+        command: u8,
+        dummy_cycles: u8,
+        params: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.crc.update(&[command]);
+        self.di
+            .read_command_with_dummy_cycles(command, dummy_cycles, params)
+    }
+
+    fn read_memory(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.crc.update(&[command]);
+        self.di.read_memory(command, data)
+    }
+}
+
+/// CRC-32 (IEEE 802.3) state.
+#[derive(Clone, Copy)]
+struct Crc32(u32);
+
+impl Crc32 {
+    const fn new() -> Self {
+        Self(!0)
+    }
+
+    const fn value(&self) -> u32 {
+        !self.0
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u32::from(byte);
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    fn update_words<W: Copy + Into<u32>>(&mut self, words: &[W]) {
+        for &word in words {
+            let bytes = word.into().to_le_bytes();
+            self.update(&bytes[..size_of::<W>().min(4)]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::_mock::MockDisplayInterface;
+
+    use super::*;
+
+    #[test]
+    fn crc32_check_value() {
+        let mut crc = Crc32::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.value(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn crc_covers_commands_and_pixels() {
+        let mut di = CrcInterface::new(MockDisplayInterface);
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels([[0xAB, 0xCD], [0x12, 0x34]]).unwrap();
+        let crc = di.take_crc();
+
+        let mut expected = Crc32::new();
+        expected.update(&[0x2C, 0xAB, 0xCD, 0x12, 0x34]);
+        assert_eq!(crc, expected.value());
+
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_repeated_pixel([0xAB, 0xCD], 1).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 1).unwrap();
+        assert_eq!(di.take_crc(), crc);
+        assert_eq!(di.crc(), Crc32::new().value());
+    }
+}
@@ -0,0 +1,130 @@
+use core::{convert::Infallible, ptr};
+
+use super::{InterfaceKind, ParallelBus};
+
+/// Memory-mapped parallel bus for STM32 style FSMC/FMC LCD access.
+///
+/// The FSMC/FMC peripheral generates the chip select, write-enable and
+/// read-enable signals for an external memory bank. The D/C signal of the
+/// display is connected to one of the address lines, which means that the
+/// command register and the data register are mapped to two different
+/// addresses in the memory bank.
+///
+/// The FSMC/FMC peripheral, its timing and the GPIO alternate functions must
+/// be configured by the HAL before the bus is used. Use
+/// [`BusInterface`](super::BusInterface) to use the bus with a
+/// [`Display`](crate::Display).
+///
+/// This bus is only available if the `fsmc` feature is enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mipidsi::interface::{BusInterface, FsmcBus};
+///
+/// // NE1 bank with the D/C signal connected to A16
+/// let bus = unsafe { FsmcBus::<u16>::from_address_line(0x6000_0000, 16) };
+/// let di = BusInterface::new(bus);
+/// ```
+pub struct FsmcBus<W> {
+    command: *mut W,
+    data: *mut W,
+}
+
+impl<W> FsmcBus<W> {
+    /// Creates a new bus with the given command and data register addresses.
+    ///
+    /// # Safety
+    ///
+    /// Both addresses must be valid for volatile writes of `W` for the whole
+    /// lifetime of the bus, which is the case for a correctly configured
+    /// FSMC/FMC bank. No other code may access the addresses while the bus
+    /// is in use.
+    pub const unsafe fn new(command: *mut W, data: *mut W) -> Self {
+        Self { command, data }
+    }
+
+    /// Creates a new bus for a display whose D/C signal is connected to the
+    /// address line `A<address_line>`.
+    ///
+    /// The command register is mapped to `bank_base` and the data register to
+    /// the address with the D/C address line set. For 16 bit buses the
+    /// FSMC/FMC shifts the internal address by one bit, which is taken into
+    /// account.
+    ///
+    /// # Safety
+    ///
+    /// See [`FsmcBus::new`].
+    pub const unsafe fn from_address_line(bank_base: usize, address_line: u8) -> Self {
+        let offset = (1 << address_line) * core::mem::size_of::<W>();
+        Self::new(bank_base as *mut W, (bank_base + offset) as *mut W)
+    }
+
+    /// Returns the command register address.
+    pub fn command_address(&self) -> *mut W {
+        self.command
+    }
+
+    /// Returns the data register address.
+    pub fn data_address(&self) -> *mut W {
+        self.data
+    }
+}
+
+macro_rules! fsmc_bus {
+    ($($word:ty => $kind:expr,)*) => {
+        $(
+            impl ParallelBus for FsmcBus<$word> {
+                type Word = $word;
+                type Error = Infallible;
+
+                const KIND: InterfaceKind = $kind;
+
+                fn write_command(&mut self, command: Self::Word) -> Result<(), Self::Error> {
+                    // SAFETY: the address is valid according to the `new` contract
+                    unsafe { ptr::write_volatile(self.command, command) };
+                    Ok(())
+                }
+
+                fn write_data(&mut self, word: Self::Word) -> Result<(), Self::Error> {
+                    // SAFETY: the address is valid according to the `new` contract
+                    unsafe { ptr::write_volatile(self.data, word) };
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+fsmc_bus! {
+    u8 => InterfaceKind::Parallel8Bit,
+    u16 => InterfaceKind::Parallel16Bit,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::{BusInterface, Interface};
+
+    #[test]
+    fn address_line_offset() {
+        let bus = unsafe { FsmcBus::<u16>::from_address_line(0x6000_0000, 16) };
+        assert_eq!(bus.command_address() as usize, 0x6000_0000);
+        assert_eq!(bus.data_address() as usize, 0x6002_0000);
+
+        let bus = unsafe { FsmcBus::<u8>::from_address_line(0x6000_0000, 16) };
+        assert_eq!(bus.data_address() as usize, 0x6001_0000);
+    }
+
+    #[test]
+    fn writes_registers() {
+        let mut registers = [0u16; 2];
+        let base = registers.as_mut_ptr();
+        let bus = unsafe { FsmcBus::new(base, base.add(1)) };
+        let mut di = BusInterface::new(bus);
+
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels([[0xF800u16]]).unwrap();
+        assert_eq!(registers, [0x2C, 0xF800]);
+    }
+}
@@ -1,6 +1,6 @@
 use embedded_hal::digital::OutputPin;
 
-use super::{Interface, InterfaceKind};
+use super::{bus, Interface, InterfaceKind, ParallelBus};
 
 /// This trait represents the data pins of a parallel bus.
 ///
@@ -142,6 +142,60 @@ generic_bus! {
     }
 }
 
+/// 16 bit bus wrapper which swaps the byte lanes.
+///
+/// Some boards route the upper and lower byte of the 16 bit data bus to the
+/// opposite controller pins. This wrapper swaps the bytes of every word
+/// written to the wrapped bus, including command words, which makes it
+/// possible to use these boards without rewiring them or converting every
+/// pixel in user code.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::interface::{ByteSwappedBus, Generic16BitBus, ParallelInterface};
+///
+/// # let p = || mipidsi::_mock::MockOutputPin;
+/// # let (dc, wr) = (p(), p());
+/// let bus = Generic16BitBus::new((
+///     p(), p(), p(), p(), p(), p(), p(), p(),
+///     p(), p(), p(), p(), p(), p(), p(), p(),
+/// ));
+/// let di = ParallelInterface::new(ByteSwappedBus::new(bus), dc, wr);
+/// ```
+pub struct ByteSwappedBus<BUS> {
+    bus: BUS,
+}
+
+impl<BUS> ByteSwappedBus<BUS>
+where
+    BUS: OutputBus<Word = u16>,
+{
+    /// Creates a new byte swapping wrapper for the given bus.
+    pub fn new(bus: BUS) -> Self {
+        Self { bus }
+    }
+
+    /// Consumes the wrapper and returns the wrapped bus.
+    pub fn release(self) -> BUS {
+        self.bus
+    }
+}
+
+impl<BUS> OutputBus for ByteSwappedBus<BUS>
+where
+    BUS: OutputBus<Word = u16>,
+{
+    type Word = u16;
+    type Error = BUS::Error;
+
+    const KIND: InterfaceKind = BUS::KIND;
+
+    fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error> {
+        self.bus.set_value(value.swap_bytes())
+    }
+}
+
 /// Parallel interface error
 #[derive(Clone, Copy, Debug)]
 pub enum ParallelError<BUS, DC, WR> {
@@ -159,9 +213,36 @@ pub enum ParallelError<BUS, DC, WR> {
 /// [`OutputBus`] implementation as well as one
 /// [`OutputPin`] for the data/command selection and one [`OutputPin`] for the write-enable flag.
 ///
+/// The interface is built on its [`ParallelBus`] implementation. Use
+/// [`BusInterface`](super::BusInterface) with a custom [`ParallelBus`] to replace
+/// the GPIO based bus with a faster MCU specific implementation.
+///
 /// All pins in the data bus are supposed to be high-active. High for the D/C pin meaning "data" and the
 /// write-enable being pulled low before the setting of the bits and supposed to be sampled at a
 /// low to high edge.
+///
+/// The interface never reads from the display. If the read-enable (RD) pin of the display is
+/// connected to the MCU it must be driven high before the interface is used, otherwise it can be
+/// tied to the supply voltage.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{
+///     interface::{Generic8BitBus, ParallelInterface},
+///     models::ILI9341Rgb565,
+///     Builder,
+/// };
+///
+/// # let p = || mipidsi::_mock::MockOutputPin;
+/// # let (dc, wr) = (p(), p());
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// // D0 is the first pin in the tuple, D7 the last one
+/// let bus = Generic8BitBus::new((p(), p(), p(), p(), p(), p(), p(), p()));
+/// let di = ParallelInterface::new(bus, dc, wr);
+///
+/// let mut display = Builder::new(ILI9341Rgb565, di).init(&mut delay).unwrap();
+/// ```
 pub struct ParallelInterface<BUS, DC, WR> {
     bus: BUS,
     dc: DC,
@@ -196,7 +277,7 @@ where
     }
 }
 
-impl<BUS, DC, WR> Interface for ParallelInterface<BUS, DC, WR>
+impl<BUS, DC, WR> ParallelBus for ParallelInterface<BUS, DC, WR>
 where
     BUS: OutputBus,
     BUS::Word: From<u8> + Eq,
@@ -208,28 +289,60 @@ where
 
     const KIND: InterfaceKind = BUS::KIND;
 
-    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+    fn write_command(&mut self, command: Self::Word) -> Result<(), Self::Error> {
         self.dc.set_low().map_err(ParallelError::Dc)?;
-        self.send_word(BUS::Word::from(command))?;
-        self.dc.set_high().map_err(ParallelError::Dc)?;
+        self.send_word(command)?;
+        self.dc.set_high().map_err(ParallelError::Dc)
+    }
 
-        for arg in args {
-            self.send_word(BUS::Word::from(*arg))?;
+    fn write_data(&mut self, word: Self::Word) -> Result<(), Self::Error> {
+        self.send_word(word)
+    }
+
+    fn write_data_repeated(&mut self, word: Self::Word, count: u32) -> Result<(), Self::Error> {
+        if count == 0 {
+            return Ok(());
         }
 
+        // the bus already holds the word after the first write
+        self.send_word(word)?;
+        for _ in 1..count {
+            self.wr.set_low().map_err(ParallelError::Wr)?;
+            self.wr.set_high().map_err(ParallelError::Wr)?;
+        }
         Ok(())
     }
+}
+
+impl<BUS, DC, WR> Interface for ParallelInterface<BUS, DC, WR>
+where
+    BUS: OutputBus,
+    BUS::Word: From<u8> + TryFrom<u16> + Eq,
+    DC: OutputPin,
+    WR: OutputPin,
+{
+    type Word = BUS::Word;
+    type Error = ParallelError<BUS::Error, DC::Error, WR::Error>;
+
+    const KIND: InterfaceKind = BUS::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        bus::send_command(self, command, args)
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        bus::send_wide_command(self, command, args)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        bus::send_register(self, index, value)
+    }
 
     fn send_pixels<const N: usize>(
         &mut self,
         pixels: impl IntoIterator<Item = [Self::Word; N]>,
     ) -> Result<(), Self::Error> {
-        for pixel in pixels {
-            for word in pixel {
-                self.send_word(word)?;
-            }
-        }
-        Ok(())
+        bus::send_pixels(self, pixels)
     }
 
     fn send_repeated_pixel<const N: usize>(
@@ -237,29 +350,72 @@ where
         pixel: [Self::Word; N],
         count: u32,
     ) -> Result<(), Self::Error> {
-        if count == 0 || N == 0 {
-            return Ok(());
-        }
+        bus::send_repeated_pixel(self, pixel, count)
+    }
+}
 
-        if let Some(word) = is_same(pixel) {
-            self.send_word(word)?;
-            for _ in 1..(count * N as u32) {
-                self.wr.set_low().map_err(ParallelError::Wr)?;
-                self.wr.set_high().map_err(ParallelError::Wr)?;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LastValueBus(u16);
+
+    impl OutputBus for LastValueBus {
+        type Word = u16;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+
+        fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error> {
+            self.0 = value;
             Ok(())
-        } else {
-            self.send_pixels((0..count).map(|_| pixel))
         }
     }
-}
 
-fn is_same<const N: usize, T: Copy + Eq>(array: [T; N]) -> Option<T> {
-    let (&first, rest) = array.split_first()?;
-    for &x in rest {
-        if x != first {
-            return None;
+    /// WR pin which counts the rising edges.
+    #[derive(Default)]
+    struct StrobeCounter(u32);
+
+    impl embedded_hal::digital::ErrorType for StrobeCounter {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for StrobeCounter {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
         }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0 += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn rgb565_pixel_per_strobe() {
+        use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+        use crate::interface::InterfacePixelFormat;
+
+        let mut di = ParallelInterface::new(
+            LastValueBus(0),
+            StrobeCounter::default(),
+            StrobeCounter::default(),
+        );
+
+        Rgb565::send_pixels(&mut di, [Rgb565::RED, Rgb565::BLUE]).unwrap();
+
+        let (bus, _, wr) = di.release();
+        assert_eq!(wr.0, 2);
+        assert_eq!(bus.0, 0x001F);
+    }
+
+    #[test]
+    fn byte_swapped_bus() {
+        let mut bus = ByteSwappedBus::new(LastValueBus(0));
+        bus.set_value(0x2A).unwrap();
+        assert_eq!(bus.bus.0, 0x2A00);
+        bus.set_value(0xF800).unwrap();
+        assert_eq!(bus.release().0, 0x00F8);
     }
-    Some(first)
 }
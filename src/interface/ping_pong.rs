@@ -0,0 +1,213 @@
+use embedded_hal::digital::OutputPin;
+
+use super::{Interface, InterfaceKind};
+
+/// Writer for background transfers, e.g. by using DMA.
+///
+/// Used by [`PingPongInterface`] to transmit one half of its buffer while the
+/// next pixels are converted into the other half.
+pub trait BackgroundWriter {
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Starts writing `data` in the background.
+    ///
+    /// The write doesn't need to be finished when this method returns. Use
+    /// [`wait`](Self::wait) to wait for the write to complete.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `data` isn't modified or dropped until
+    /// [`wait`](Self::wait) has returned.
+    unsafe fn start_write(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Blocks until the last write started by
+    /// [`start_write`](Self::start_write) is complete.
+    ///
+    /// Returns immediately if no write is in progress.
+    fn wait(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Ping-pong interface error
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum PingPongError<W, DC> {
+    /// Writer error
+    Writer(W),
+    /// Data/command pin error
+    Dc(DC),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+/// Serial interface with double buffered pixel transfers.
+///
+/// The buffer is split into two halves. While one half is transmitted by
+/// the [`BackgroundWriter`], the next pixels are converted into the other half,
+/// which overlaps the pixel conversion with the transfer instead of
+/// serializing them like [`SpiInterface`](super::SpiInterface).
+///
+/// Each half of the buffer should be at least big enough to hold a few
+/// pixels of data.
+pub struct PingPongInterface<'a, W, DC> {
+    writer: W,
+    dc: DC,
+    buffer: &'a mut [u8],
+}
+
+impl<'a, W, DC> PingPongInterface<'a, W, DC>
+where
+    W: BackgroundWriter,
+    DC: OutputPin,
+{
+    /// Create new interface
+    pub fn new(writer: W, dc: DC, buffer: &'a mut [u8]) -> Self {
+        Self { writer, dc, buffer }
+    }
+
+    /// Release the writer and DC pin back, deconstructing the interface
+    pub fn release(self) -> (W, DC) {
+        (self.writer, self.dc)
+    }
+
+    // Writes data and waits for the transfer to complete.
+    fn write_blocking(&mut self, data: &[u8]) -> Result<(), PingPongError<W::Error, DC::Error>> {
+        // SAFETY: `data` is borrowed until the write is complete.
+        unsafe { self.writer.start_write(data) }.map_err(PingPongError::Writer)?;
+        self.writer.wait().map_err(PingPongError::Writer)
+    }
+
+    // Writes the pixels by alternating between both halves of the buffer.
+    fn write_double_buffered<const N: usize>(
+        &mut self,
+        mut arrays: impl Iterator<Item = [u8; N]>,
+    ) -> Result<(), PingPongError<W::Error, DC::Error>> {
+        let half = self.buffer.len() / 2;
+        ensure!(half >= N, PingPongError::BufferTooSmall);
+
+        let (mut front, mut back) = self.buffer.split_at_mut(half);
+
+        let mut done = false;
+        while !done {
+            let mut i = 0;
+            for chunk in back.chunks_exact_mut(N) {
+                if let Some(array) = arrays.next() {
+                    let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+                    *chunk = array;
+                    i += N;
+                } else {
+                    done = true;
+                    break;
+                };
+            }
+
+            // The previous write still uses `front`, wait for it to complete
+            // before `back` is transmitted and `front` is refilled.
+            self.writer.wait().map_err(PingPongError::Writer)?;
+            if i > 0 {
+                // SAFETY: `back` isn't modified until `wait` was called in the
+                // next iteration or after the loop.
+                unsafe { self.writer.start_write(&back[..i]) }.map_err(PingPongError::Writer)?;
+            }
+
+            core::mem::swap(&mut front, &mut back);
+        }
+
+        self.writer.wait().map_err(PingPongError::Writer)
+    }
+}
+
+impl<W, DC> Interface for PingPongInterface<'_, W, DC>
+where
+    W: BackgroundWriter,
+    DC: OutputPin,
+{
+    type Word = u8;
+    type Error = PingPongError<W::Error, DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(PingPongError::Dc)?;
+        self.write_blocking(&[command])?;
+        self.dc.set_high().map_err(PingPongError::Dc)?;
+        if !args.is_empty() {
+            self.write_blocking(args)?;
+        }
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.write_double_buffered(pixels.into_iter())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.write_double_buffered((0..count).map(|_| pixel))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    /// Writer which records the written data and checks that no writes overlap.
+    struct RecordingWriter {
+        data: [u8; 32],
+        len: usize,
+        busy: bool,
+        completed: usize,
+    }
+
+    impl BackgroundWriter for RecordingWriter {
+        type Error = core::convert::Infallible;
+
+        unsafe fn start_write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+            assert!(!self.busy, "write started before wait");
+            self.data[self.len..self.len + data.len()].copy_from_slice(data);
+            self.len += data.len();
+            self.busy = true;
+            Ok(())
+        }
+
+        fn wait(&mut self) -> Result<(), Self::Error> {
+            if self.busy {
+                self.busy = false;
+                self.completed += 1;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pixels_are_double_buffered() {
+        let writer = RecordingWriter {
+            data: [0; 32],
+            len: 0,
+            busy: false,
+            completed: 0,
+        };
+        let mut buffer = [0; 8];
+        let mut di = PingPongInterface::new(writer, MockOutputPin, &mut buffer);
+
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels((0..5u8).map(|i| [i, i + 10])).unwrap();
+
+        let (writer, _) = di.release();
+        assert_eq!(
+            &writer.data[..writer.len],
+            &[0x2C, 0, 10, 1, 11, 2, 12, 3, 13, 4, 14]
+        );
+        assert!(!writer.busy);
+        // command + 3 pixel buffers
+        assert_eq!(writer.completed, 4);
+    }
+}
@@ -0,0 +1,419 @@
+use embedded_hal::digital::OutputPin;
+
+use super::{Interface, InterfaceKind, OutputBus};
+
+/// Writer for blocks of words on a parallel port.
+///
+/// A port writer outputs each word of a block to the data bus and strobes
+/// the write-enable signal once per word. Implementations can use MCU
+/// specific fast paths, like writing directly to a GPIO port register or
+/// handing the block to a DMA or PIO peripheral.
+///
+/// See [`StrobedBus`] for a generic implementation using an [`OutputBus`]
+/// and an [`OutputPin`] for the write-enable signal.
+pub trait PortWriter {
+    /// [u8] for 8-bit ports, [u16] for 16-bit ports, etc.
+    type Word: Copy;
+
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Interface kind.
+    const KIND: InterfaceKind;
+
+    /// Writes a block of words to the port.
+    fn write_words(&mut self, words: &[Self::Word]) -> Result<(), Self::Error>;
+}
+
+/// Strobed bus error
+#[derive(Clone, Copy, Debug)]
+pub enum StrobedBusError<BUS, WR> {
+    /// Bus error
+    Bus(BUS),
+    /// Write pin error
+    Wr(WR),
+}
+
+/// Generic [`PortWriter`] implementation using an [`OutputBus`] and a
+/// write-enable [`OutputPin`].
+pub struct StrobedBus<BUS, WR> {
+    bus: BUS,
+    wr: WR,
+}
+
+impl<BUS, WR> StrobedBus<BUS, WR>
+where
+    BUS: OutputBus,
+    WR: OutputPin,
+{
+    /// Creates a new strobed bus.
+    pub fn new(bus: BUS, wr: WR) -> Self {
+        Self { bus, wr }
+    }
+
+    /// Consumes the strobed bus and returns the bus and write-enable pin.
+    pub fn release(self) -> (BUS, WR) {
+        (self.bus, self.wr)
+    }
+}
+
+impl<BUS, WR> PortWriter for StrobedBus<BUS, WR>
+where
+    BUS: OutputBus,
+    WR: OutputPin,
+{
+    type Word = BUS::Word;
+    type Error = StrobedBusError<BUS::Error, WR::Error>;
+
+    const KIND: InterfaceKind = BUS::KIND;
+
+    fn write_words(&mut self, words: &[Self::Word]) -> Result<(), Self::Error> {
+        for &word in words {
+            self.wr.set_low().map_err(StrobedBusError::Wr)?;
+            self.bus.set_value(word).map_err(StrobedBusError::Bus)?;
+            self.wr.set_high().map_err(StrobedBusError::Wr)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Port interface error
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum PortError<PORT, DC> {
+    /// Port writer error
+    Port(PORT),
+    /// Data/command pin error
+    Dc(DC),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+/// Parallel interface with a staging buffer
+///
+/// Unlike [`ParallelInterface`](super::ParallelInterface), which converts
+/// every pixel between two write strobes, this interface first converts a
+/// whole buffer of pixels into bus words and then passes the buffer to a
+/// [`PortWriter`]. This keeps the pixel conversion out of the time critical
+/// strobe loop and allows port writers to output the buffer with a single
+/// port, DMA, or PIO transfer.
+///
+/// Larger buffers will generally be faster, at the expense of using more RAM.
+/// A buffer which holds one line of pixels is a good starting point.
+pub struct PortInterface<'a, PORT: PortWriter, DC> {
+    port: PORT,
+    dc: DC,
+    buffer: &'a mut [PORT::Word],
+}
+
+impl<'a, PORT, DC> PortInterface<'a, PORT, DC>
+where
+    PORT: PortWriter,
+    PORT::Word: From<u8> + TryFrom<u16>,
+    DC: OutputPin,
+{
+    /// Create new interface
+    pub fn new(port: PORT, dc: DC, buffer: &'a mut [PORT::Word]) -> Self {
+        Self { port, dc, buffer }
+    }
+
+    /// Release the port writer and DC pin back, deconstructing the interface
+    pub fn release(self) -> (PORT, DC) {
+        (self.port, self.dc)
+    }
+
+    fn write_commands(
+        &mut self,
+        words: &[PORT::Word],
+    ) -> Result<(), PortError<PORT::Error, DC::Error>> {
+        self.dc.set_low().map_err(PortError::Dc)?;
+        self.port.write_words(words).map_err(PortError::Port)?;
+        self.dc.set_high().map_err(PortError::Dc)
+    }
+
+    fn write_data(
+        &mut self,
+        words: &[PORT::Word],
+    ) -> Result<(), PortError<PORT::Error, DC::Error>> {
+        self.port.write_words(words).map_err(PortError::Port)
+    }
+
+    // Converts a 16 bit value into a single word on 16 bit ports.
+    fn wide_word(value: u16) -> Option<PORT::Word> {
+        match PORT::Word::try_from(value) {
+            Ok(word) if PORT::KIND == InterfaceKind::Parallel16Bit => Some(word),
+            _ => None,
+        }
+    }
+
+    fn write_address(&mut self, address: u16) -> Result<(), PortError<PORT::Error, DC::Error>> {
+        match Self::wide_word(address) {
+            Some(word) => self.write_commands(&[word]),
+            None => {
+                let [high, low] = address.to_be_bytes();
+                self.write_commands(&[PORT::Word::from(high), PORT::Word::from(low)])
+            }
+        }
+    }
+}
+
+impl<PORT, DC> Interface for PortInterface<'_, PORT, DC>
+where
+    PORT: PortWriter,
+    PORT::Word: From<u8> + TryFrom<u16>,
+    DC: OutputPin,
+{
+    type Word = PORT::Word;
+    type Error = PortError<PORT::Error, DC::Error>;
+
+    const KIND: InterfaceKind = PORT::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.write_commands(&[PORT::Word::from(command)])?;
+
+        ensure!(
+            args.is_empty() || !self.buffer.is_empty(),
+            PortError::BufferTooSmall
+        );
+        for args in args.chunks(self.buffer.len()) {
+            for (word, arg) in self.buffer.iter_mut().zip(args) {
+                *word = PORT::Word::from(*arg);
+            }
+            self.port
+                .write_words(&self.buffer[..args.len()])
+                .map_err(PortError::Port)?;
+        }
+
+        Ok(())
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        if args.is_empty() {
+            return self.write_address(command);
+        }
+
+        for (address, arg) in (command..).zip(args) {
+            self.write_address(address)?;
+            self.write_data(&[PORT::Word::from(*arg)])?;
+        }
+        Ok(())
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        if let (Some(index), Some(value)) = (Self::wide_word(index), Self::wide_word(value)) {
+            self.write_commands(&[index])?;
+            return self.write_data(&[value]);
+        }
+
+        let [value_high, value_low] = value.to_be_bytes();
+        self.write_address(index)?;
+        self.write_data(&[PORT::Word::from(value_high), PORT::Word::from(value_low)])
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut arrays = pixels.into_iter();
+
+        ensure!(self.buffer.len() >= N, PortError::BufferTooSmall);
+
+        let mut done = false;
+        while !done {
+            let mut i = 0;
+            for chunk in self.buffer.chunks_exact_mut(N) {
+                if let Some(array) = arrays.next() {
+                    chunk.copy_from_slice(&array);
+                    i += N;
+                } else {
+                    done = true;
+                    break;
+                };
+            }
+            self.port
+                .write_words(&self.buffer[..i])
+                .map_err(PortError::Port)?;
+        }
+
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, PortError::BufferTooSmall);
+
+        let fill_count = core::cmp::min(count, (self.buffer.len() / N) as u32);
+        let filled_len = fill_count as usize * N;
+        for chunk in self.buffer[..filled_len].chunks_exact_mut(N) {
+            chunk.copy_from_slice(&pixel);
+        }
+
+        let mut count = count;
+        while count >= fill_count && count > 0 {
+            self.port
+                .write_words(&self.buffer[..filled_len])
+                .map_err(PortError::Port)?;
+            count -= fill_count;
+        }
+        if count != 0 {
+            self.port
+                .write_words(&self.buffer[..(count as usize * N)])
+                .map_err(PortError::Port)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::{Cell, RefCell};
+
+    use alloc::vec::Vec;
+
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    /// D/C pin which stores its level in a shared cell.
+    struct DcPin<'a>(&'a Cell<bool>);
+
+    impl embedded_hal::digital::ErrorType for DcPin<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for DcPin<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.set(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.set(true);
+            Ok(())
+        }
+    }
+
+    /// Port writer which records the written words and the D/C level.
+    struct RecordingPort<'a, W> {
+        dc: &'a Cell<bool>,
+        words: &'a RefCell<Vec<(bool, W)>>,
+    }
+
+    macro_rules! impl_recording_port {
+        ($word:ty, $kind:expr) => {
+            impl PortWriter for RecordingPort<'_, $word> {
+                type Word = $word;
+                type Error = core::convert::Infallible;
+
+                const KIND: InterfaceKind = $kind;
+
+                fn write_words(&mut self, words: &[$word]) -> Result<(), Self::Error> {
+                    let dc = self.dc.get();
+                    self.words
+                        .borrow_mut()
+                        .extend(words.iter().map(|word| (dc, *word)));
+                    Ok(())
+                }
+            }
+        };
+    }
+
+    impl_recording_port!(u8, InterfaceKind::Parallel8Bit);
+    impl_recording_port!(u16, InterfaceKind::Parallel16Bit);
+
+    /// Port writer which counts the written words and blocks.
+    #[derive(Default)]
+    struct CountingPort {
+        words: usize,
+        blocks: usize,
+    }
+
+    impl PortWriter for CountingPort {
+        type Word = u8;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+
+        fn write_words(&mut self, words: &[Self::Word]) -> Result<(), Self::Error> {
+            self.words += words.len();
+            self.blocks += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pixels_are_written_in_blocks() {
+        let mut buffer = [0; 8];
+        let mut di = PortInterface::new(CountingPort::default(), MockOutputPin, &mut buffer);
+
+        di.send_pixels((0..10).map(|i| [i, i])).unwrap();
+        assert_eq!(di.port.words, 20);
+        assert_eq!(di.port.blocks, 3);
+
+        di.send_repeated_pixel([1, 2, 3], 5).unwrap();
+        assert_eq!(di.port.words, 35);
+        assert_eq!(di.port.blocks, 6);
+    }
+
+    #[test]
+    fn wide_commands_and_registers_16bit() {
+        let dc = Cell::new(true);
+        let words = RefCell::new(Vec::new());
+        let port = RecordingPort::<u16> {
+            dc: &dc,
+            words: &words,
+        };
+        let mut buffer = [0; 8];
+        let mut di = PortInterface::new(port, DcPin(&dc), &mut buffer);
+
+        di.send_wide_command(0x2A00, &[0x01, 0xDF]).unwrap();
+        di.send_wide_command(0x1100, &[]).unwrap();
+        di.send_register(0x0007, 0x0133).unwrap();
+
+        assert_eq!(
+            words.take(),
+            [
+                (false, 0x2A00),
+                (true, 0x01),
+                (false, 0x2A01),
+                (true, 0xDF),
+                (false, 0x1100),
+                (false, 0x0007),
+                (true, 0x0133),
+            ]
+        );
+    }
+
+    #[test]
+    fn wide_commands_and_registers_8bit() {
+        let dc = Cell::new(true);
+        let words = RefCell::new(Vec::new());
+        let port = RecordingPort::<u8> {
+            dc: &dc,
+            words: &words,
+        };
+        let mut buffer = [0; 8];
+        let mut di = PortInterface::new(port, DcPin(&dc), &mut buffer);
+
+        di.send_wide_command(0x2A00, &[0x01]).unwrap();
+        di.send_register(0x0007, 0x0133).unwrap();
+
+        assert_eq!(
+            words.take(),
+            [
+                (false, 0x2A),
+                (false, 0x00),
+                (true, 0x01),
+                (false, 0x00),
+                (false, 0x07),
+                (true, 0x01),
+                (true, 0x33),
+            ]
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use embedded_hal::spi::{Operation, SpiDevice};
+
+use super::{Interface, InterfaceKind};
+
+/// QSPI write instruction with the data on a single data line.
+const WRITE_SINGLE: u8 = 0x02;
+/// QSPI write instruction with the data on four data lines.
+const WRITE_QUAD: u8 = 0x32;
+
+/// DCS Write Memory Continue instruction.
+const WRITE_MEMORY_CONTINUE: u8 = 0x3C;
+
+/// Quad SPI bus.
+///
+/// Every write is a single chip select cycle, which consists of an 8 bit
+/// instruction and a 24 bit address, both sent on a single data line,
+/// followed by the data. Implement this trait for the QSPI peripheral of the
+/// MCU to use it with [`QspiInterface`].
+pub trait QspiBus {
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Writes the instruction, address and data on a single data line.
+    fn write_single(
+        &mut self,
+        instruction: u8,
+        address: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Writes the instruction and address on a single data line and the data
+    /// on four data lines.
+    fn write_quad(&mut self, instruction: u8, address: u32, data: &[u8])
+        -> Result<(), Self::Error>;
+}
+
+/// [`QspiBus`] implementation for a standard SPI device.
+///
+/// This is a reference implementation of the QSPI framing, which sends the
+/// instruction, the address and the data on a single data line. Quad writes
+/// are sent with the single line write instruction, because the data can't
+/// be transferred on four data lines. This is useful for MCUs without a QSPI
+/// peripheral and to bring up a new panel, implement [`QspiBus`] for the QSPI
+/// peripheral of the MCU for the full bandwidth.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{
+///     interface::{QspiInterface, SpiQspiBus},
+///     models::RM67162,
+///     Builder,
+/// };
+///
+/// # let spi = mipidsi::_mock::MockSpi;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut buffer = [0u8; 512];
+/// let di = QspiInterface::new(SpiQspiBus::new(spi), &mut buffer);
+///
+/// let mut display = Builder::new(RM67162, di).init(&mut delay).unwrap();
+/// ```
+pub struct SpiQspiBus<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> SpiQspiBus<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Creates a new bus.
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Release the SPI peripheral back, deconstructing the bus
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+impl<SPI> QspiBus for SpiQspiBus<SPI>
+where
+    SPI: SpiDevice,
+{
+    type Error = SPI::Error;
+
+    fn write_single(
+        &mut self,
+        instruction: u8,
+        address: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        let [_, a2, a1, a0] = address.to_be_bytes();
+        self.spi.transaction(&mut [
+            Operation::Write(&[instruction, a2, a1, a0]),
+            Operation::Write(data),
+        ])
+    }
+
+    fn write_quad(
+        &mut self,
+        _instruction: u8,
+        address: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.write_single(WRITE_SINGLE, address, data)
+    }
+}
+
+/// QSPI interface error
+#[derive(Clone, Copy, Debug)]
+pub enum QspiError<BUS> {
+    /// QSPI bus error
+    Bus(BUS),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+/// Quad SPI interface, including a buffer.
+///
+/// AMOLED controllers like the RM67162 or SH8601 are usually connected over
+/// QSPI. The controllers don't use a data/command pin, instead the DCS
+/// instruction is sent as part of the 24 bit address of each write.
+/// Commands are written on a single data line and pixel data is written on
+/// four data lines. Pixel data is split into writes which fit into the
+/// buffer, each of which is sent as a `Write Memory Continue` command.
+/// Larger buffers will result in less overhead.
+///
+/// The interface reports [`InterfaceKind::Serial4Line`], because QSPI uses
+/// the same byte oriented pixel formats as the SPI interface, which allows
+/// all existing models to be used with this interface.
+pub struct QspiInterface<'a, BUS> {
+    bus: BUS,
+    buffer: &'a mut [u8],
+}
+
+impl<'a, BUS> QspiInterface<'a, BUS>
+where
+    BUS: QspiBus,
+{
+    /// Create new interface
+    pub fn new(bus: BUS, buffer: &'a mut [u8]) -> Self {
+        Self { bus, buffer }
+    }
+
+    /// Release the QSPI bus back, deconstructing the interface
+    pub fn release(self) -> BUS {
+        self.bus
+    }
+
+    fn write_pixel_data(&mut self, len: usize) -> Result<(), QspiError<BUS::Error>> {
+        self.bus
+            .write_quad(
+                WRITE_QUAD,
+                u32::from(WRITE_MEMORY_CONTINUE) << 8,
+                &self.buffer[..len],
+            )
+            .map_err(QspiError::Bus)
+    }
+}
+
+impl<BUS> Interface for QspiInterface<'_, BUS>
+where
+    BUS: QspiBus,
+{
+    type Word = u8;
+    type Error = QspiError<BUS::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.bus
+            .write_single(WRITE_SINGLE, u32::from(command) << 8, args)
+            .map_err(QspiError::Bus)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut arrays = pixels.into_iter();
+
+        ensure!(self.buffer.len() >= N, QspiError::BufferTooSmall);
+
+        let mut done = false;
+        while !done {
+            let mut i = 0;
+            for chunk in self.buffer.chunks_exact_mut(N) {
+                if let Some(array) = arrays.next() {
+                    let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+                    *chunk = array;
+                    i += N;
+                } else {
+                    done = true;
+                    break;
+                };
+            }
+            if i > 0 {
+                self.write_pixel_data(i)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, QspiError::BufferTooSmall);
+        if count == 0 {
+            return Ok(());
+        }
+
+        let fill_count = core::cmp::min(count, (self.buffer.len() / N) as u32);
+        let filled_len = fill_count as usize * N;
+        for chunk in self.buffer[..filled_len].chunks_exact_mut(N) {
+            let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+            *chunk = pixel;
+        }
+
+        let mut count = count;
+        while count >= fill_count {
+            self.write_pixel_data(filled_len)?;
+            count -= fill_count;
+        }
+        if count != 0 {
+            self.write_pixel_data(count as usize * N)?;
+        }
+        Ok(())
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        for chunk in data.chunks(self.buffer.len().max(1)) {
+            self.bus
+                .write_quad(WRITE_QUAD, u32::from(WRITE_MEMORY_CONTINUE) << 8, chunk)
+                .map_err(QspiError::Bus)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// QSPI bus which records the last write.
+    #[derive(Default)]
+    struct RecordingBus {
+        writes: usize,
+        instruction: u8,
+        address: u32,
+        len: usize,
+    }
+
+    impl QspiBus for RecordingBus {
+        type Error = core::convert::Infallible;
+
+        fn write_single(
+            &mut self,
+            instruction: u8,
+            address: u32,
+            data: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.writes += 1;
+            self.instruction = instruction;
+            self.address = address;
+            self.len = data.len();
+            Ok(())
+        }
+
+        fn write_quad(
+            &mut self,
+            instruction: u8,
+            address: u32,
+            data: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.write_single(instruction, address, data)
+        }
+    }
+
+    #[test]
+    fn commands_and_pixels() {
+        let mut buffer = [0; 8];
+        let mut di = QspiInterface::new(RecordingBus::default(), &mut buffer);
+
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        assert_eq!(di.bus.instruction, 0x02);
+        assert_eq!(di.bus.address, 0x00_2A_00);
+        assert_eq!(di.bus.len, 4);
+
+        di.send_pixels([[1, 2]; 5]).unwrap();
+        assert_eq!(di.bus.writes, 1 + 2);
+        assert_eq!(di.bus.instruction, 0x32);
+        assert_eq!(di.bus.address, 0x00_3C_00);
+        assert_eq!(di.bus.len, 2);
+
+        di.send_repeated_pixel([1, 2], 9).unwrap();
+        assert_eq!(di.bus.writes, 3 + 3);
+        assert_eq!(di.bus.len, 2);
+    }
+
+    /// SPI device which records the written bytes.
+    #[derive(Default)]
+    struct RecordingSpi {
+        data: [u8; 8],
+        len: usize,
+    }
+
+    impl embedded_hal::spi::ErrorType for RecordingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.data[self.len..self.len + data.len()].copy_from_slice(data);
+                    self.len += data.len();
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn spi_bus_sends_single_line_frames() {
+        let mut bus = SpiQspiBus::new(RecordingSpi::default());
+        bus.write_quad(WRITE_QUAD, 0x00_3C_00, &[0x12, 0x34])
+            .unwrap();
+
+        let spi = bus.release();
+        assert_eq!(&spi.data[..spi.len], &[0x02, 0x00, 0x3C, 0x00, 0x12, 0x34]);
+    }
+}
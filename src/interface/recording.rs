@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+
+use super::{Interface, InterfaceKind};
+
+/// Transfer recorded by [`RecordingInterface`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transfer {
+    /// Command with parameters.
+    Command {
+        /// Instruction code.
+        instruction: u8,
+        /// Parameter bytes.
+        params: Vec<u8>,
+    },
+    /// Command with a 16 bit address and parameters.
+    WideCommand {
+        /// Command address.
+        address: u16,
+        /// Parameter bytes.
+        params: Vec<u8>,
+    },
+    /// Write of a 16 bit register.
+    Register {
+        /// Register index.
+        index: u16,
+        /// Register value.
+        value: u16,
+    },
+    /// Pixel data.
+    Pixels(Vec<u8>),
+}
+
+/// Interface which records all transfers for host side tests.
+///
+/// The interface records every command, its parameters and the pixel data
+/// into a `Vec`, which makes it possible to assert model init sequences and
+/// drawing code in unit tests without hardware. Commands with 16 bit
+/// addresses and register writes are recorded as
+/// [`Transfer::WideCommand`] and [`Transfer::Register`].
+///
+/// The interface reports [`InterfaceKind::Serial4Line`] and is only
+/// available if the `test-util` feature is enabled, which requires `alloc`.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{
+///     interface::{RecordingInterface, Transfer},
+///     models::ILI9341Rgb565,
+///     Builder,
+/// };
+///
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+///     .init(&mut delay)
+///     .unwrap();
+///
+/// let (di, _, _) = display.release();
+/// assert!(di.transfers().contains(&Transfer::Command {
+///     instruction: 0x29, // DISPON
+///     params: vec![],
+/// }));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RecordingInterface {
+    transfers: Vec<Transfer>,
+}
+
+impl RecordingInterface {
+    /// Creates a new recording interface.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded transfers.
+    pub fn transfers(&self) -> &[Transfer] {
+        &self.transfers
+    }
+
+    /// Returns the recorded transfers and clears the recording.
+    pub fn take_transfers(&mut self) -> Vec<Transfer> {
+        core::mem::take(&mut self.transfers)
+    }
+
+    /// Clears the recorded transfers.
+    pub fn clear(&mut self) {
+        self.transfers.clear();
+    }
+
+    /// Returns the instruction codes of all recorded 8 bit commands.
+    pub fn instructions(&self) -> Vec<u8> {
+        self.transfers
+            .iter()
+            .filter_map(|transfer| match transfer {
+                Transfer::Command { instruction, .. } => Some(*instruction),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Interface for RecordingInterface {
+    type Word = u8;
+    type Error = core::convert::Infallible;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.transfers.push(Transfer::Command {
+            instruction: command,
+            params: args.to_vec(),
+        });
+        Ok(())
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        self.transfers.push(Transfer::WideCommand {
+            address: command,
+            params: args.to_vec(),
+        });
+        Ok(())
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        self.transfers.push(Transfer::Register { index, value });
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.transfers
+            .push(Transfer::Pixels(pixels.into_iter().flatten().collect()));
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.send_pixels((0..count).map(|_| pixel))
+    }
+}
+
+#[cfg(feature = "async")]
+impl super::AsyncInterface for RecordingInterface {
+    async fn send_command_async(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.send_command(command, args)
+    }
+
+    async fn send_pixels_async<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.send_pixels(pixels)
+    }
+
+    async fn send_repeated_pixel_async<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.send_repeated_pixel(pixel, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn records_commands_and_pixels() {
+        let mut di = RecordingInterface::new();
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        di.send_wide_command(0x2A00, &[0x01]).unwrap();
+        di.send_register(0x0022, 0x1234).unwrap();
+        di.send_repeated_pixel([0xAB, 0xCD], 2).unwrap();
+
+        assert_eq!(di.instructions(), vec![0x2A]);
+        assert_eq!(
+            di.take_transfers()[1..],
+            [
+                Transfer::WideCommand {
+                    address: 0x2A00,
+                    params: vec![0x01],
+                },
+                Transfer::Register {
+                    index: 0x0022,
+                    value: 0x1234,
+                },
+                Transfer::Pixels(vec![0xAB, 0xCD, 0xAB, 0xCD]),
+            ]
+        );
+        assert!(di.transfers().is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+use embedded_hal::digital::OutputPin;
+use rp2040_hal::pio::{
+    InstallError, PIOBuilder, PIOExt, PinDir, PinState, Running, Rx, ShiftDirection, StateMachine,
+    StateMachineIndex, Tx, UninitStateMachine, PIO,
+};
+
+use super::{InterfaceKind, ParallelBus};
+
+/// 8 bit parallel bus driven by a state machine of the RP2040 PIO.
+///
+/// The state machine outputs the data bits `D0..D7` on 8 consecutive GPIOs
+/// and generates the active low write strobe `WRX` by using side-set. The
+/// D/C signal is a normal GPIO, which is only toggled when the state machine
+/// has written all queued words. Chip select and read strobe aren't driven by
+/// the bus and must be tied low and high.
+///
+/// The data and write strobe GPIOs must be switched to the PIO function,
+/// e.g. with `into_function::<FunctionPio0>()`, before the bus is created.
+/// Use [`BusInterface`](super::BusInterface) to use the bus with a
+/// [`Display`](crate::Display).
+///
+/// This bus is only available if the `rp2040` feature is enabled.
+///
+/// # Examples
+///
+/// ```ignore
+/// use mipidsi::interface::{BusInterface, PioBus};
+/// use rp2040_hal::{gpio::FunctionPio0, pio::PIOExt};
+///
+/// let (mut pio, sm0, _, _, _) = pac.PIO0.split(&mut pac.RESETS);
+/// // D0..D7 on GPIO 0..7, WRX on GPIO 8
+/// let _d0 = pins.gpio0.into_function::<FunctionPio0>();
+/// // ... GPIO 1 to 8
+/// let dc = pins.gpio9.into_push_pull_output();
+///
+/// // 125 MHz / 4 = 31.25 MHz state machine clock
+/// let bus = PioBus::new(&mut pio, sm0, 0, 8, dc, (4, 0)).unwrap();
+/// let di = BusInterface::new(bus);
+/// ```
+pub struct PioBus<P: PIOExt, SM: StateMachineIndex, DC> {
+    sm: StateMachine<(P, SM), Running>,
+    rx: Rx<(P, SM)>,
+    tx: Tx<(P, SM)>,
+    dc: DC,
+    // Current level of the D/C pin
+    data: bool,
+}
+
+impl<P, SM, DC> PioBus<P, SM, DC>
+where
+    P: PIOExt,
+    SM: StateMachineIndex,
+    DC: OutputPin,
+{
+    /// Installs the write program and starts the state machine.
+    ///
+    /// `data_base` is the GPIO number of `D0`, `D1..D7` must be connected to
+    /// the following GPIOs. `wr` is the GPIO number of the write strobe.
+    ///
+    /// A write cycle takes 3 state machine clock cycles, with the write
+    /// strobe low for 2 of them. The state machine clock is set by the
+    /// fixed point `clock_divisor`, given as integer and fractional part in
+    /// 1/256, and must be chosen to meet the write cycle timing of the
+    /// controller.
+    pub fn new(
+        pio: &mut PIO<P>,
+        sm: UninitStateMachine<(P, SM)>,
+        data_base: u8,
+        wr: u8,
+        dc: DC,
+        clock_divisor: (u16, u8),
+    ) -> Result<Self, InstallError> {
+        let program = pio.install(&program())?;
+        let (mut sm, rx, tx) = PIOBuilder::from_installed_program(program)
+            .out_pins(data_base, 8)
+            .side_set_pin_base(wr)
+            .out_shift_direction(ShiftDirection::Right)
+            .clock_divisor_fixed_point(clock_divisor.0, clock_divisor.1)
+            .build(sm);
+
+        sm.set_pins([(wr, PinState::High)]);
+        sm.set_pindirs(
+            (data_base..data_base + 8)
+                .chain([wr])
+                .map(|pin| (pin, PinDir::Output)),
+        );
+
+        Ok(Self {
+            sm: sm.start(),
+            rx,
+            tx,
+            dc,
+            data: true,
+        })
+    }
+
+    /// Stops the state machine, uninstalls the program and returns the state
+    /// machine and the D/C pin.
+    pub fn release(mut self, pio: &mut PIO<P>) -> (UninitStateMachine<(P, SM)>, DC) {
+        self.flush();
+        let (sm, program) = self.sm.uninit(self.rx, self.tx);
+        pio.uninstall(program);
+        (sm, self.dc)
+    }
+
+    // Waits until all queued words are written and the state machine is
+    // stalled on the empty TX FIFO.
+    fn flush(&mut self) {
+        while !self.tx.is_empty() {}
+        self.tx.clear_stalled_flag();
+        while !self.tx.has_stalled() {}
+    }
+
+    fn set_dc(&mut self, data: bool) -> Result<(), DC::Error> {
+        if self.data != data {
+            self.flush();
+            if data {
+                self.dc.set_high()?;
+            } else {
+                self.dc.set_low()?;
+            }
+            self.data = data;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, word: u8) {
+        while !self.tx.write(u32::from(word)) {}
+    }
+}
+
+impl<P, SM, DC> ParallelBus for PioBus<P, SM, DC>
+where
+    P: PIOExt,
+    SM: StateMachineIndex,
+    DC: OutputPin,
+{
+    type Word = u8;
+    type Error = DC::Error;
+
+    const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+
+    fn write_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.set_dc(false)?;
+        self.write(command);
+        Ok(())
+    }
+
+    fn write_data(&mut self, word: u8) -> Result<(), Self::Error> {
+        self.set_dc(true)?;
+        self.write(word);
+        Ok(())
+    }
+
+    fn write_data_block(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.set_dc(true)?;
+        for word in words {
+            self.write(*word);
+        }
+        Ok(())
+    }
+
+    fn write_data_repeated(&mut self, word: u8, count: u32) -> Result<(), Self::Error> {
+        self.set_dc(true)?;
+        for _ in 0..count {
+            self.write(word);
+        }
+        Ok(())
+    }
+}
+
+// Returns the write program.
+//
+// ```text
+// .side_set 1
+// .wrap_target
+//     pull block   side 1
+//     out pins, 8  side 0 [1]
+// .wrap
+// ```
+//
+// The state machine stalls on the `pull` with the write strobe high. The
+// controller latches the data on the rising edge of the write strobe, which
+// happens on the next `pull`, while the data pins are unchanged.
+fn program() -> pio::Program<{ pio::RP2040_MAX_PROGRAM_SIZE }> {
+    let mut assembler = pio::Assembler::<{ pio::RP2040_MAX_PROGRAM_SIZE }>::new_with_side_set(
+        pio::SideSet::new(false, 1, false),
+    );
+    assembler.pull_with_side_set(false, true, 1);
+    assembler.out_with_delay_and_side_set(pio::OutDestination::PINS, 8, 1, 0);
+    assembler.assemble_program()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_program() {
+        let program = program();
+        assert_eq!(program.code.as_slice(), [0x90A0, 0x6108]);
+        assert_eq!((program.wrap.source, program.wrap.target), (1, 0));
+        assert_eq!(program.side_set.bits(), 1);
+    }
+}
@@ -0,0 +1,255 @@
+use embedded_hal::{digital::OutputPin, i2c::I2c};
+
+use super::{Interface, InterfaceKind};
+
+/// Size of the SC18IS602 data buffer.
+const DATA_BUFFER_LEN: usize = 200;
+
+/// SC18IS602 interface error
+#[derive(Clone, Copy, Debug)]
+pub enum Sc18is602Error<I2C, DC> {
+    /// I2C bus error
+    I2c(I2C),
+    /// Data/command pin error
+    Dc(DC),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+/// Slave select output of the SC18IS602.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Sc18is602SlaveSelect {
+    /// `SS0` output
+    Ss0 = 0,
+    /// `SS1` output
+    Ss1 = 1,
+    /// `SS2` output
+    Ss2 = 2,
+    /// `SS3` output
+    Ss3 = 3,
+}
+
+/// SPI interface behind a SC18IS602 I2C to SPI bridge.
+///
+/// The SC18IS602 receives the data for each SPI transfer over I2C, prefixed by
+/// a function ID which selects the slave select output, and forwards it to the
+/// SPI bus. This can be used to connect a display if all SPI peripherals of
+/// the MCU are already in use. The data/command signal is driven by a GPIO of
+/// the MCU.
+///
+/// The bridge must be configured for SPI mode 0 or 3 before the interface is
+/// used, the default configuration after power up is SPI mode 0.
+///
+/// The buffer is used to gather the data of each I2C write. The bridge can
+/// only buffer 200 data bytes, larger buffers are truncated to 201 bytes,
+/// including the function ID. I2C is much slower than SPI, this interface is
+/// only suitable for small displays or infrequent updates.
+pub struct Sc18is602Interface<'a, I2C, DC> {
+    i2c: I2C,
+    address: u8,
+    function_id: u8,
+    dc: DC,
+    buffer: &'a mut [u8],
+}
+
+impl<'a, I2C, DC> Sc18is602Interface<'a, I2C, DC>
+where
+    I2C: I2c,
+    DC: OutputPin,
+{
+    /// Create new interface
+    ///
+    /// `address` is the 7 bit I2C address of the bridge and `slave_select`
+    /// the slave select output the display is connected to.
+    pub fn new(
+        i2c: I2C,
+        address: u8,
+        slave_select: Sc18is602SlaveSelect,
+        dc: DC,
+        buffer: &'a mut [u8],
+    ) -> Self {
+        let len = buffer.len().min(DATA_BUFFER_LEN + 1);
+        Self {
+            i2c,
+            address,
+            function_id: 1 << slave_select as u8,
+            dc,
+            buffer: &mut buffer[..len],
+        }
+    }
+
+    /// Release the DC pin and I2C peripheral back, deconstructing the interface
+    pub fn release(self) -> (I2C, DC) {
+        (self.i2c, self.dc)
+    }
+
+    /// Writes the first `len` data bytes in the buffer to the SPI bus.
+    fn flush(&mut self, len: usize) -> Result<(), Sc18is602Error<I2C::Error, DC::Error>> {
+        self.buffer[0] = self.function_id;
+        self.i2c
+            .write(self.address, &self.buffer[..len + 1])
+            .map_err(Sc18is602Error::I2c)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Sc18is602Error<I2C::Error, DC::Error>> {
+        ensure!(self.buffer.len() >= 2, Sc18is602Error::BufferTooSmall);
+
+        for chunk in data.chunks(self.buffer.len() - 1) {
+            self.buffer[1..chunk.len() + 1].copy_from_slice(chunk);
+            self.flush(chunk.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl<I2C, DC> Interface for Sc18is602Interface<'_, I2C, DC>
+where
+    I2C: I2c,
+    DC: OutputPin,
+{
+    type Word = u8;
+    type Error = Sc18is602Error<I2C::Error, DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(Sc18is602Error::Dc)?;
+        self.write(&[command])?;
+        self.dc.set_high().map_err(Sc18is602Error::Dc)?;
+        self.write(args)?;
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() > N, Sc18is602Error::BufferTooSmall);
+
+        let mut arrays = pixels.into_iter();
+
+        let mut done = false;
+        while !done {
+            let mut i = 0;
+            for chunk in self.buffer[1..].chunks_exact_mut(N) {
+                if let Some(array) = arrays.next() {
+                    let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+                    *chunk = array;
+                    i += N;
+                } else {
+                    done = true;
+                    break;
+                };
+            }
+            if i > 0 {
+                self.flush(i)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() > N, Sc18is602Error::BufferTooSmall);
+
+        let fill_count = core::cmp::min(count, ((self.buffer.len() - 1) / N) as u32);
+        let filled_len = fill_count as usize * N;
+        for chunk in self.buffer[1..filled_len + 1].chunks_exact_mut(N) {
+            let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+            *chunk = pixel;
+        }
+
+        let mut count = count;
+        while count > 0 && count >= fill_count {
+            self.flush(filled_len)?;
+            count -= fill_count;
+        }
+        if count != 0 {
+            self.flush(count as usize * N)?;
+        }
+        Ok(())
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.write(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use embedded_hal::i2c::{self, Operation};
+
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    /// I2C bus which records all writes.
+    #[derive(Default)]
+    struct RecordingI2c(Vec<(u8, Vec<u8>)>);
+
+    impl i2c::ErrorType for RecordingI2c {
+        type Error = core::convert::Infallible;
+    }
+
+    impl I2c for RecordingI2c {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.0.push((address, data.to_vec()));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_are_prefixed_with_function_id() {
+        let mut i2c = RecordingI2c::default();
+        let mut buffer = [0; 5];
+        let mut di = Sc18is602Interface::new(
+            &mut i2c,
+            0x28,
+            Sc18is602SlaveSelect::Ss2,
+            MockOutputPin,
+            &mut buffer,
+        );
+
+        di.send_command(0x2A, &[0, 0, 0, 239, 1]).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 3).unwrap();
+
+        assert_eq!(
+            i2c.0,
+            vec![
+                (0x28, vec![0x04, 0x2A]),
+                (0x28, vec![0x04, 0, 0, 0, 239]),
+                (0x28, vec![0x04, 1]),
+                (0x28, vec![0x04, 0x12, 0x34, 0x12, 0x34]),
+                (0x28, vec![0x04, 0x12, 0x34]),
+            ]
+        );
+    }
+
+    #[test]
+    fn buffer_is_limited_to_bridge_buffer() {
+        let mut buffer = [0; 300];
+        let di = Sc18is602Interface::new(
+            RecordingI2c::default(),
+            0x28,
+            Sc18is602SlaveSelect::Ss0,
+            MockOutputPin,
+            &mut buffer,
+        );
+
+        assert_eq!(di.buffer.len(), 201);
+    }
+}
@@ -0,0 +1,134 @@
+use core::marker::PhantomData;
+
+use embedded_hal::spi::SpiDevice;
+
+use super::{InterfaceKind, OutputBus};
+
+/// [`OutputBus`] implementation using 74HC595 shift registers on a SPI bus.
+///
+/// Cheap "serial backpack" boards drive the data bus of a parallel display
+/// through one or two daisy-chained 74HC595 shift registers. The data input
+/// and shift clock of the registers are connected to the SPI bus and the
+/// storage register clock (latch) is connected to the chip select pin, so
+/// that the new value is latched to the outputs when the chip select pin is
+/// deasserted at the end of every SPI transaction.
+///
+/// Use the bus with a [`ParallelInterface`](super::ParallelInterface), which
+/// drives the D/C and WR pins and strobes WR after each latched value.
+///
+/// For 16 bit buses the high byte is shifted out first and ends up in the
+/// second register of the chain.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{
+///     interface::{ParallelInterface, ShiftRegisterBus},
+///     models::ILI9341Rgb565,
+///     Builder,
+/// };
+///
+/// # let spi = mipidsi::_mock::MockSpi;
+/// # let (dc, wr) = (mipidsi::_mock::MockOutputPin, mipidsi::_mock::MockOutputPin);
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let bus = ShiftRegisterBus::<_, u8>::new(spi);
+/// let di = ParallelInterface::new(bus, dc, wr);
+///
+/// let mut display = Builder::new(ILI9341Rgb565, di).init(&mut delay).unwrap();
+/// ```
+pub struct ShiftRegisterBus<SPI, W = u8> {
+    spi: SPI,
+    last: Option<W>,
+    word: PhantomData<W>,
+}
+
+impl<SPI, W> ShiftRegisterBus<SPI, W>
+where
+    SPI: SpiDevice,
+{
+    /// Creates a new bus. This does not change the state of the register outputs.
+    pub fn new(spi: SPI) -> Self {
+        Self {
+            spi,
+            last: None,
+            word: PhantomData,
+        }
+    }
+
+    /// Consumes the bus and returns the SPI device.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+macro_rules! shift_register_bus {
+    ($Word:ty, $InterfaceKind:expr) => {
+        impl<SPI> OutputBus for ShiftRegisterBus<SPI, $Word>
+        where
+            SPI: SpiDevice,
+        {
+            type Word = $Word;
+            type Error = SPI::Error;
+
+            const KIND: InterfaceKind = $InterfaceKind;
+
+            fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error> {
+                // the registers keep the latched value, e.g. while filling the screen
+                if self.last == Some(value) {
+                    return Ok(());
+                }
+
+                self.last = None;
+                self.spi.write(&value.to_be_bytes())?;
+                self.last = Some(value);
+
+                Ok(())
+            }
+        }
+    };
+}
+
+shift_register_bus!(u8, InterfaceKind::Parallel8Bit);
+shift_register_bus!(u16, InterfaceKind::Parallel16Bit);
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    use super::*;
+
+    /// SPI device which records the last write.
+    #[derive(Default)]
+    struct RecordingSpi {
+        writes: usize,
+        last: [u8; 2],
+    }
+
+    impl ErrorType for RecordingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.last[..data.len()].copy_from_slice(data);
+                }
+            }
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn latches_changed_values() {
+        let mut bus = ShiftRegisterBus::<_, u16>::new(RecordingSpi::default());
+        bus.set_value(0x1234).unwrap();
+        bus.set_value(0x1234).unwrap();
+        bus.set_value(0xABCD).unwrap();
+
+        let spi = bus.release();
+        assert_eq!(spi.writes, 2);
+        assert_eq!(spi.last, [0xAB, 0xCD]);
+    }
+}
@@ -1,14 +1,22 @@
-use embedded_hal::{digital::OutputPin, spi::SpiDevice};
+use core::num::NonZeroUsize;
 
-use super::{Interface, InterfaceKind};
+use embedded_hal::{
+    digital::OutputPin,
+    spi::{Operation, SpiDevice},
+};
+
+use super::{Interface, InterfaceKind, ReadInterface};
 
 /// Spi interface error
 #[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
 pub enum SpiError<SPI, DC> {
     /// SPI bus error
     Spi(SPI),
     /// Data/command pin error
     Dc(DC),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
 }
 
 /// Spi interface, including a buffer
@@ -19,10 +27,73 @@ pub enum SpiError<SPI, DC> {
 ///
 /// You may want to use [static_cell](https://crates.io/crates/static_cell)
 /// to obtain a `&'static mut [u8; N]` buffer.
+///
+/// # Examples
+///
+/// An interface with a `'static` buffer doesn't borrow from the stack and the
+/// display can be moved into a task or stored in a static, like any other
+/// owned value:
+///
+/// ```
+/// use embedded_hal::{digital::OutputPin, spi::SpiDevice};
+/// use mipidsi::{interface::SpiInterface, models::ST7789, Builder, Display, NoResetPin};
+///
+/// type SpiDisplay<SPI, DC> = Display<SpiInterface<'static, SPI, DC>, ST7789, NoResetPin>;
+///
+/// fn display_task<SPI: SpiDevice, DC: OutputPin>(display: SpiDisplay<SPI, DC>) {
+///     // ...
+/// }
+///
+/// # let (spi, dc) = (mipidsi::_mock::MockSpi, mipidsi::_mock::MockOutputPin);
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// // e.g. `BUFFER.init([0; 512])` with a `static BUFFER: StaticCell<[u8; 512]>`
+/// let buffer: &'static mut [u8; 512] = Box::leak(Box::new([0; 512]));
+///
+/// let di = SpiInterface::new(spi, dc, buffer);
+/// let display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// display_task(display);
+/// ```
 pub struct SpiInterface<'a, SPI, DC> {
     spi: SPI,
     dc: DC,
     buffer: &'a mut [u8],
+    cs_framing: CsFraming,
+    raw_byte_order: RawByteOrder,
+}
+
+/// Chip select framing of [`SpiInterface`] writes.
+///
+/// The chip select signal is controlled by the [`SpiDevice`] implementation
+/// and is deasserted after every SPI transaction. The framing determines how
+/// writes are split into transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsFraming {
+    /// One transaction for each command byte, block of command parameters,
+    /// and buffer of pixel data.
+    #[default]
+    Transfer,
+    /// One transaction for every `n` bytes.
+    ///
+    /// Some SPI to 8080 bridge boards latch a single bus write per chip select
+    /// cycle and require CS to be deasserted between every command and data
+    /// word. Use this quirk mode with the word size of the bridge, e.g.
+    /// `CsFraming::Every(NonZeroUsize::MIN)` for bridges with an 8 bit shift
+    /// register.
+    Every(NonZeroUsize),
+}
+
+/// Byte order of raw 16 bit pixel data.
+///
+/// Used by [`SpiInterface::with_raw_byte_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RawByteOrder {
+    /// High byte first, which is the byte order expected by the display.
+    #[default]
+    BigEndian,
+    /// Low byte first.
+    ///
+    /// The bytes of each 16 bit word are swapped before they are sent.
+    LittleEndian,
 }
 
 impl<'a, SPI, DC> SpiInterface<'a, SPI, DC>
@@ -32,7 +103,51 @@ where
 {
     /// Create new interface
     pub fn new(spi: SPI, dc: DC, buffer: &'a mut [u8]) -> Self {
-        Self { spi, dc, buffer }
+        Self {
+            spi,
+            dc,
+            buffer,
+            cs_framing: CsFraming::default(),
+            raw_byte_order: RawByteOrder::default(),
+        }
+    }
+
+    /// Sets the chip select framing quirk mode.
+    #[must_use]
+    pub fn with_cs_framing(mut self, cs_framing: CsFraming) -> Self {
+        self.cs_framing = cs_framing;
+        self
+    }
+
+    /// Limits the length of a single SPI write to `max_len` bytes.
+    ///
+    /// Pixel data is written in transfers of at most the buffer length. Some
+    /// SPI or DMA drivers limit the length of a single transfer, e.g. to
+    /// 32 KiB, which can be smaller than the buffer. This method limits the
+    /// used part of the buffer, which splits pixel data into transfers that
+    /// are accepted by the driver without changing the chip select framing.
+    ///
+    /// A `max_len` value of 0 is treated like 1.
+    #[must_use]
+    pub fn with_max_transfer_len(mut self, max_len: usize) -> Self {
+        let len = self.buffer.len().min(max_len.max(1));
+        self.buffer = &mut core::mem::take(&mut self.buffer)[..len];
+        self
+    }
+
+    /// Sets the byte order of raw pixel data.
+    ///
+    /// Some graphics libraries and DMA engines produce Rgb565 framebuffers in
+    /// little endian byte order. With [`RawByteOrder::LittleEndian`] these
+    /// framebuffers can be sent by using
+    /// [`Display::set_pixels_raw`](crate::Display::set_pixels_raw), the bytes
+    /// are swapped while they are copied into the buffer. Pixels which are
+    /// converted from a color type, e.g. by embedded-graphics, are always sent
+    /// in the correct byte order.
+    #[must_use]
+    pub fn with_raw_byte_order(mut self, byte_order: RawByteOrder) -> Self {
+        self.raw_byte_order = byte_order;
+        self
     }
 
     /// Release the DC pin and SPI peripheral back, deconstructing the interface
@@ -53,9 +168,9 @@ where
 
     fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
         self.dc.set_low().map_err(SpiError::Dc)?;
-        self.spi.write(&[command]).map_err(SpiError::Spi)?;
+        write(&mut self.spi, self.cs_framing, &[command]).map_err(SpiError::Spi)?;
         self.dc.set_high().map_err(SpiError::Dc)?;
-        self.spi.write(args).map_err(SpiError::Spi)?;
+        write(&mut self.spi, self.cs_framing, args).map_err(SpiError::Spi)?;
         Ok(())
     }
 
@@ -65,7 +180,7 @@ where
     ) -> Result<(), Self::Error> {
         let mut arrays = pixels.into_iter();
 
-        assert!(self.buffer.len() >= N);
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
 
         let mut done = false;
         while !done {
@@ -80,7 +195,7 @@ where
                     break;
                 };
             }
-            self.spi.write(&self.buffer[..i]).map_err(SpiError::Spi)?;
+            write(&mut self.spi, self.cs_framing, &self.buffer[..i]).map_err(SpiError::Spi)?;
         }
         Ok(())
     }
@@ -90,6 +205,11 @@ where
         pixel: [Self::Word; N],
         count: u32,
     ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
+        if count == 0 {
+            return Ok(());
+        }
+
         let fill_count = core::cmp::min(count, (self.buffer.len() / N) as u32);
         let filled_len = fill_count as usize * N;
         for chunk in self.buffer[..(filled_len)].chunks_exact_mut(N) {
@@ -99,16 +219,322 @@ where
 
         let mut count = count;
         while count >= fill_count {
-            self.spi
-                .write(&self.buffer[..filled_len])
+            write(&mut self.spi, self.cs_framing, &self.buffer[..filled_len])
                 .map_err(SpiError::Spi)?;
             count -= fill_count;
         }
         if count != 0 {
+            write(
+                &mut self.spi,
+                self.cs_framing,
+                &self.buffer[..(count as usize * pixel.len())],
+            )
+            .map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        if self.raw_byte_order == RawByteOrder::LittleEndian {
+            ensure!(self.buffer.len() >= 2, SpiError::BufferTooSmall);
+
+            for chunk in data.chunks(self.buffer.len() & !1) {
+                let buffer = &mut self.buffer[..chunk.len()];
+                for (dst, src) in buffer.chunks_mut(2).zip(chunk.chunks(2)) {
+                    dst.copy_from_slice(src);
+                    dst.reverse();
+                }
+                write(&mut self.spi, self.cs_framing, buffer).map_err(SpiError::Spi)?;
+            }
+            return Ok(());
+        }
+
+        // keep the transfers within the maximum transfer length
+        for chunk in data.chunks(self.buffer.len().max(1)) {
+            write(&mut self.spi, self.cs_framing, chunk).map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+}
+
+fn write<SPI: SpiDevice>(
+    spi: &mut SPI,
+    cs_framing: CsFraming,
+    data: &[u8],
+) -> Result<(), SPI::Error> {
+    match cs_framing {
+        CsFraming::Transfer => spi.write(data),
+        CsFraming::Every(n) => {
+            for chunk in data.chunks(n.get()) {
+                spi.write(chunk)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+impl<SPI, DC> ReadInterface for SpiInterface<'_, SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    fn read_command(&mut self, command: u8, params: &mut [u8]) -> Result<(), Self::Error> {
+        // Reads of more than 8 bits start with a single dummy clock cycle.
+        self.read_command_with_dummy_cycles(command, u8::from(params.len() > 1), params)
+    }
+
+    fn read_command_with_dummy_cycles(
+        &mut self,
+        command: u8,
+        dummy_cycles: u8,
+        params: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        // Whole dummy bytes are skipped, the remaining dummy cycles shift the
+        // returned data and need an extra byte in the buffer.
+        let skip = usize::from(dummy_cycles / 8);
+        let shift = dummy_cycles % 8;
+        let len = skip + params.len() + usize::from(shift != 0);
+        ensure!(
+            dummy_cycles == 0 || self.buffer.len() >= len,
+            SpiError::BufferTooSmall
+        );
+
+        self.dc.set_low().map_err(SpiError::Dc)?;
+
+        if dummy_cycles == 0 {
+            self.spi
+                .transaction(&mut [Operation::Write(&[command]), Operation::Read(params)])
+                .map_err(SpiError::Spi)?;
+        } else {
+            let buffer = &mut self.buffer[..len];
             self.spi
-                .write(&self.buffer[..(count as usize * pixel.len())])
+                .transaction(&mut [Operation::Write(&[command]), Operation::Read(buffer)])
                 .map_err(SpiError::Spi)?;
+
+            let data = &buffer[skip..];
+            if shift == 0 {
+                params.copy_from_slice(&data[..params.len()]);
+            } else {
+                for (param, bytes) in params.iter_mut().zip(data.windows(2)) {
+                    *param = bytes[0] << shift | bytes[1] >> (8 - shift);
+                }
+            }
         }
-        Ok(())
+
+        self.dc.set_high().map_err(SpiError::Dc)
+    }
+
+    fn read_memory(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(SpiError::Dc)?;
+        self.spi
+            .transaction(&mut [Operation::Write(&[command]), Operation::Read(data)])
+            .map_err(SpiError::Spi)?;
+        self.dc.set_high().map_err(SpiError::Dc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    /// SPI device which returns a fixed byte sequence on reads.
+    struct ReadSpi(&'static [u8]);
+
+    impl ErrorType for ReadSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for ReadSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Read(buffer) = operation {
+                    buffer.copy_from_slice(&self.0[..buffer.len()]);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// SPI device which counts transactions.
+    #[derive(Default)]
+    struct CountingSpi(usize);
+
+    impl ErrorType for CountingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for CountingSpi {
+        fn transaction(
+            &mut self,
+            _operations: &mut [Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            self.0 += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn cs_framing_splits_transactions() {
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(CountingSpi::default(), MockOutputPin, &mut buffer);
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        assert_eq!(di.spi.0, 2);
+
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(CountingSpi::default(), MockOutputPin, &mut buffer)
+            .with_cs_framing(CsFraming::Every(NonZeroUsize::MIN));
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        assert_eq!(di.spi.0, 5);
+
+        di.send_repeated_pixel([0x12, 0x34], 3).unwrap();
+        assert_eq!(di.spi.0, 11);
+    }
+
+    #[test]
+    fn max_transfer_len_splits_pixels() {
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(CountingSpi::default(), MockOutputPin, &mut buffer)
+            .with_max_transfer_len(4);
+        di.send_repeated_pixel([0x12, 0x34], 5).unwrap();
+        assert_eq!(di.spi.0, 3);
+    }
+
+    #[test]
+    fn raw_pixels_respect_max_transfer_len() {
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(CountingSpi::default(), MockOutputPin, &mut buffer)
+            .with_max_transfer_len(4);
+        di.send_raw_pixels(&[0; 10]).unwrap();
+        assert_eq!(di.spi.0, 3);
+    }
+
+    /// SPI device which records all written bytes.
+    #[derive(Default)]
+    struct RecordingSpi {
+        data: [u8; 8],
+        len: usize,
+    }
+
+    impl ErrorType for RecordingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.data[self.len..self.len + data.len()].copy_from_slice(data);
+                    self.len += data.len();
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn raw_pixels_little_endian() {
+        let mut buffer = [0; 4];
+        let mut di = SpiInterface::new(RecordingSpi::default(), MockOutputPin, &mut buffer)
+            .with_raw_byte_order(RawByteOrder::LittleEndian);
+        di.send_raw_pixels(&[0x1F, 0x00, 0x00, 0xF8, 0xE0, 0x07])
+            .unwrap();
+
+        let (spi, _) = di.release();
+        assert_eq!(&spi.data[..spi.len], &[0x00, 0x1F, 0xF8, 0x00, 0x07, 0xE0]);
+    }
+
+    #[test]
+    fn repeated_pixel_zero_count() {
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(CountingSpi::default(), MockOutputPin, &mut buffer);
+        di.send_repeated_pixel([0x12, 0x34], 0).unwrap();
+        assert_eq!(di.spi.0, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "no-panic")]
+    fn buffer_too_small_is_error() {
+        let mut buffer = [0; 1];
+        let mut di = SpiInterface::new(CountingSpi::default(), MockOutputPin, &mut buffer);
+        assert!(matches!(
+            di.send_repeated_pixel([0x12, 0x34], 3),
+            Err(SpiError::BufferTooSmall)
+        ));
+        assert!(matches!(
+            di.read_command(0x45, &mut [0; 2]),
+            Err(SpiError::BufferTooSmall)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic]
+    fn buffer_too_small_panics() {
+        let mut buffer = [0; 1];
+        let mut di = SpiInterface::new(CountingSpi::default(), MockOutputPin, &mut buffer);
+        let _ = di.send_pixels([[0x12, 0x34]]);
+    }
+
+    #[test]
+    fn read_single_byte() {
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(ReadSpi(&[0xA5]), MockOutputPin, &mut buffer);
+
+        let mut params = [0];
+        di.read_command(0x0A, &mut params).unwrap();
+        assert_eq!(params, [0xA5]);
+    }
+
+    #[test]
+    fn read_skips_dummy_cycle() {
+        let mut buffer = [0; 8];
+        // 0x12 0x34 shifted right by one dummy bit
+        let mut di = SpiInterface::new(ReadSpi(&[0x09, 0x1A, 0x00]), MockOutputPin, &mut buffer);
+
+        let mut params = [0; 2];
+        di.read_command(0x45, &mut params).unwrap();
+        assert_eq!(params, [0x12, 0x34]);
+    }
+
+    #[test]
+    fn read_with_dummy_cycles() {
+        let mut buffer = [0; 8];
+        // 0x12 0x34 shifted right by 9 dummy bits
+        let mut di = SpiInterface::new(
+            ReadSpi(&[0xFF, 0x09, 0x1A, 0x00]),
+            MockOutputPin,
+            &mut buffer,
+        );
+
+        let mut params = [0; 2];
+        di.read_command_with_dummy_cycles(0x04, 9, &mut params)
+            .unwrap();
+        assert_eq!(params, [0x12, 0x34]);
+
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(ReadSpi(&[0xFF, 0x12, 0x34]), MockOutputPin, &mut buffer);
+        di.read_command_with_dummy_cycles(0x04, 8, &mut params)
+            .unwrap();
+        assert_eq!(params, [0x12, 0x34]);
+
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(ReadSpi(&[0x12, 0x34]), MockOutputPin, &mut buffer);
+        di.read_command_with_dummy_cycles(0x04, 0, &mut params)
+            .unwrap();
+        assert_eq!(params, [0x12, 0x34]);
+    }
+
+    #[test]
+    fn read_memory_is_not_realigned() {
+        let mut buffer = [0; 8];
+        let mut di = SpiInterface::new(ReadSpi(&[0x00, 0xF8, 0xFC]), MockOutputPin, &mut buffer);
+
+        let mut data = [0; 3];
+        di.read_memory(0x2E, &mut data).unwrap();
+        assert_eq!(data, [0x00, 0xF8, 0xFC]);
     }
 }
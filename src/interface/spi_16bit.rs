@@ -0,0 +1,180 @@
+use embedded_hal::{digital::OutputPin, spi::SpiDevice};
+
+use super::{Interface, InterfaceKind, SpiError};
+
+/// SPI interface with 16 bit pixel frames, including a buffer.
+///
+/// Some SPI peripherals, e.g. on STM32 or RP2040 MCUs, can transfer 16 bit
+/// frames, which halves the FIFO and interrupt overhead compared to 8 bit
+/// frames. This interface sends commands and parameters as 8 bit frames and
+/// pixel data as 16 bit frames, Rgb565 pixels are written as a single native
+/// `u16` word. The SPI device must therefore implement [`SpiDevice`] for both
+/// `u8` and `u16` words.
+///
+/// The buffer is used to gather batches of pixel data to be sent over SPI.
+/// Larger buffers will genererally be faster (with diminishing returns), at the
+/// expense of using more RAM.
+pub struct Spi16BitInterface<'a, SPI, DC> {
+    spi: SPI,
+    dc: DC,
+    buffer: &'a mut [u16],
+}
+
+impl<'a, SPI, DC> Spi16BitInterface<'a, SPI, DC>
+where
+    SPI: SpiDevice<u8> + SpiDevice<u16>,
+    DC: OutputPin,
+{
+    /// Create new interface
+    pub fn new(spi: SPI, dc: DC, buffer: &'a mut [u16]) -> Self {
+        Self { spi, dc, buffer }
+    }
+
+    /// Release the DC pin and SPI peripheral back, deconstructing the interface
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+}
+
+impl<SPI, DC> Interface for Spi16BitInterface<'_, SPI, DC>
+where
+    SPI: SpiDevice<u8> + SpiDevice<u16>,
+    DC: OutputPin,
+{
+    type Word = u16;
+    type Error = SpiError<SPI::Error, DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(SpiError::Dc)?;
+        SpiDevice::<u8>::write(&mut self.spi, &[command]).map_err(SpiError::Spi)?;
+        self.dc.set_high().map_err(SpiError::Dc)?;
+        SpiDevice::<u8>::write(&mut self.spi, args).map_err(SpiError::Spi)?;
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut arrays = pixels.into_iter();
+
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
+
+        let mut done = false;
+        while !done {
+            let mut i = 0;
+            for chunk in self.buffer.chunks_exact_mut(N) {
+                if let Some(array) = arrays.next() {
+                    let chunk: &mut [u16; N] = chunk.try_into().unwrap();
+                    *chunk = array;
+                    i += N;
+                } else {
+                    done = true;
+                    break;
+                };
+            }
+            SpiDevice::<u16>::write(&mut self.spi, &self.buffer[..i]).map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
+        if count == 0 {
+            return Ok(());
+        }
+
+        let fill_count = core::cmp::min(count, (self.buffer.len() / N) as u32);
+        let filled_len = fill_count as usize * N;
+        for chunk in self.buffer[..filled_len].chunks_exact_mut(N) {
+            let chunk: &mut [u16; N] = chunk.try_into().unwrap();
+            *chunk = pixel;
+        }
+
+        let mut count = count;
+        while count >= fill_count {
+            SpiDevice::<u16>::write(&mut self.spi, &self.buffer[..filled_len])
+                .map_err(SpiError::Spi)?;
+            count -= fill_count;
+        }
+        if count != 0 {
+            SpiDevice::<u16>::write(&mut self.spi, &self.buffer[..count as usize * N])
+                .map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        SpiDevice::<u16>::write(&mut self.spi, data).map_err(SpiError::Spi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    use crate::{_mock::MockOutputPin, interface::InterfacePixelFormat};
+
+    use super::*;
+
+    /// SPI device which records the number of 8 and 16 bit frames.
+    #[derive(Default)]
+    struct FrameCountingSpi {
+        bytes: usize,
+        words: usize,
+        last_word: u16,
+    }
+
+    impl ErrorType for FrameCountingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice<u8> for FrameCountingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.bytes += data.len();
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl SpiDevice<u16> for FrameCountingSpi {
+        fn transaction(
+            &mut self,
+            operations: &mut [Operation<'_, u16>],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.words += data.len();
+                    self.last_word = data[data.len() - 1];
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn commands_use_bytes_and_pixels_use_words() {
+        let mut buffer = [0; 4];
+        let mut di =
+            Spi16BitInterface::new(FrameCountingSpi::default(), MockOutputPin, &mut buffer);
+
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        Rgb565::send_pixels(&mut di, [Rgb565::RED; 6]).unwrap();
+        Rgb565::send_repeated_pixel(&mut di, Rgb565::BLUE, 5).unwrap();
+
+        let (spi, _) = di.release();
+        assert_eq!(spi.bytes, 5);
+        assert_eq!(spi.words, 11);
+        assert_eq!(spi.last_word, 0x001F);
+    }
+}
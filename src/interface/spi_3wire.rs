@@ -0,0 +1,233 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Interface, InterfaceKind};
+
+/// 3-wire SPI interface error
+#[derive(Clone, Copy, Debug)]
+pub enum Spi3WireError<SPI> {
+    /// SPI bus error
+    Spi(SPI),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+/// 3-wire SPI interface with 9 bit words, including a buffer.
+///
+/// Some boards only connect the SDA, SCL and CS pins of the display
+/// controller. In this mode the controller expects 9 bit words, the first bit
+/// of each word selects between command (0) and data (1) and replaces the
+/// D/C pin.
+///
+/// Most SPI peripherals only support 8 bit words, so the 9 bit words are
+/// packed into a byte stream, eight 9 bit words are sent as nine bytes.
+/// Every write is padded with zero bits to the next byte boundary. The
+/// padding forms an incomplete word, which the controller discards when the
+/// chip select signal is deasserted at the end of the SPI transaction.
+///
+/// The buffer is used to pack the words and must be at least 3 bytes long.
+/// Larger buffers will genererally be faster, because the commands and pixel
+/// data are split into fewer SPI transactions.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::Spi3WireInterface, models::ST7789, Builder};
+///
+/// # let spi = mipidsi::_mock::MockSpi;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut buffer = [0u8; 576];
+/// let di = Spi3WireInterface::new(spi, &mut buffer);
+///
+/// let mut display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// ```
+pub struct Spi3WireInterface<'a, SPI> {
+    spi: SPI,
+    buffer: &'a mut [u8],
+    /// Number of complete bytes in the buffer.
+    len: usize,
+    /// Bits which haven't been written to the buffer yet.
+    bits: u32,
+    /// Number of valid bits in `bits`.
+    bit_count: u32,
+}
+
+impl<'a, SPI> Spi3WireInterface<'a, SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Create new interface
+    pub fn new(spi: SPI, buffer: &'a mut [u8]) -> Self {
+        Self {
+            spi,
+            buffer,
+            len: 0,
+            bits: 0,
+            bit_count: 0,
+        }
+    }
+
+    /// Release the SPI peripheral back, deconstructing the interface
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Appends a 9 bit word to the buffer.
+    fn push(&mut self, data: bool, byte: u8) -> Result<(), Spi3WireError<SPI::Error>> {
+        // A word adds up to two bytes and the padding of the flush one more.
+        if self.len + 3 > self.buffer.len() {
+            ensure!(self.buffer.len() >= 3, Spi3WireError::BufferTooSmall);
+            self.flush()?;
+        }
+
+        self.bits = self.bits << 9 | u32::from(data) << 8 | u32::from(byte);
+        self.bit_count += 9;
+        while self.bit_count >= 8 {
+            self.bit_count -= 8;
+            self.buffer[self.len] = (self.bits >> self.bit_count) as u8;
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Pads the buffered words to a byte boundary and writes them to the bus.
+    fn flush(&mut self) -> Result<(), Spi3WireError<SPI::Error>> {
+        if self.bit_count > 0 {
+            self.buffer[self.len] = (self.bits << (8 - self.bit_count)) as u8;
+            self.len += 1;
+        }
+
+        let len = core::mem::take(&mut self.len);
+        self.bits = 0;
+        self.bit_count = 0;
+
+        if len > 0 {
+            self.spi
+                .write(&self.buffer[..len])
+                .map_err(Spi3WireError::Spi)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<SPI> Interface for Spi3WireInterface<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    type Word = u8;
+    type Error = Spi3WireError<SPI::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial3Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.push(false, command)?;
+        for arg in args {
+            self.push(true, *arg)?;
+        }
+        self.flush()
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        for pixel in pixels {
+            for byte in pixel {
+                self.push(true, byte)?;
+            }
+        }
+        self.flush()
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        for _ in 0..count {
+            for byte in pixel {
+                self.push(true, byte)?;
+            }
+        }
+        self.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    use super::*;
+
+    /// SPI device which records the written bytes.
+    #[derive(Default)]
+    struct RecordingSpi {
+        data: [u8; 16],
+        len: usize,
+        transactions: usize,
+    }
+
+    impl ErrorType for RecordingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.data[self.len..self.len + data.len()].copy_from_slice(data);
+                    self.len += data.len();
+                }
+            }
+            self.transactions += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn packs_dc_bit() {
+        let mut buffer = [0u8; 16];
+        let mut di = Spi3WireInterface::new(RecordingSpi::default(), &mut buffer);
+
+        di.send_command(0x2A, &[0x00, 0xEF]).unwrap();
+
+        // 0_0010_1010 1_0000_0000 1_1110_1111 + 5 padding bits
+        let spi = di.release();
+        assert_eq!(&spi.data[..spi.len], &[0x15, 0x40, 0x3D, 0xE0]);
+        assert_eq!(spi.transactions, 1);
+    }
+
+    #[test]
+    fn splits_writes() {
+        let mut buffer = [0u8; 4];
+        let mut di = Spi3WireInterface::new(RecordingSpi::default(), &mut buffer);
+
+        di.send_repeated_pixel([0xFF], 3).unwrap();
+
+        // 1_1111_1111 1_1111_1111 + 6 padding bits, 1_1111_1111 + 7 padding bits
+        let spi = di.release();
+        assert_eq!(&spi.data[..spi.len], &[0xFF, 0xFF, 0xC0, 0xFF, 0x80]);
+        assert_eq!(spi.transactions, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "no-panic")]
+    fn buffer_too_small_is_error() {
+        let mut buffer = [0u8; 2];
+        let mut di = Spi3WireInterface::new(RecordingSpi::default(), &mut buffer);
+        assert!(matches!(
+            di.send_command(0x01, &[]),
+            Err(Spi3WireError::BufferTooSmall)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic]
+    fn buffer_too_small_panics() {
+        let mut buffer = [0u8; 2];
+        let mut di = Spi3WireInterface::new(RecordingSpi::default(), &mut buffer);
+        let _ = di.send_command(0x01, &[]);
+    }
+}
@@ -0,0 +1,277 @@
+use embedded_hal::{digital::OutputPin, spi::SpiDevice};
+use embedded_hal_async::spi::SpiDevice as AsyncSpiDevice;
+
+use super::{AsyncInterface, Interface, InterfaceKind, SpiError};
+
+/// Async Spi interface, including a buffer
+///
+/// Like [`SpiInterface`](super::SpiInterface), but pixel data is sent by
+/// using the `SpiDevice` trait of `embedded-hal-async`. This allows DMA driven
+/// SPI drivers, e.g. of esp-hal or embassy, to transfer the pixels without
+/// blocking the executor. See [`AsyncInterface`] for the async drawing
+/// methods.
+///
+/// Commands are sent by using the blocking `SpiDevice` trait, because the
+/// display is initialized and configured by the blocking
+/// [`Model`](crate::models::Model) methods. The SPI device must implement both
+/// traits, like `ExclusiveDevice` of `embedded-hal-bus` with an SPI bus which
+/// supports blocking and async transfers.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::Rgb565;
+/// use mipidsi::{interface::AsyncSpiInterface, models::ST7789, Builder};
+///
+/// # let (spi, dc) = (mipidsi::_mock::MockSpi, mipidsi::_mock::MockOutputPin);
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// # async {
+/// let mut buffer = [0; 512];
+/// let di = AsyncSpiInterface::new(spi, dc, &mut buffer);
+/// let mut display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+///
+/// let colors = core::iter::repeat(Rgb565::new(31, 0, 0)).take(100 * 100);
+/// display.set_pixels_async(0, 0, 99, 99, colors).await.unwrap();
+/// # };
+/// ```
+pub struct AsyncSpiInterface<'a, SPI, DC> {
+    spi: SPI,
+    dc: DC,
+    buffer: &'a mut [u8],
+}
+
+impl<'a, SPI, DC> AsyncSpiInterface<'a, SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// Create new interface
+    pub fn new(spi: SPI, dc: DC, buffer: &'a mut [u8]) -> Self {
+        Self { spi, dc, buffer }
+    }
+
+    /// Release the DC pin and SPI peripheral back, deconstructing the interface
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+}
+
+impl<SPI, DC> Interface for AsyncSpiInterface<'_, SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    type Word = u8;
+    type Error = SpiError<SPI::Error, DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(SpiError::Dc)?;
+        SpiDevice::write(&mut self.spi, &[command]).map_err(SpiError::Spi)?;
+        self.dc.set_high().map_err(SpiError::Dc)?;
+        SpiDevice::write(&mut self.spi, args).map_err(SpiError::Spi)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
+
+        let mut pixels = pixels.into_iter();
+        loop {
+            let len = fill(self.buffer, &mut pixels);
+            if len == 0 {
+                return Ok(());
+            }
+            SpiDevice::write(&mut self.spi, &self.buffer[..len]).map_err(SpiError::Spi)?;
+        }
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
+
+        let filled = fill_repeated(self.buffer, pixel, count);
+        for len in chunk_lengths(filled, N, count) {
+            SpiDevice::write(&mut self.spi, &self.buffer[..len]).map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        for chunk in data.chunks(self.buffer.len().max(1)) {
+            SpiDevice::write(&mut self.spi, chunk).map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, DC> AsyncInterface for AsyncSpiInterface<'_, SPI, DC>
+where
+    SPI: SpiDevice + AsyncSpiDevice,
+    DC: OutputPin,
+{
+    async fn send_command_async(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(SpiError::Dc)?;
+        AsyncSpiDevice::write(&mut self.spi, &[command])
+            .await
+            .map_err(SpiError::Spi)?;
+        self.dc.set_high().map_err(SpiError::Dc)?;
+        AsyncSpiDevice::write(&mut self.spi, args)
+            .await
+            .map_err(SpiError::Spi)
+    }
+
+    async fn send_pixels_async<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
+
+        let mut pixels = pixels.into_iter();
+        loop {
+            let len = fill(self.buffer, &mut pixels);
+            if len == 0 {
+                return Ok(());
+            }
+            AsyncSpiDevice::write(&mut self.spi, &self.buffer[..len])
+                .await
+                .map_err(SpiError::Spi)?;
+        }
+    }
+
+    async fn send_repeated_pixel_async<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() >= N, SpiError::BufferTooSmall);
+
+        let filled = fill_repeated(self.buffer, pixel, count);
+        for len in chunk_lengths(filled, N, count) {
+            AsyncSpiDevice::write(&mut self.spi, &self.buffer[..len])
+                .await
+                .map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+
+    async fn send_raw_pixels_async(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        for chunk in data.chunks(self.buffer.len().max(1)) {
+            AsyncSpiDevice::write(&mut self.spi, chunk)
+                .await
+                .map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+}
+
+// Copies pixels into the buffer until it is full and returns the number of
+// used bytes.
+fn fill<const N: usize>(buffer: &mut [u8], pixels: &mut impl Iterator<Item = [u8; N]>) -> usize {
+    let mut len = 0;
+    for (chunk, pixel) in buffer.chunks_exact_mut(N).zip(pixels) {
+        chunk.copy_from_slice(&pixel);
+        len += N;
+    }
+    len
+}
+
+// Fills the buffer with up to `count` copies of the pixel and returns the
+// number of filled pixels.
+fn fill_repeated<const N: usize>(buffer: &mut [u8], pixel: [u8; N], count: u32) -> u32 {
+    let filled = core::cmp::min(count, (buffer.len() / N) as u32);
+    for chunk in buffer.chunks_exact_mut(N).take(filled as usize) {
+        chunk.copy_from_slice(&pixel);
+    }
+    filled
+}
+
+// Returns the lengths in bytes of the writes which send `count` pixels from a
+// buffer that holds `filled` pixels.
+fn chunk_lengths(filled: u32, n: usize, count: u32) -> impl Iterator<Item = usize> {
+    let full = count.checked_div(filled).unwrap_or(0);
+    let rest = count.checked_rem(filled).unwrap_or(0);
+    core::iter::repeat(filled as usize * n)
+        .take(full as usize)
+        .chain((rest != 0).then_some(rest as usize * n))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    use crate::{_mock::MockOutputPin, interface::block_on};
+
+    use super::*;
+
+    /// SPI device which records the length of all writes.
+    #[derive(Default)]
+    struct RecordingSpi {
+        blocking: Vec<usize>,
+        asynchronous: Vec<usize>,
+    }
+
+    impl ErrorType for RecordingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.blocking.push(data.len());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl AsyncSpiDevice for RecordingSpi {
+        async fn transaction(
+            &mut self,
+            operations: &mut [Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.asynchronous.push(data.len());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pixels_are_sent_async() {
+        let mut buffer = [0; 8];
+        let mut di = AsyncSpiInterface::new(RecordingSpi::default(), MockOutputPin, &mut buffer);
+
+        di.send_command(0x2C, &[]).unwrap();
+        block_on(di.send_pixels_async([[0x12, 0x34]; 5])).unwrap();
+        block_on(di.send_repeated_pixel_async([0x12, 0x34, 0x56], 5)).unwrap();
+        block_on(di.send_raw_pixels_async(&[0; 10])).unwrap();
+
+        let (spi, _) = di.release();
+        assert_eq!(spi.blocking, [1, 0]);
+        assert_eq!(spi.asynchronous, [8, 2, 6, 6, 3, 8, 2]);
+    }
+
+    #[test]
+    fn blocking_pixels() {
+        let mut buffer = [0; 8];
+        let mut di = AsyncSpiInterface::new(RecordingSpi::default(), MockOutputPin, &mut buffer);
+
+        di.send_pixels([[0x12, 0x34]; 4]).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 0).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 4).unwrap();
+
+        assert_eq!(di.release().0.blocking, [8, 8]);
+    }
+}
@@ -0,0 +1,336 @@
+use embedded_hal::{digital::OutputPin, spi::SpiBus};
+
+use super::{Interface, InterfaceKind};
+
+/// Memory write commands, which are followed by pixel data.
+const MEMORY_WRITE_COMMANDS: [u8; 2] = [0x2C, 0x3C];
+
+/// SPI bus interface error
+#[derive(Clone, Copy, Debug)]
+pub enum SpiBusError<SPI, DC, CS> {
+    /// SPI bus error
+    Spi(SPI),
+    /// Data/command pin error
+    Dc(DC),
+    /// Chip select pin error
+    Cs(CS),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+/// SPI interface with chip select management, including a buffer.
+///
+/// [`SpiInterface`](super::SpiInterface) uses a [`SpiDevice`], which
+/// deasserts the chip select signal after every transaction, including
+/// between the memory write command and the pixel data. Some controllers, like
+/// the ST7789, don't tolerate these chip select toggles well in the middle of
+/// a frame.
+///
+/// This interface uses a [`SpiBus`] and a chip select [`OutputPin`] instead.
+/// The chip select signal is kept asserted from a memory write command
+/// (`RAMWR` or `RAMWRC`) until the end of the following pixel data and is
+/// deasserted after all other commands, which allows other devices to use the
+/// bus between two transfers.
+///
+/// The buffer is used to gather batches of pixel data to be sent over SPI.
+/// Larger buffers will genererally be faster (with diminishing returns), at
+/// the expense of using more RAM.
+///
+/// [`SpiDevice`]: embedded_hal::spi::SpiDevice
+pub struct SpiBusInterface<'a, SPI, DC, CS> {
+    spi: SPI,
+    dc: DC,
+    cs: CS,
+    buffer: &'a mut [u8],
+}
+
+impl<'a, SPI, DC, CS> SpiBusInterface<'a, SPI, DC, CS>
+where
+    SPI: SpiBus,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    /// Create new interface
+    ///
+    /// The chip select pin must be deasserted (high) when the interface is
+    /// created.
+    pub fn new(spi: SPI, dc: DC, cs: CS, buffer: &'a mut [u8]) -> Self {
+        Self {
+            spi,
+            dc,
+            cs,
+            buffer,
+        }
+    }
+
+    /// Release the DC and CS pins and SPI peripheral back, deconstructing the interface
+    pub fn release(self) -> (SPI, DC, CS) {
+        (self.spi, self.dc, self.cs)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), SpiBusError<SPI::Error, DC::Error, CS::Error>> {
+        self.spi.write(data).map_err(SpiBusError::Spi)?;
+        self.spi.flush().map_err(SpiBusError::Spi)
+    }
+
+    fn end_transfer(&mut self) -> Result<(), SpiBusError<SPI::Error, DC::Error, CS::Error>> {
+        self.cs.set_high().map_err(SpiBusError::Cs)
+    }
+
+    /// Runs `f` with the chip select asserted.
+    ///
+    /// The chip select is deasserted afterwards, unless `keep_asserted` is
+    /// set and `f` succeeded. It is always deasserted on errors, so that a
+    /// failed transfer doesn't block the bus for other devices.
+    fn transfer<F>(
+        &mut self,
+        keep_asserted: bool,
+        f: F,
+    ) -> Result<(), SpiBusError<SPI::Error, DC::Error, CS::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), SpiBusError<SPI::Error, DC::Error, CS::Error>>,
+    {
+        let result = self
+            .cs
+            .set_low()
+            .map_err(SpiBusError::Cs)
+            .and_then(|()| f(self));
+
+        if keep_asserted && result.is_ok() {
+            return result;
+        }
+
+        let end = self.end_transfer();
+        result.and(end)
+    }
+}
+
+impl<SPI, DC, CS> Interface for SpiBusInterface<'_, SPI, DC, CS>
+where
+    SPI: SpiBus,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    type Word = u8;
+    type Error = SpiBusError<SPI::Error, DC::Error, CS::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        // keep the chip select asserted for the following pixel data
+        let keep_asserted = MEMORY_WRITE_COMMANDS.contains(&command);
+
+        self.transfer(keep_asserted, |di| {
+            di.dc.set_low().map_err(SpiBusError::Dc)?;
+            di.write(&[command])?;
+            di.dc.set_high().map_err(SpiBusError::Dc)?;
+            di.write(args)
+        })
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut arrays = pixels.into_iter();
+
+        self.transfer(false, |di| {
+            ensure!(di.buffer.len() >= N, SpiBusError::BufferTooSmall);
+
+            let mut done = false;
+            while !done {
+                let mut i = 0;
+                for chunk in di.buffer.chunks_exact_mut(N) {
+                    if let Some(array) = arrays.next() {
+                        let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+                        *chunk = array;
+                        i += N;
+                    } else {
+                        done = true;
+                        break;
+                    };
+                }
+                di.spi.write(&di.buffer[..i]).map_err(SpiBusError::Spi)?;
+            }
+            di.spi.flush().map_err(SpiBusError::Spi)
+        })
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.transfer(false, |di| {
+            ensure!(di.buffer.len() >= N, SpiBusError::BufferTooSmall);
+
+            let fill_count = core::cmp::min(count, (di.buffer.len() / N) as u32);
+            let filled_len = fill_count as usize * N;
+            for chunk in di.buffer[..filled_len].chunks_exact_mut(N) {
+                let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+                *chunk = pixel;
+            }
+
+            let mut count = count;
+            while count > 0 && count >= fill_count {
+                di.spi
+                    .write(&di.buffer[..filled_len])
+                    .map_err(SpiBusError::Spi)?;
+                count -= fill_count;
+            }
+            if count != 0 {
+                di.spi
+                    .write(&di.buffer[..count as usize * N])
+                    .map_err(SpiBusError::Spi)?;
+            }
+            di.spi.flush().map_err(SpiBusError::Spi)
+        })
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.transfer(false, |di| di.write(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use embedded_hal::{digital, spi};
+
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    /// Chip select pin which counts the deasserts.
+    struct CountingPin<'a>(&'a Cell<u32>);
+
+    impl digital::ErrorType for CountingPin<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for CountingPin<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.set(self.0.get() + 1);
+            Ok(())
+        }
+    }
+
+    struct NopBus;
+
+    /// SPI bus which fails every write.
+    struct FailingBus;
+
+    impl spi::ErrorType for FailingBus {
+        type Error = spi::ErrorKind;
+    }
+
+    impl SpiBus for FailingBus {
+        fn read(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn write(&mut self, _words: &[u8]) -> Result<(), Self::Error> {
+            Err(spi::ErrorKind::Other)
+        }
+
+        fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl spi::ErrorType for NopBus {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiBus for NopBus {
+        fn read(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn write(&mut self, _words: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn cs_stays_asserted_for_memory_writes() {
+        let deasserts = Cell::new(0);
+        let mut buffer = [0; 4];
+        let mut di =
+            SpiBusInterface::new(NopBus, MockOutputPin, CountingPin(&deasserts), &mut buffer);
+
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        assert_eq!(deasserts.get(), 1);
+
+        di.send_command(0x2C, &[]).unwrap();
+        assert_eq!(deasserts.get(), 1);
+
+        di.send_repeated_pixel([0x12, 0x34], 5).unwrap();
+        assert_eq!(deasserts.get(), 2);
+    }
+
+    #[test]
+    fn cs_is_deasserted_on_errors() {
+        let deasserts = Cell::new(0);
+        let mut buffer = [0; 4];
+        let mut di = SpiBusInterface::new(
+            FailingBus,
+            MockOutputPin,
+            CountingPin(&deasserts),
+            &mut buffer,
+        );
+
+        assert!(di.send_command(0x2C, &[]).is_err());
+        assert_eq!(deasserts.get(), 1);
+
+        assert!(di.send_pixels([[0x12, 0x34]; 3]).is_err());
+        assert_eq!(deasserts.get(), 2);
+
+        assert!(di.send_repeated_pixel([0x12, 0x34], 5).is_err());
+        assert_eq!(deasserts.get(), 3);
+
+        assert!(di.send_raw_pixels(&[0x12, 0x34]).is_err());
+        assert_eq!(deasserts.get(), 4);
+    }
+
+    #[cfg(feature = "no-panic")]
+    #[test]
+    fn cs_is_deasserted_if_buffer_is_too_small() {
+        let deasserts = Cell::new(0);
+        let mut buffer = [0; 2];
+        let mut di =
+            SpiBusInterface::new(NopBus, MockOutputPin, CountingPin(&deasserts), &mut buffer);
+
+        di.send_command(0x2C, &[]).unwrap();
+        assert!(matches!(
+            di.send_pixels([[0x12, 0x34, 0x56]]),
+            Err(SpiBusError::BufferTooSmall)
+        ));
+        assert_eq!(deasserts.get(), 1);
+    }
+}
@@ -0,0 +1,296 @@
+use embedded_hal::{digital::OutputPin, spi::SpiDevice};
+
+use super::{Interface, InterfaceKind, SpiError};
+
+/// SSD2825 register addresses.
+pub mod ssd2825_reg {
+    /// Configuration register.
+    pub const CONFIGURATION: u8 = 0xB7;
+    /// Virtual channel control register.
+    pub const VC_CONTROL: u8 = 0xB8;
+    /// PLL control register.
+    pub const PLL_CONTROL: u8 = 0xB9;
+    /// PLL configuration register.
+    pub const PLL_CONFIGURATION: u8 = 0xBA;
+    /// Clock control register.
+    pub const CLOCK_CONTROL: u8 = 0xBB;
+    /// Packet size control register 1.
+    pub const PACKET_SIZE_CONTROL_1: u8 = 0xBC;
+    /// Packet size control register 2.
+    pub const PACKET_SIZE_CONTROL_2: u8 = 0xBD;
+    /// Packet drop register.
+    pub const PACKET_DROP: u8 = 0xBF;
+    /// Lane configuration register.
+    pub const LANE_CONFIGURATION: u8 = 0xDE;
+}
+
+use ssd2825_reg::*;
+
+/// Configuration register value for DCS packets with ECC and EOT packets enabled.
+const CONFIGURATION_DCS: u16 = 0x0340;
+/// High speed mode bit of the configuration register.
+const CONFIGURATION_HS: u16 = 0x0001;
+/// Low power mode bit of the configuration register.
+const CONFIGURATION_LPE: u16 = 0x0400;
+
+/// DCS Write Memory Continue instruction.
+const WRITE_MEMORY_CONTINUE: u8 = 0x3C;
+
+/// SSD2825 bridge configuration.
+///
+/// The values are written to the PLL, clock and lane configuration
+/// registers by [`Ssd2825Interface::configure`]. Refer to the SSD2825
+/// datasheet and the panel datasheet for the correct values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ssd2825Config {
+    /// Value of the PLL configuration register (0xBA).
+    pub pll_configuration: u16,
+    /// Value of the clock control register (0xBB), i.e. the LP clock divider.
+    pub clock_control: u16,
+    /// Number of MIPI DSI data lanes (1 to 4).
+    pub lanes: u8,
+    /// Send pixel data in high speed mode.
+    pub high_speed_pixels: bool,
+}
+
+/// SSD2825 SPI to MIPI DSI bridge interface.
+///
+/// This interface tunnels DCS commands and pixel data through a Solomon
+/// Systech SSD2825 bridge, which makes it possible to use MIPI DSI panels
+/// with MCUs that only have a SPI peripheral. The bridge must be configured
+/// for the 4-wire 8-bit SPI mode, with the SDC pin connected to `dc`.
+///
+/// Every DCS command is sent as a DCS packet by the bridge. Pixel data is
+/// split into packets which fit into the buffer, each of which is sent as a
+/// `Write Memory Continue` command. Larger buffers will result in larger
+/// packets and less overhead.
+///
+/// The interface reports [`InterfaceKind::Serial4Line`], because DSI packets
+/// use the same byte oriented pixel formats as the SPI interface, which
+/// allows all existing models to be used with this interface.
+pub struct Ssd2825Interface<'a, SPI, DC> {
+    spi: SPI,
+    dc: DC,
+    buffer: &'a mut [u8],
+    high_speed_pixels: bool,
+}
+
+impl<'a, SPI, DC> Ssd2825Interface<'a, SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// Create new interface
+    ///
+    /// The bridge isn't configured by this method, use [`configure`](Self::configure)
+    /// before initializing the display.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is shorter than 4 bytes. If the `no-panic`
+    /// feature is enabled, sending data returns [`SpiError::BufferTooSmall`]
+    /// instead.
+    pub fn new(spi: SPI, dc: DC, buffer: &'a mut [u8]) -> Self {
+        #[cfg(not(feature = "no-panic"))]
+        assert!(buffer.len() >= 4);
+
+        Self {
+            spi,
+            dc,
+            buffer,
+            high_speed_pixels: false,
+        }
+    }
+
+    /// Release the DC pin and SPI peripheral back, deconstructing the interface
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+
+    /// Configures the bridge PLL, LP clock and lanes.
+    pub fn configure(
+        &mut self,
+        config: Ssd2825Config,
+    ) -> Result<(), SpiError<SPI::Error, DC::Error>> {
+        self.write_register(PLL_CONTROL, 0x0000)?;
+        self.write_register(PLL_CONFIGURATION, config.pll_configuration)?;
+        self.write_register(CLOCK_CONTROL, config.clock_control)?;
+        self.write_register(LANE_CONFIGURATION, u16::from(config.lanes.clamp(1, 4) - 1))?;
+        self.write_register(PLL_CONTROL, 0x0001)?;
+        self.write_register(VC_CONTROL, 0x0000)?;
+        self.high_speed_pixels = config.high_speed_pixels;
+
+        Ok(())
+    }
+
+    /// Writes a 16 bit bridge register.
+    pub fn write_register(
+        &mut self,
+        register: u8,
+        value: u16,
+    ) -> Result<(), SpiError<SPI::Error, DC::Error>> {
+        self.dc.set_low().map_err(SpiError::Dc)?;
+        self.spi.write(&[register]).map_err(SpiError::Spi)?;
+        self.dc.set_high().map_err(SpiError::Dc)?;
+        self.spi.write(&value.to_le_bytes()).map_err(SpiError::Spi)
+    }
+
+    // Sends the first `len` bytes of the buffer as a single DCS packet.
+    fn send_packet(
+        &mut self,
+        len: usize,
+        high_speed: bool,
+    ) -> Result<(), SpiError<SPI::Error, DC::Error>> {
+        let mode = if high_speed {
+            CONFIGURATION_HS
+        } else {
+            CONFIGURATION_LPE
+        };
+        self.write_register(CONFIGURATION, CONFIGURATION_DCS | mode)?;
+
+        let len = len as u32;
+        self.write_register(PACKET_SIZE_CONTROL_1, len as u16)?;
+        self.write_register(PACKET_SIZE_CONTROL_2, (len >> 16) as u16)?;
+
+        self.dc.set_low().map_err(SpiError::Dc)?;
+        self.spi.write(&[PACKET_DROP]).map_err(SpiError::Spi)?;
+        self.dc.set_high().map_err(SpiError::Dc)?;
+        self.spi
+            .write(&self.buffer[..len as usize])
+            .map_err(SpiError::Spi)
+    }
+}
+
+impl<SPI, DC> Interface for Ssd2825Interface<'_, SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    type Word = u8;
+    type Error = SpiError<SPI::Error, DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        ensure!(self.buffer.len() > args.len(), SpiError::BufferTooSmall);
+
+        self.buffer[0] = command;
+        self.buffer[1..=args.len()].copy_from_slice(args);
+        self.send_packet(args.len() + 1, false)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut arrays = pixels.into_iter();
+
+        ensure!(self.buffer.len() > N, SpiError::BufferTooSmall);
+
+        let mut done = false;
+        while !done {
+            self.buffer[0] = WRITE_MEMORY_CONTINUE;
+            let mut i = 1;
+            for chunk in self.buffer[1..].chunks_exact_mut(N) {
+                if let Some(array) = arrays.next() {
+                    let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+                    *chunk = array;
+                    i += N;
+                } else {
+                    done = true;
+                    break;
+                };
+            }
+            if i > 1 {
+                self.send_packet(i, self.high_speed_pixels)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.send_pixels((0..count).map(|_| pixel))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    /// SPI device which records the written bytes.
+    struct RecordingSpi {
+        data: [u8; 64],
+        len: usize,
+    }
+
+    impl ErrorType for RecordingSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(bytes) = operation {
+                    self.data[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+                    self.len += bytes.len();
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn command_is_sent_as_packet() {
+        let spi = RecordingSpi {
+            data: [0; 64],
+            len: 0,
+        };
+        let mut buffer = [0; 8];
+        let mut di = Ssd2825Interface::new(spi, MockOutputPin, &mut buffer);
+        di.send_command(0x3A, &[0x55]).unwrap();
+
+        let (spi, _) = di.release();
+        assert_eq!(
+            &spi.data[..spi.len],
+            &[
+                CONFIGURATION,
+                0x40,
+                0x07, // DCS, LP mode
+                PACKET_SIZE_CONTROL_1,
+                2,
+                0, // packet size
+                PACKET_SIZE_CONTROL_2,
+                0,
+                0, //
+                PACKET_DROP,
+                0x3A,
+                0x55, // packet data
+            ]
+        );
+    }
+
+    #[test]
+    fn pixels_are_sent_as_write_memory_continue() {
+        let spi = RecordingSpi {
+            data: [0; 64],
+            len: 0,
+        };
+        let mut buffer = [0; 5];
+        let mut di = Ssd2825Interface::new(spi, MockOutputPin, &mut buffer);
+        di.send_repeated_pixel([0xAB, 0xCD], 3).unwrap();
+
+        let (spi, _) = di.release();
+        let data = &spi.data[..spi.len];
+        // two packets: 2 pixels, 1 pixel
+        assert_eq!(&data[9..15], &[PACKET_DROP, 0x3C, 0xAB, 0xCD, 0xAB, 0xCD]);
+        assert_eq!(&data[15 + 9..], &[PACKET_DROP, 0x3C, 0xAB, 0xCD]);
+    }
+}
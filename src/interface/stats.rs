@@ -0,0 +1,196 @@
+use core::mem::{size_of, size_of_val};
+
+use super::{Interface, InterfaceKind, ReadInterface};
+
+/// Transfer statistics collected by [`StatsInterface`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterfaceStats {
+    /// Number of commands, including wide commands and register writes.
+    pub commands: u32,
+    /// Number of pixel transfers, i.e. calls to send pixels.
+    pub pixel_transfers: u32,
+    /// Number of bytes sent, including commands, parameters and pixel data.
+    pub bytes: u64,
+    /// Number of pixel data bytes in the largest pixel transfer.
+    pub largest_transfer: u64,
+}
+
+/// Interface wrapper which collects transfer statistics.
+///
+/// The wrapper counts the commands, pixel transfers and bytes sent through
+/// the wrapped interface and records the size of the largest pixel transfer.
+/// Pixel words wider than 8 bits are counted with their full size.
+///
+/// This is intended for tuning buffer sizes and for finding out why a frame
+/// takes longer than expected, e.g. because the pixel data is split into many
+/// small transfers.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::StatsInterface, models::ILI9341Rgb565, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(ILI9341Rgb565, StatsInterface::new(di))
+///     .init(&mut delay)
+///     .unwrap();
+///
+/// // draw the frame
+///
+/// // SAFETY: the statistics don't depend on the interface state.
+/// let stats = unsafe { display.dcs() }.take_stats();
+/// # let _ = stats;
+/// ```
+pub struct StatsInterface<DI> {
+    di: DI,
+    stats: InterfaceStats,
+}
+
+impl<DI> StatsInterface<DI>
+where
+    DI: Interface,
+{
+    /// Creates a new statistics wrapper for the given interface.
+    pub fn new(di: DI) -> Self {
+        Self {
+            di,
+            stats: InterfaceStats::default(),
+        }
+    }
+
+    /// Consumes the wrapper and returns the wrapped interface.
+    pub fn release(self) -> DI {
+        self.di
+    }
+
+    /// Returns the statistics collected since the last reset.
+    pub fn stats(&self) -> InterfaceStats {
+        self.stats
+    }
+
+    /// Resets the statistics, e.g. at the start of a frame.
+    pub fn reset_stats(&mut self) {
+        self.stats = InterfaceStats::default();
+    }
+
+    /// Returns the statistics collected since the last reset and resets them.
+    pub fn take_stats(&mut self) -> InterfaceStats {
+        core::mem::take(&mut self.stats)
+    }
+
+    fn count_command(&mut self, bytes: usize) {
+        self.stats.commands = self.stats.commands.saturating_add(1);
+        self.stats.bytes = self.stats.bytes.saturating_add(bytes as u64);
+    }
+
+    fn count_pixel_transfer(&mut self, bytes: u64) {
+        self.stats.pixel_transfers = self.stats.pixel_transfers.saturating_add(1);
+        self.stats.bytes = self.stats.bytes.saturating_add(bytes);
+        self.stats.largest_transfer = self.stats.largest_transfer.max(bytes);
+    }
+}
+
+impl<DI> Interface for StatsInterface<DI>
+where
+    DI: Interface,
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.count_command(1 + args.len());
+        self.di.send_command(command, args)
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        self.count_command(2 + args.len());
+        self.di.send_wide_command(command, args)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        self.count_command(4);
+        self.di.send_register(index, value)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut count = 0u64;
+        let result = self
+            .di
+            .send_pixels(pixels.into_iter().inspect(|_| count += 1));
+        self.count_pixel_transfer(count * (N * size_of::<DI::Word>()) as u64);
+        result
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.count_pixel_transfer(u64::from(count) * (N * size_of::<DI::Word>()) as u64);
+        self.di.send_repeated_pixel(pixel, count)
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.count_pixel_transfer(size_of_val(data) as u64);
+        self.di.send_raw_pixels(data)
+    }
+}
+
+impl<DI> ReadInterface for StatsInterface<DI>
+where
+    DI: ReadInterface,
+{
+    fn read_command(&mut self, command: u8, params: &mut [u8]) -> Result<(), Self::Error> {
+        self.count_command(1);
+        self.di.read_command(command, params)
+    }
+
+    fn read_command_with_dummy_cycles(
+        &mut self,
+        command: u8,
+        dummy_cycles: u8,
+        params: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.count_command(1);
+        self.di
+            .read_command_with_dummy_cycles(command, dummy_cycles, params)
+    }
+
+    fn read_memory(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.count_command(1);
+        self.di.read_memory(command, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::_mock::MockDisplayInterface;
+
+    use super::*;
+
+    #[test]
+    fn counts_commands_and_pixels() {
+        let mut di = StatsInterface::new(MockDisplayInterface);
+        di.send_command(0x2A, &[0, 0, 0, 239]).unwrap();
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels([[0xAB, 0xCD]; 3]).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 10).unwrap();
+
+        assert_eq!(
+            di.take_stats(),
+            InterfaceStats {
+                commands: 2,
+                pixel_transfers: 2,
+                bytes: 5 + 1 + 6 + 20,
+                largest_transfer: 20,
+            }
+        );
+        assert_eq!(di.stats(), InterfaceStats::default());
+    }
+}
@@ -0,0 +1,179 @@
+use super::{Interface, InterfaceKind};
+
+/// Number of pixels which are buffered on the stack before they are forwarded.
+const CHUNK_PIXELS: usize = 32;
+
+/// Tee interface error
+#[derive(Clone, Copy, Debug)]
+pub enum TeeError<A, B> {
+    /// Error of the first interface
+    First(A),
+    /// Error of the second interface
+    Second(B),
+}
+
+/// Interface wrapper which mirrors all transfers to two interfaces.
+///
+/// Every command and every pixel transfer is forwarded to both wrapped
+/// interfaces, first to `a` and then to `b`. This can be used to show the same
+/// content on two identical displays without drawing everything twice.
+///
+/// Pixel iterators can only be consumed once, so pixels are gathered in
+/// small chunks on the stack, which are then sent to both interfaces. The
+/// [`KIND`](Interface::KIND) of the first interface is reported and both
+/// interfaces should be of the same kind.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::TeeInterface, models::ILI9341Rgb565, Builder};
+///
+/// # let left = mipidsi::_mock::MockDisplayInterface;
+/// # let right = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let di = TeeInterface::new(left, right);
+///
+/// let mut display = Builder::new(ILI9341Rgb565, di)
+///     .init(&mut delay)
+///     .unwrap();
+///
+/// // release both interfaces
+/// let (di, _, _) = display.release();
+/// let (left, right) = di.release();
+/// ```
+pub struct TeeInterface<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> TeeInterface<A, B>
+where
+    A: Interface,
+    B: Interface<Word = A::Word>,
+{
+    /// Creates a new wrapper which mirrors all transfers to `a` and `b`.
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+
+    /// Consumes the wrapper and returns the wrapped interfaces.
+    pub fn release(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B> Interface for TeeInterface<A, B>
+where
+    A: Interface,
+    B: Interface<Word = A::Word>,
+{
+    type Word = A::Word;
+    type Error = TeeError<A::Error, B::Error>;
+
+    const KIND: InterfaceKind = A::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.a
+            .send_command(command, args)
+            .map_err(TeeError::First)?;
+        self.b.send_command(command, args).map_err(TeeError::Second)
+    }
+
+    fn send_wide_command(&mut self, command: u16, args: &[u8]) -> Result<(), Self::Error> {
+        self.a
+            .send_wide_command(command, args)
+            .map_err(TeeError::First)?;
+        self.b
+            .send_wide_command(command, args)
+            .map_err(TeeError::Second)
+    }
+
+    fn send_register(&mut self, index: u16, value: u16) -> Result<(), Self::Error> {
+        self.a
+            .send_register(index, value)
+            .map_err(TeeError::First)?;
+        self.b.send_register(index, value).map_err(TeeError::Second)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut pixels = pixels.into_iter();
+
+        while let Some(first) = pixels.next() {
+            let mut chunk = [first; CHUNK_PIXELS];
+            let mut len = 1;
+            for (slot, pixel) in chunk[1..].iter_mut().zip(&mut pixels) {
+                *slot = pixel;
+                len += 1;
+            }
+
+            let chunk = &chunk[..len];
+            self.a
+                .send_pixels(chunk.iter().copied())
+                .map_err(TeeError::First)?;
+            self.b
+                .send_pixels(chunk.iter().copied())
+                .map_err(TeeError::Second)?;
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.a
+            .send_repeated_pixel(pixel, count)
+            .map_err(TeeError::First)?;
+        self.b
+            .send_repeated_pixel(pixel, count)
+            .map_err(TeeError::Second)
+    }
+
+    fn send_raw_pixels(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.a.send_raw_pixels(data).map_err(TeeError::First)?;
+        self.b.send_raw_pixels(data).map_err(TeeError::Second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::interface::{RecordingInterface, Transfer};
+
+    use super::*;
+
+    fn pixel_bytes(di: &RecordingInterface) -> Vec<u8> {
+        di.transfers()
+            .iter()
+            .filter_map(|transfer| match transfer {
+                Transfer::Pixels(data) => Some(data.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn mirrors_transfers() {
+        let mut di = TeeInterface::new(RecordingInterface::new(), RecordingInterface::new());
+
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels((0..100u8).map(|i| [i, !i])).unwrap();
+        di.send_repeated_pixel([0xAB, 0xCD], 3).unwrap();
+
+        let (a, b) = di.release();
+        assert_eq!(a.transfers(), b.transfers());
+        assert_eq!(a.instructions(), [0x2C]);
+
+        let expected: Vec<u8> = (0..100u8)
+            .flat_map(|i| [i, !i])
+            .chain([0xAB, 0xCD].repeat(3))
+            .collect();
+        assert_eq!(pixel_bytes(&a), expected);
+    }
+}
@@ -0,0 +1,133 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::{interface::Interface, interface::InterfacePixelFormat, models::Model, Display};
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    ///
+    /// Sets pixel colors in a rectangular region in interlaced order.
+    ///
+    /// The even rows of the region, relative to `sy`, are transmitted first,
+    /// followed by the odd rows. A coarse version of a large update becomes
+    /// visible after half the transfer time, which reduces the perceived
+    /// latency on slow interfaces. Each row is sent with its own address
+    /// window, which adds a small command overhead per row compared to
+    /// [`set_pixels`](Self::set_pixels).
+    ///
+    /// The `colors` slice contains the pixels of the region in row-major
+    /// order. Rows which aren't completely covered by the slice are only
+    /// partially drawn or skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `colors` - pixel data of the region in row-major order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::pixelcolor::{Rgb565, RgbColor};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let image = [Rgb565::RED; 240 * 20];
+    /// display.set_pixels_interlaced(0, 100, 239, 119, &image).unwrap();
+    /// ```
+    pub fn set_pixels_interlaced(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: &[M::ColorFormat],
+    ) -> Result<(), DI::Error> {
+        if ex < sx || ey < sy {
+            return Ok(());
+        }
+
+        let width = usize::from(ex - sx) + 1;
+        for pass in 0..2 {
+            for y in (sy + pass..=ey).step_by(2) {
+                let start = usize::from(y - sy) * width;
+                if start >= colors.len() {
+                    break;
+                }
+
+                let row = &colors[start..colors.len().min(start + width)];
+                self.set_pixels(sx, y, ex, y, row.iter().copied())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _mock::MockDelay,
+        interface::{Interface, InterfaceKind},
+        models::ILI9341Rgb565,
+        Builder,
+    };
+
+    /// Interface which records the start rows of the address windows (RASET).
+    #[derive(Default)]
+    struct RowInterface {
+        rows: [u16; 8],
+        len: usize,
+    }
+
+    impl Interface for RowInterface {
+        type Word = u8;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+            if command == 0x2B && self.len < self.rows.len() {
+                self.rows[self.len] = u16::from_be_bytes([args[0], args[1]]);
+                self.len += 1;
+            }
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            _pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn even_rows_first() {
+        let mut display = Builder::new(ILI9341Rgb565, RowInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display
+            .set_pixels_interlaced(10, 20, 13, 24, &[Rgb565::RED; 4 * 5])
+            .unwrap();
+
+        let di = display.release().0;
+        assert_eq!(&di.rows[..di.len], &[20, 22, 24, 21, 23]);
+    }
+}
@@ -10,23 +10,64 @@
 //! hardware via different transports. Builtin support for these transports is
 //! available:
 //! - SPI ([`interface::SpiInterface`])
+//! - SPI style serial with double buffered DMA transfers ([`interface::PingPongInterface`])
+//! - quad SPI ([`interface::QspiInterface`])
 //! - 8080 style parallel via GPIO ([`interface::ParallelInterface`])
+//! - 8080 style parallel with a staging buffer ([`interface::PortInterface`])
+//! - MIPI DSI via a SSD2825 bridge ([`interface::Ssd2825Interface`])
+//! - memory-mapped STM32 FSMC/FMC via the `fsmc` feature (`interface::FsmcBus`)
+//! - 8080 style parallel via the RP2040 PIO with the `rp2040` feature (`interface::PioBus`)
+//! - `display-interface` implementations via the `display-interface` feature (`interface::DisplayInterfaceAdapter`)
 //!
 //! An optional batching of draws is supported via the `batch` feature (default on)
 //!
+//! The optional `no-panic` feature converts panics caused by invalid buffer
+//! sizes or arguments in the interfaces and display methods into returned
+//! errors, for use in firmware that forbids panics. Constructors which return
+//! `Self`, like [`palette::PalettedCanvas::new`] or
+//! [`Display::with_framebuffer`], still panic on invalid arguments. Their
+//! `try_` alternatives never panic and return the moved arguments in the
+//! `Err` variant instead.
+//!
+//! The optional `test-util` feature adds the `interface::RecordingInterface`,
+//! which records all commands and pixel data for host side tests. This feature
+//! requires `alloc`.
+//!
 //! ### List of supported models
 //!
+//! Each model can be enabled by a cargo feature with the lowercase model name,
+//! e.g. `st7789`. All models are enabled by the default `all-models` feature.
+//! Disable the default features and select only the required models to
+//! reduce the code size on small flash parts.
+//!
+//! * GC9106
 //! * GC9107
+//! * GC9503V
 //! * GC9A01
+//! * HX8347D
+//! * ILI9163C
 //! * ILI9225
+//! * ILI9328
 //! * ILI9341
 //! * ILI9342C
+//! * ILI9481
 //! * ILI9486
 //! * ILI9488
+//! * ILI9806E
+//! * NT35510
+//! * NV3041A
+//! * R61529
 //! * RM67162
+//! * SH8601
+//! * SPFD5408
+//! * SSD1331
+//! * SSD1351
+//! * ST7305
+//! * ST7701S
 //! * ST7735
 //! * ST7789
 //! * ST7796
+//! * ST7796S
 //!
 //! ## Troubleshooting
 //!
@@ -106,12 +147,30 @@
 //! display.clear(Rgb666::RED).unwrap();
 //! ```
 
-use dcs::SetAddressMode;
+#[cfg(any(test, feature = "test-util"))]
+extern crate alloc;
+
+use dcs::InterfaceExt;
+
+// Checks a precondition. Returns the error if the `no-panic` feature is
+// enabled and panics otherwise.
+macro_rules! ensure {
+    ($cond:expr, $err:expr) => {
+        if !$cond {
+            #[cfg(feature = "no-panic")]
+            return Err($err);
+            #[cfg(not(feature = "no-panic"))]
+            panic!(concat!("assertion failed: ", stringify!($cond)));
+        }
+    };
+}
 
 pub mod interface;
 
+use core::cell::{Cell, RefCell};
+
 use embedded_hal::delay::DelayNs;
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{InputPin, OutputPin};
 
 pub mod options;
 use interface::InterfacePixelFormat;
@@ -120,6 +179,7 @@ use options::MemoryMapping;
 mod builder;
 pub use builder::*;
 
+#[macro_use]
 pub mod dcs;
 
 pub mod models;
@@ -127,9 +187,51 @@ use models::Model;
 
 mod graphics;
 
+mod delay;
+use delay::{DelayedInterface, DryRunInterface, InitDelay, InitPhase, NoDelay, SharedDelay};
+
+mod page;
+pub use page::Page;
+
+mod region;
+pub use region::Region;
+
+mod stream;
+pub use stream::FrameStream;
+
+#[cfg(feature = "async")]
+mod asynch;
+
+mod interlace;
+
+mod managed;
+pub use managed::{ManagedDisplay, ShutdownError};
+
 mod test_image;
 pub use test_image::TestImage;
 
+pub mod self_test;
+
+pub mod shadow;
+
+pub mod testpattern;
+
+pub mod framebuffer;
+
+pub mod canvas;
+
+pub mod gamma;
+
+pub mod glyph;
+
+pub mod palette;
+
+pub mod playback;
+
+pub mod scanline;
+
+pub mod transition;
+
 #[cfg(feature = "batch")]
 mod batch;
 
@@ -153,10 +255,57 @@ where
     rst: Option<RST>,
     // Model Options, includes current orientation
     options: options::ModelOptions,
-    // Current MADCTL value copy for runtime updates
-    madctl: SetAddressMode,
     // State monitor for sleeping TODO: refactor to a Model-connected state machine
     sleeping: bool,
+    // Last brightness set by the user
+    brightness: Option<u8>,
+    // Last frame rate set by the user
+    frame_rate: Option<u16>,
+    // Idle mode and partial area set by the user
+    idle: bool,
+    partial_area: Option<(u16, u16)>,
+    // Active AOD mode and the state which is restored when it is exited
+    aod: Option<AodState>,
+    // Last vertical scroll region and offset set by the user
+    scroll_region: Option<(u16, u16)>,
+    scroll_offset: Option<u16>,
+}
+
+/// Error returned by [`Display::read_pixels`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadPixelsError<DI> {
+    /// Interface error.
+    Interface(DI),
+    /// The dummy bytes of the model don't leave room for a pixel in the read
+    /// buffer.
+    TooManyDummyBytes,
+}
+
+// Size of the buffer used by `read_pixels`, including the dummy bytes.
+const READ_BUFFER_LEN: usize = 64;
+
+// Active AOD mode and the state before it was entered.
+#[derive(Clone, Copy)]
+struct AodState {
+    mode: options::AodMode,
+    brightness: Option<u8>,
+    frame_rate: Option<u16>,
+    idle: bool,
+    partial_area: Option<(u16, u16)>,
+}
+
+// Reset method used before the model init sequence is run.
+#[derive(Clone, Copy)]
+enum Reset {
+    // Reset pin if available, otherwise software reset
+    Auto,
+    // Reset pin only
+    Pin,
+    // Software reset only
+    Software,
+    // No reset
+    None,
 }
 
 impl<DI, M, RST> Display<DI, M, RST>
@@ -189,6 +338,38 @@ where
         self.model.update_options(&mut self.di, &self.options)
     }
 
+    ///
+    /// Sets the color inversion.
+    ///
+    /// The framebuffer content isn't changed, only the way it's shown on the
+    /// panel. This can be used to flash the display as a visual alert or to
+    /// fix the colors of panels which need a different inversion setting than
+    /// the model default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::ColorInversion;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_invert_colors(ColorInversion::Inverted).unwrap();
+    /// assert_eq!(display.invert_colors(), ColorInversion::Inverted);
+    /// ```
+    pub fn set_invert_colors(
+        &mut self,
+        invert_colors: options::ColorInversion,
+    ) -> Result<(), DI::Error> {
+        self.options.invert_colors = invert_colors;
+        self.model.set_invert_colors(&mut self.di, &self.options)
+    }
+
+    ///
+    /// Returns the current color inversion.
+    ///
+    pub fn invert_colors(&self) -> options::ColorInversion {
+        self.options.invert_colors
+    }
+
     ///
     /// Sets a pixel color at the given coords.
     ///
@@ -255,6 +436,40 @@ where
         M::ColorFormat::send_pixels(&mut self.di, colors)
     }
 
+    /// Sets pixel data which is already in the format of the display interface.
+    ///
+    /// This is a fast path for framebuffers which are stored in the format
+    /// that is sent to the display, e.g. Rgb565 pixels as big endian byte
+    /// pairs for SPI interfaces. The data is sent without converting each
+    /// pixel, which saves a lot of CPU time for full frame updates.
+    ///
+    /// The `data` must contain the pixels of the area from `sx`, `sy` to `ex`,
+    /// `ey` (inclusive) in row major order. No checks are performed on the
+    /// length or the format of the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// // 2x2 pixels of big endian Rgb565 data
+    /// let data = [0xF8, 0x00, 0x07, 0xE0, 0x00, 0x1F, 0xFF, 0xFF];
+    /// display.set_pixels_raw(0, 0, 1, 1, &data).unwrap();
+    /// ```
+    pub fn set_pixels_raw(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        data: &[DI::Word],
+    ) -> Result<(), DI::Error> {
+        self.set_address_window(sx, sy, ex, ey)?;
+
+        M::write_memory_start(&mut self.di)?;
+
+        self.di.send_raw_pixels(data)
+    }
+
     /// Sets the vertical scroll region.
     ///
     /// The `top_fixed_area` and `bottom_fixed_area` arguments can be used to
@@ -265,8 +480,9 @@ where
     /// and will always scroll vertically relative to the default display
     /// orientation.
     ///
-    /// The combined height of the fixed area must not larger than the
-    /// height of the framebuffer height in the default orientation.
+    /// The combined height of the fixed areas must not be larger than the
+    /// framebuffer height in the default orientation, otherwise the whole
+    /// framebuffer is fixed.
     ///
     /// After the scrolling region is defined the [`set_vertical_scroll_offset`](Self::set_vertical_scroll_offset) can be
     /// used to scroll the display.
@@ -275,7 +491,10 @@ where
         top_fixed_area: u16,
         bottom_fixed_area: u16,
     ) -> Result<(), DI::Error> {
-        M::set_vertical_scroll_region(&mut self.di, top_fixed_area, bottom_fixed_area)
+        self.model
+            .set_vertical_scroll_region(&mut self.di, top_fixed_area, bottom_fixed_area)?;
+        self.scroll_region = Some((top_fixed_area, bottom_fixed_area));
+        Ok(())
     }
 
     /// Sets the vertical scroll offset.
@@ -283,10 +502,35 @@ where
     /// Setting the vertical scroll offset shifts the vertical scroll region
     /// upwards by `offset` pixels.
     ///
+    /// Offsets larger than the height of the scroll region wrap around,
+    /// which makes it possible to scroll continuously by incrementing the
+    /// offset. The fixed areas are never scrolled.
+    ///
     /// Use [`set_vertical_scroll_region`](Self::set_vertical_scroll_region) to setup the scroll region, before
     /// using this method.
     pub fn set_vertical_scroll_offset(&mut self, offset: u16) -> Result<(), DI::Error> {
-        M::set_vertical_scroll_offset(&mut self.di, offset)
+        let start_row = self.scroll_start(offset);
+        self.model
+            .set_vertical_scroll_offset(&mut self.di, start_row)?;
+        self.scroll_offset = Some(offset);
+        Ok(())
+    }
+
+    // Returns the framebuffer row which is shown at the top of the scroll
+    // region for the given scroll offset.
+    fn scroll_start(&self, offset: u16) -> u16 {
+        let rows = self.model.framebuffer_size().1;
+        let (top_fixed_area, bottom_fixed_area) = self.scroll_region.unwrap_or((0, 0));
+        let Some(height) = rows
+            .checked_sub(top_fixed_area)
+            .and_then(|rows| rows.checked_sub(bottom_fixed_area))
+            .filter(|height| *height > 0)
+        else {
+            // the whole framebuffer is fixed
+            return 0;
+        };
+
+        top_fixed_area + offset % height
     }
 
     ///
@@ -299,29 +543,36 @@ where
 
     // Sets the address window for the display.
     fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), DI::Error> {
+        let offset = self.address_offset();
+        let (sx, sy, ex, ey) = (sx + offset.0, sy + offset.1, ex + offset.0, ey + offset.1);
+
+        M::update_address_window(
+            &mut self.di,
+            self.options.orientation.rotation,
+            sx,
+            sy,
+            ex,
+            ey,
+        )
+    }
+
+    // Returns the offset between display and framebuffer coordinates.
+    fn address_offset(&self) -> (u16, u16) {
         // add clipping offsets if present
         let mut offset = self.options.display_offset;
+        let framebuffer_size = self.model.framebuffer_size();
         let mapping = MemoryMapping::from(self.options.orientation);
         if mapping.reverse_columns {
-            offset.0 = M::FRAMEBUFFER_SIZE.0 - (self.options.display_size.0 + offset.0);
+            offset.0 = framebuffer_size.0 - (self.options.display_size.0 + offset.0);
         }
         if mapping.reverse_rows {
-            offset.1 = M::FRAMEBUFFER_SIZE.1 - (self.options.display_size.1 + offset.1);
+            offset.1 = framebuffer_size.1 - (self.options.display_size.1 + offset.1);
         }
         if mapping.swap_rows_and_columns {
             offset = (offset.1, offset.0);
         }
 
-        let (sx, sy, ex, ey) = (sx + offset.0, sy + offset.1, ex + offset.0, ey + offset.1);
-
-        M::update_address_window(
-            &mut self.di,
-            self.options.orientation.rotation,
-            sx,
-            sy,
-            ex,
-            ey,
-        )
+        offset
     }
 
     ///
@@ -334,6 +585,48 @@ where
         M::set_tearing_effect(&mut self.di, tearing_effect, &self.options)
     }
 
+    /// Sets the scanline at which the tearing effect output is activated.
+    ///
+    /// By default the output is activated at the start of the vertical
+    /// blanking period. Activating it at a later scanline can be used to start
+    /// updates which are slower than the refresh, while the controller is
+    /// already reading the first lines of the new frame.
+    ///
+    /// The tearing effect output must be enabled with
+    /// [`set_tearing_effect`](Self::set_tearing_effect) or the
+    /// [`Builder::tearing_effect`] option to use this setting.
+    pub fn set_tear_scanline(&mut self, scanline: u16) -> Result<(), DI::Error> {
+        M::set_tear_scanline(&mut self.di, scanline)
+    }
+
+    /// Waits for the start of the next tearing effect pulse.
+    ///
+    /// Blocks until a rising edge is detected on the `te` pin, which is
+    /// connected to the TE output of the controller. Updating the framebuffer
+    /// directly after this method returns prevents visible tearing, as long
+    /// as the update is faster than the refresh of the display.
+    ///
+    /// The tearing effect output must be enabled with
+    /// [`set_tearing_effect`](Self::set_tearing_effect) or the
+    /// [`Builder::tearing_effect`] option, otherwise this method never
+    /// returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut te = mipidsi::_mock::MockTearingEffectPin::default();
+    /// display.wait_for_tearing_effect(&mut te).unwrap();
+    /// display.clear(Rgb565::BLACK).unwrap();
+    /// ```
+    pub fn wait_for_tearing_effect<TE: InputPin>(&mut self, te: &mut TE) -> Result<(), TE::Error> {
+        while te.is_high()? {}
+        while te.is_low()? {}
+        Ok(())
+    }
+
     ///
     /// Returns `true` if display is currently set to sleep.
     ///
@@ -345,6 +638,21 @@ where
     /// Puts the display to sleep, reducing power consumption.
     /// Need to call [Self::wake] before issuing other commands
     ///
+    /// The delay required by the controller after entering the sleep mode is
+    /// handled by this method. The framebuffer content and settings, like
+    /// the orientation and pixel format, are retained during sleep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// display.sleep(&mut delay).unwrap();
+    /// assert!(display.is_sleeping());
+    ///
+    /// display.wake(&mut delay).unwrap();
+    /// assert!(!display.is_sleeping());
+    /// ```
     pub fn sleep<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
         M::sleep(&mut self.di, delay)?;
         self.sleeping = true;
@@ -354,12 +662,647 @@ where
     ///
     /// Wakes the display after it's been set to sleep via [Self::sleep]
     ///
+    /// The delay required by the controller after leaving the sleep mode is
+    /// handled by this method, the display can be used directly afterwards
+    /// without initializing it again.
+    ///
     pub fn wake<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
         M::wake(&mut self.di, delay)?;
         self.sleeping = false;
         Ok(())
     }
 
+    ///
+    /// Turns the display on.
+    ///
+    /// The display is turned on by [`Builder::init`], this method is used to
+    /// turn it on again after [`display_off`](Self::display_off).
+    ///
+    pub fn display_on(&mut self) -> Result<(), DI::Error> {
+        M::display_on(&mut self.di)
+    }
+
+    ///
+    /// Turns the display off without entering the sleep mode.
+    ///
+    /// The panel shows a blank screen, but the framebuffer content and all
+    /// settings are kept and the framebuffer can still be updated. Turning
+    /// the display off hides updates until [`display_on`](Self::display_on)
+    /// is called, but doesn't reduce the power consumption as much as
+    /// [`sleep`](Self::sleep).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.display_off().unwrap();
+    /// display.clear(Rgb565::BLUE).unwrap();
+    /// display.display_on().unwrap();
+    /// ```
+    pub fn display_off(&mut self) -> Result<(), DI::Error> {
+        M::display_off(&mut self.di)
+    }
+
+    ///
+    /// Turns the display off and puts it to sleep.
+    ///
+    /// This prevents the last image from staying visible, or slowly fading
+    /// into garbage, after the firmware stops driving the display. Use
+    /// [`into_managed`](Self::into_managed) to automatically shut down the
+    /// display when the driver is dropped.
+    ///
+    pub fn shutdown<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
+        M::shutdown(&mut self.di, delay)?;
+        self.sleeping = true;
+        Ok(())
+    }
+
+    ///
+    /// Sets the display brightness.
+    ///
+    /// Not all controllers support brightness control, some panels use
+    /// a PWM controlled backlight instead.
+    ///
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<(), DI::Error> {
+        M::set_brightness(&mut self.di, brightness)?;
+        self.brightness = Some(brightness);
+        Ok(())
+    }
+
+    ///
+    /// Selects one of the predefined gamma curves of the controller.
+    ///
+    /// Controllers without predefined gamma curves ignore this setting.
+    ///
+    pub fn set_gamma(&mut self, curve: options::GammaCurve) -> Result<(), DI::Error> {
+        M::set_gamma_curve(&mut self.di, curve)
+    }
+
+    ///
+    /// Sets the frame rate of the display in Hz.
+    ///
+    /// The controller uses the closest frame rate it supports. A lower frame
+    /// rate reduces the power consumption, a higher frame rate can reduce
+    /// flicker. Controllers without frame rate control ignore this setting.
+    ///
+    pub fn set_frame_rate(&mut self, frame_rate: u16) -> Result<(), DI::Error> {
+        self.model.set_frame_rate(&mut self.di, frame_rate)?;
+        self.frame_rate = Some(frame_rate);
+        Ok(())
+    }
+
+    ///
+    /// Sets the positive and negative gamma correction tables.
+    ///
+    /// The tables are written to the `PGAMCTRL` and `NGAMCTRL` registers
+    /// (`E0h` and `E1h`) of ILI and ST controllers, which can be used to color
+    /// match clone panels. The number and meaning of the entries depends on
+    /// the controller, see its datasheet for details.
+    ///
+    /// Returns [`GammaTablesError::Unsupported`](gamma::GammaTablesError::Unsupported)
+    /// if the controller doesn't have these registers, see
+    /// [`Model::gamma_tables`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display
+    ///     .set_gamma_tables(
+    ///         &[0x0F, 0x31, 0x2B, 0x0C, 0x0E, 0x08, 0x4E, 0xF1, 0x37, 0x07, 0x10, 0x03, 0x0E, 0x09, 0x00],
+    ///         &[0x00, 0x0E, 0x14, 0x03, 0x11, 0x07, 0x31, 0xC1, 0x48, 0x08, 0x0F, 0x0C, 0x31, 0x36, 0x0F],
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn set_gamma_tables(
+        &mut self,
+        positive: &[u8],
+        negative: &[u8],
+    ) -> Result<(), gamma::GammaTablesError<DI::Error>> {
+        let (positive_instruction, negative_instruction) = self
+            .model
+            .gamma_tables()
+            .ok_or(gamma::GammaTablesError::Unsupported)?;
+
+        self.di
+            .write_raw(positive_instruction, positive)
+            .map_err(gamma::GammaTablesError::Interface)?;
+        self.di
+            .write_raw(negative_instruction, negative)
+            .map_err(gamma::GammaTablesError::Interface)
+    }
+
+    ///
+    /// Configures the brightness control block and backlight.
+    ///
+    /// Some controllers ignore [`set_brightness`](Self::set_brightness) until
+    /// the brightness control block is enabled with this method. Controllers
+    /// without brightness control ignore this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::BacklightMode;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_backlight_control(BacklightMode::Dimmed).unwrap();
+    /// display.set_brightness(128).unwrap();
+    /// ```
+    pub fn set_backlight_control(&mut self, mode: options::BacklightMode) -> Result<(), DI::Error> {
+        M::set_backlight_control(&mut self.di, mode)
+    }
+
+    ///
+    /// Configures the content adaptive brightness control.
+    ///
+    /// Controllers without adaptive brightness control ignore this setting.
+    ///
+    pub fn set_adaptive_brightness(
+        &mut self,
+        mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error> {
+        M::set_adaptive_brightness(&mut self.di, mode)
+    }
+
+    ///
+    /// Enters the partial display mode.
+    ///
+    /// Only the rows from `start_row` to `end_row` (inclusive) are driven,
+    /// the rest of the panel shows the non-display color, which reduces the
+    /// power consumption of the panel. The rows are relative to the default
+    /// display orientation. Use [`exit_partial_mode`](Self::exit_partial_mode)
+    /// to return to the normal display mode. Controllers without a partial
+    /// display mode ignore this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// // only show a status bar
+    /// display.set_partial_area(0, 19).unwrap();
+    ///
+    /// display.exit_partial_mode().unwrap();
+    /// ```
+    pub fn set_partial_area(&mut self, start_row: u16, end_row: u16) -> Result<(), DI::Error> {
+        M::set_partial_area(&mut self.di, start_row, end_row)?;
+        self.partial_area = Some((start_row, end_row));
+        Ok(())
+    }
+
+    ///
+    /// Exits the partial display mode and returns to the normal display mode.
+    ///
+    pub fn exit_partial_mode(&mut self) -> Result<(), DI::Error> {
+        M::exit_partial_mode(&mut self.di)?;
+        self.partial_area = None;
+        Ok(())
+    }
+
+    ///
+    /// Enables or disables the idle mode.
+    ///
+    /// In idle mode the color depth is reduced to 8 colors, which reduces the
+    /// power consumption of the panel. The content of the framebuffer isn't
+    /// changed and is shown in full color again after the idle mode is
+    /// disabled. Controllers without an idle mode ignore this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_idle_mode(true).unwrap();
+    /// ```
+    pub fn set_idle_mode(&mut self, idle: bool) -> Result<(), DI::Error> {
+        M::set_idle_mode(&mut self.di, idle)?;
+        self.idle = idle;
+        Ok(())
+    }
+
+    ///
+    /// Enters the always-on-display mode.
+    ///
+    /// Configures the partial area, idle mode, brightness and frame rate
+    /// according to the given [`AodMode`](options::AodMode) setting. The
+    /// current settings are saved and restored by [`exit_aod`](Self::exit_aod).
+    ///
+    /// The brightness and frame rate can't be read back from the controller.
+    /// They are only changed if they were set before with
+    /// [`set_brightness`](Self::set_brightness) and
+    /// [`set_frame_rate`](Self::set_frame_rate), otherwise the previous value
+    /// couldn't be restored.
+    ///
+    /// Does nothing if the AOD mode is already active.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::AodMode;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// display.set_brightness(255).unwrap();
+    /// display.set_frame_rate(60).unwrap();
+    ///
+    /// let aod = AodMode::new(100, 139).idle(true).brightness(64).frame_rate(40);
+    /// display.enter_aod(aod, &mut delay).unwrap();
+    /// assert!(display.is_aod());
+    ///
+    /// display.exit_aod(&mut delay).unwrap();
+    /// ```
+    pub fn enter_aod<D: DelayNs>(
+        &mut self,
+        mode: options::AodMode,
+        delay: &mut D,
+    ) -> Result<(), DI::Error> {
+        if self.aod.is_some() {
+            return Ok(());
+        }
+
+        let state = AodState {
+            mode,
+            brightness: self.brightness,
+            frame_rate: self.frame_rate,
+            idle: self.idle,
+            partial_area: self.partial_area,
+        };
+
+        M::set_partial_area(&mut self.di, mode.rows.0, mode.rows.1)?;
+        if mode.idle && !state.idle {
+            M::set_idle_mode(&mut self.di, true)?;
+        }
+        if let (Some(brightness), Some(_)) = (mode.brightness, state.brightness) {
+            M::set_brightness(&mut self.di, brightness)?;
+        }
+        if let (Some(frame_rate), Some(_)) = (mode.frame_rate, state.frame_rate) {
+            self.model.set_frame_rate(&mut self.di, frame_rate)?;
+        }
+        self.di.write_sequence(mode.enter_sequence, delay)?;
+
+        self.aod = Some(state);
+        Ok(())
+    }
+
+    ///
+    /// Exits the always-on-display mode and restores the state from before
+    /// [`enter_aod`](Self::enter_aod) was called.
+    ///
+    /// Does nothing if the AOD mode isn't active.
+    ///
+    pub fn exit_aod<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
+        let Some(state) = self.aod else {
+            return Ok(());
+        };
+        let mode = state.mode;
+
+        self.di.write_sequence(mode.exit_sequence, delay)?;
+        if let (Some(_), Some(frame_rate)) = (mode.frame_rate, state.frame_rate) {
+            self.model.set_frame_rate(&mut self.di, frame_rate)?;
+        }
+        if let (Some(_), Some(brightness)) = (mode.brightness, state.brightness) {
+            M::set_brightness(&mut self.di, brightness)?;
+        }
+        if mode.idle && !state.idle {
+            M::set_idle_mode(&mut self.di, false)?;
+        }
+        match state.partial_area {
+            Some((start_row, end_row)) => M::set_partial_area(&mut self.di, start_row, end_row)?,
+            None => M::exit_partial_mode(&mut self.di)?,
+        }
+
+        self.aod = None;
+        Ok(())
+    }
+
+    ///
+    /// Returns `true` if the always-on-display mode is active.
+    ///
+    pub fn is_aod(&self) -> bool {
+        self.aod.is_some()
+    }
+
+    /// Resets the controller and re-runs the model init sequence.
+    ///
+    /// This re-attaches a panel that was power cycled or replaced at runtime,
+    /// e.g. a hot pluggable display module. The controller is reset by using
+    /// the reset pin, if one was provided to the builder, or by sending a
+    /// software reset command. The current options, including the orientation,
+    /// are used to initialize the display.
+    ///
+    /// The content of the framebuffer is undefined after this method returns
+    /// and needs to be redrawn.
+    pub fn reattach<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), InitError<DI::Error, RST::Error>> {
+        self.reset_and_init(delay, Reset::Auto)
+    }
+
+    /// Checks if the panel is still attached and re-attaches it if necessary.
+    ///
+    /// The `is_attached` closure is used to detect if the panel was power
+    /// cycled or replaced, e.g. by reading the display ID or status or by
+    /// checking a GPIO connected to the panel's presence detect pin. If the
+    /// closure returns `Ok(false)` or an error, the panel is re-attached by
+    /// using [`reattach`](Self::reattach).
+    ///
+    /// Returns `true` if the panel was re-attached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// # let mut module_present = || true;
+    /// let reattached = display
+    ///     .redetect(&mut delay, |_di| Ok(module_present()))
+    ///     .unwrap();
+    ///
+    /// if reattached {
+    ///     // redraw the screen content
+    /// }
+    /// ```
+    pub fn redetect<D, F>(
+        &mut self,
+        delay: &mut D,
+        is_attached: F,
+    ) -> Result<bool, InitError<DI::Error, RST::Error>>
+    where
+        D: DelayNs,
+        F: FnOnce(&mut DI) -> Result<bool, DI::Error>,
+    {
+        if let Ok(true) = is_attached(&mut self.di) {
+            return Ok(false);
+        }
+
+        self.reset_and_init(delay, Reset::Auto)?;
+        Ok(true)
+    }
+
+    /// Performs a hardware reset and re-runs the model init sequence.
+    ///
+    /// Unlike [`reattach`](Self::reattach), which falls back to a software
+    /// reset, this method always toggles the reset pin. This recovers a hung
+    /// controller which no longer accepts commands, without rebuilding the
+    /// whole driver stack. The current options, including the orientation,
+    /// are used to initialize the display.
+    ///
+    /// Returns [`ConfigurationError::MissingResetPin`] if no reset pin was
+    /// provided to the builder. The content of the framebuffer is undefined
+    /// after this method returns and needs to be redrawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{models::ST7789, Builder};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let rst = mipidsi::_mock::MockOutputPin;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ST7789, di)
+    ///     .reset_pin(rst)
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    ///
+    /// // the controller stopped responding
+    /// display.hard_reset(&mut delay).unwrap();
+    /// ```
+    pub fn hard_reset<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), InitError<DI::Error, RST::Error>> {
+        self.reset_and_init(delay, Reset::Pin)
+    }
+
+    /// Performs a software reset and re-runs the model init sequence.
+    ///
+    /// Unlike [`reattach`](Self::reattach) this method never uses the reset
+    /// pin, which can be used if the reset pin is shared with other devices.
+    /// See [`reinit`](Self::reinit) for the restored state.
+    ///
+    /// The content of the framebuffer is undefined after this method returns
+    /// and needs to be redrawn.
+    pub fn soft_reset<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), InitError<DI::Error, RST::Error>> {
+        self.reset_and_init(delay, Reset::Software)
+    }
+
+    /// Re-runs the model init sequence without resetting the controller.
+    ///
+    /// This recovers a controller which lost its configuration, e.g. after a
+    /// brown out or an ESD glitch, without rebuilding the driver. The current
+    /// options, including the orientation and color inversion, are used to
+    /// initialize the display. The last brightness and vertical scroll
+    /// settings are restored afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// display.set_vertical_scroll_region(0, 20).unwrap();
+    /// display.set_vertical_scroll_offset(40).unwrap();
+    ///
+    /// // the panel glitched, scrolling is restored by reinit
+    /// display.reinit(&mut delay).unwrap();
+    /// ```
+    pub fn reinit<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), InitError<DI::Error, RST::Error>> {
+        self.reset_and_init(delay, Reset::None)
+    }
+
+    /// Returns `true` if a reset pin was provided to the builder.
+    pub fn has_reset_pin(&self) -> bool {
+        self.rst.is_some()
+    }
+
+    /// Returns the kind of the physical connection to the display.
+    ///
+    /// This is the [`KIND`](interface::Interface::KIND) of the display
+    /// interface, which models use to reject unsupported interfaces during
+    /// init with [`ConfigurationError::UnsupportedInterface`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::interface::InterfaceKind;
+    ///
+    /// # let display = mipidsi::_mock::new_mock_display();
+    /// assert_eq!(display.interface_kind(), InterfaceKind::Serial4Line);
+    /// ```
+    pub fn interface_kind(&self) -> interface::InterfaceKind {
+        DI::KIND
+    }
+
+    // Resets the controller and runs the model init sequence.
+    //
+    // This is the only place where the controller is reset, all public reset
+    // and recovery methods only differ in the used reset method.
+    fn reset_and_init<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        reset: Reset,
+    ) -> Result<(), InitError<DI::Error, RST::Error>> {
+        match (reset, self.rst.as_mut()) {
+            (Reset::Auto | Reset::Pin, Some(rst)) => {
+                rst.set_low().map_err(InitError::ResetPin)?;
+                delay.delay_us(M::RESET_DURATION);
+                rst.set_high().map_err(InitError::ResetPin)?;
+            }
+            (Reset::Pin, None) => {
+                return Err(InitError::InvalidConfiguration(
+                    ConfigurationError::MissingResetPin,
+                ));
+            }
+            (Reset::Auto, None) | (Reset::Software, _) => {
+                M::software_reset(&mut self.di).map_err(InitError::Interface)?;
+            }
+            (Reset::None, _) => {}
+        }
+
+        self.init(delay)
+    }
+
+    // Runs the model init sequence and restores the state set by the user.
+    fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), InitError<DI::Error, RST::Error>> {
+        let options = &self.options;
+        if options.command_delays.is_empty() && options.init_timings.is_empty() {
+            self.model.init(&mut self.di, delay, options)?;
+        } else {
+            let delay = RefCell::new(&mut *delay);
+            let phase = Cell::new(InitPhase::Reset);
+            let init_delay =
+                || InitDelay::new(SharedDelay::new(&delay), &phase, options.init_timings);
+
+            let mut di = DelayedInterface::new(&mut self.di, init_delay(), options.command_delays);
+            let mut model_delay = init_delay();
+            self.model.init(&mut di, &mut model_delay, options)?;
+            model_delay.finish();
+        }
+        self.di
+            .write_sequence(options.init_sequence, delay)
+            .map_err(InitError::Interface)?;
+        if options.tearing_effect != options::TearingEffect::Off {
+            M::set_tearing_effect(&mut self.di, options.tearing_effect, options)
+                .map_err(InitError::Interface)?;
+        }
+        self.sleeping = false;
+        self.idle = false;
+        self.partial_area = None;
+        self.aod = None;
+
+        self.restore_state().map_err(InitError::Interface)
+    }
+
+    // Applies the options to a controller which was already initialized.
+    //
+    // The model init sequence is only run against a dry run interface, which
+    // checks the configuration, e.g. the interface kind, without changing
+    // the framebuffer content.
+    fn warm_start(&mut self) -> Result<(), InitError<DI::Error, RST::Error>> {
+        let options = &self.options;
+        self.model
+            .init(&mut DryRunInterface::<DI>::new(), &mut NoDelay, options)?;
+
+        self.model
+            .set_pixel_format(&mut self.di)
+            .map_err(InitError::Interface)?;
+        self.model
+            .update_options(&mut self.di, options)
+            .map_err(InitError::Interface)?;
+        self.model
+            .set_invert_colors(&mut self.di, options)
+            .map_err(InitError::Interface)?;
+        if options.tearing_effect != options::TearingEffect::Off {
+            M::set_tearing_effect(&mut self.di, options.tearing_effect, options)
+                .map_err(InitError::Interface)?;
+        }
+
+        Ok(())
+    }
+
+    // Restores the brightness, frame rate and scroll settings after the
+    // controller was initialized again.
+    fn restore_state(&mut self) -> Result<(), DI::Error> {
+        if let Some(brightness) = self.brightness {
+            M::set_brightness(&mut self.di, brightness)?;
+        }
+        if let Some(frame_rate) = self.frame_rate {
+            self.model.set_frame_rate(&mut self.di, frame_rate)?;
+        }
+        if let Some((top_fixed_area, bottom_fixed_area)) = self.scroll_region {
+            self.model.set_vertical_scroll_region(
+                &mut self.di,
+                top_fixed_area,
+                bottom_fixed_area,
+            )?;
+        }
+        if let Some(offset) = self.scroll_offset {
+            let start_row = self.scroll_start(offset);
+            self.model
+                .set_vertical_scroll_offset(&mut self.di, start_row)?;
+        }
+        Ok(())
+    }
+
+    /// Sends a DCS command to the display.
+    ///
+    /// This can be used to send commands which aren't wrapped by this crate,
+    /// e.g. vendor specific power or VCOM settings. Custom commands can be
+    /// defined by implementing [`DcsCommand`](dcs::DcsCommand).
+    ///
+    /// Commands which change state that is managed by this crate, like the
+    /// address mode (`MADCTL`), pixel format (`COLMOD`), address window
+    /// (`CASET`/`RASET`) or sleep mode, must not be sent with this method, use
+    /// the corresponding methods of [`Display`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::dcs::DcsCommand;
+    ///
+    /// /// ST7789 VCOM setting.
+    /// struct SetVcom(u8);
+    ///
+    /// impl DcsCommand for SetVcom {
+    ///     fn instruction(&self) -> u8 {
+    ///         0xBB
+    ///     }
+    ///
+    ///     fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+    ///         buffer[0] = self.0;
+    ///         1
+    ///     }
+    /// }
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.write_command(SetVcom(0x35)).unwrap();
+    /// ```
+    pub fn write_command(&mut self, command: impl dcs::DcsCommand) -> Result<(), DI::Error> {
+        self.di.write_command(command)
+    }
+
+    /// Sends a raw command with the given `instruction` and parameters to the display.
+    ///
+    /// See [`write_command`](Self::write_command) for the commands which must
+    /// not be sent with this method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// // ILI9341 power control 1
+    /// display.write_raw(0xC0, &[0x23]).unwrap();
+    /// ```
+    pub fn write_raw(&mut self, instruction: u8, params: &[u8]) -> Result<(), DI::Error> {
+        self.di.write_raw(instruction, params)
+    }
+
     /// Returns the DCS interface for sending raw commands.
     ///
     /// # Safety
@@ -373,6 +1316,155 @@ where
     }
 }
 
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: interface::ReadInterface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Returns the scanline which is currently refreshed by the display.
+    ///
+    /// Uses the `GSCAN` (get scanline) command, which allows software to time
+    /// updates into the blanking period on boards without a connected
+    /// tearing effect pin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let scanline = display.current_scanline().unwrap();
+    /// ```
+    pub fn current_scanline(&mut self) -> Result<u16, DI::Error> {
+        let mut params = [0; 2];
+        self.read_command(dcs::GET_SCANLINE, &mut params)?;
+        Ok(u16::from_be_bytes(params))
+    }
+
+    /// Returns the display identification information.
+    ///
+    /// Uses the `RDDID` (read display identification information) command,
+    /// which returns the manufacturer ID, the module/driver version ID and the
+    /// module/driver ID. The ID can be used to detect which controller is
+    /// populated on boards that are shipped with different panels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let [manufacturer, version, driver] = display.display_id().unwrap();
+    /// ```
+    pub fn display_id(&mut self) -> Result<[u8; 3], DI::Error> {
+        let mut params = [0; 3];
+        self.read_command(dcs::GET_DISPLAY_ID, &mut params)?;
+        Ok(params)
+    }
+
+    /// Returns the display status.
+    ///
+    /// Uses the `RDDST` (read display status) command. The four status bytes
+    /// contain the booster, address mode, pixel format, sleep, display on and
+    /// tearing effect state of the controller, the exact layout is described
+    /// in the datasheet of the controller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let status = display.display_status().unwrap();
+    /// ```
+    pub fn display_status(&mut self) -> Result<[u8; 4], DI::Error> {
+        let mut params = [0; 4];
+        self.read_command(dcs::GET_DISPLAY_STATUS, &mut params)?;
+        Ok(params)
+    }
+
+    // Sends a register read command with the dummy cycles of the model.
+    fn read_command(&mut self, command: u8, params: &mut [u8]) -> Result<(), DI::Error> {
+        let dummy_cycles = self.model.read_dummy_cycles(command, params.len());
+        self.di
+            .read_command_with_dummy_cycles(command, dummy_cycles, params)
+    }
+
+    /// Reads pixels back from the framebuffer of the controller.
+    ///
+    /// The pixels in `area` are read row by row into `pixels` using the
+    /// `RAMRD` (memory read) command. The area is clipped to the display and
+    /// the read stops when `pixels` is full. Returns the number of pixels
+    /// that were read.
+    ///
+    /// The controller returns the pixels in the 18 bit format, with each
+    /// channel in the upper bits of a byte, regardless of the pixel format
+    /// used for writes. The dummy bytes preceding the data are skipped, see
+    /// [`Model::memory_read_dummy_bytes`].
+    ///
+    /// Returns [`ReadPixelsError::TooManyDummyBytes`] if the model uses more
+    /// than 61 dummy bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let mut pixels = [Rgb565::WHITE; 16];
+    /// let area = Rectangle::new(Point::new(10, 10), Size::new(4, 4));
+    /// let count = display.read_pixels(&area, &mut pixels).unwrap();
+    /// assert_eq!(count, 16);
+    /// ```
+    pub fn read_pixels(
+        &mut self,
+        area: &embedded_graphics_core::primitives::Rectangle,
+        pixels: &mut [M::ColorFormat],
+    ) -> Result<usize, ReadPixelsError<DI::Error>>
+    where
+        M::ColorFormat: From<embedded_graphics_core::pixelcolor::Rgb666>,
+    {
+        use embedded_graphics_core::{geometry::Dimensions, pixelcolor::Rgb666};
+
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(0);
+        };
+
+        let count = pixels
+            .len()
+            .min((area.size.width * area.size.height) as usize);
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let dummy = self.model.memory_read_dummy_bytes();
+        let chunk_pixels = READ_BUFFER_LEN.saturating_sub(dummy) / 3;
+        ensure!(chunk_pixels > 0, ReadPixelsError::TooManyDummyBytes);
+
+        self.set_address_window(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        )
+        .map_err(ReadPixelsError::Interface)?;
+
+        let mut buffer = [0; READ_BUFFER_LEN];
+
+        let mut command = dcs::READ_MEMORY_START;
+        for chunk in pixels[..count].chunks_mut(chunk_pixels) {
+            let data = &mut buffer[..dummy + chunk.len() * 3];
+            self.di
+                .read_memory(command, data)
+                .map_err(ReadPixelsError::Interface)?;
+            command = dcs::READ_MEMORY_CONTINUE;
+
+            for (pixel, bytes) in chunk.iter_mut().zip(data[dummy..].chunks_exact(3)) {
+                *pixel = Rgb666::new(bytes[0] >> 2, bytes[1] >> 2, bytes[2] >> 2).into();
+            }
+        }
+
+        Ok(count)
+    }
+}
+
 /// Mock implementations of embedded-hal and interface traits.
 ///
 /// Do not use types in this module outside of doc tests.
@@ -382,12 +1474,11 @@ pub mod _mock {
 
     use embedded_hal::{delay::DelayNs, digital, spi};
 
-    use crate::{
-        interface::{Interface, InterfaceKind},
-        models::ILI9341Rgb565,
-        Builder, Display, NoResetPin,
-    };
+    use crate::interface::{Interface, InterfaceKind, ReadInterface};
+    #[cfg(feature = "ili9341")]
+    use crate::{models::ILI9341Rgb565, Builder, Display, NoResetPin};
 
+    #[cfg(feature = "ili9341")]
     pub fn new_mock_display() -> Display<MockDisplayInterface, ILI9341Rgb565, NoResetPin> {
         Builder::new(ILI9341Rgb565, MockDisplayInterface)
             .init(&mut MockDelay)
@@ -410,6 +1501,28 @@ pub mod _mock {
         type Error = core::convert::Infallible;
     }
 
+    /// Input pin which toggles its level on every read.
+    #[derive(Default)]
+    pub struct MockTearingEffectPin {
+        level: bool,
+    }
+
+    impl digital::InputPin for MockTearingEffectPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            self.level = !self.level;
+            Ok(self.level)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            self.level = !self.level;
+            Ok(!self.level)
+        }
+    }
+
+    impl digital::ErrorType for MockTearingEffectPin {
+        type Error = core::convert::Infallible;
+    }
+
     pub struct MockSpi;
 
     impl spi::SpiDevice for MockSpi {
@@ -425,6 +1538,16 @@ pub mod _mock {
         type Error = core::convert::Infallible;
     }
 
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::spi::SpiDevice for MockSpi {
+        async fn transaction(
+            &mut self,
+            _operations: &mut [spi::Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
     pub struct MockDelay;
 
     impl DelayNs for MockDelay {
@@ -445,8 +1568,10 @@ pub mod _mock {
 
         fn send_pixels<const N: usize>(
             &mut self,
-            _pixels: impl IntoIterator<Item = [Self::Word; N]>,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
         ) -> Result<(), Self::Error> {
+            // consume the pixels like a real interface would
+            for _ in pixels {}
             Ok(())
         }
 
@@ -458,4 +1583,37 @@ pub mod _mock {
             Ok(())
         }
     }
+
+    #[cfg(feature = "async")]
+    impl crate::interface::AsyncInterface for MockDisplayInterface {
+        async fn send_command_async(
+            &mut self,
+            command: u8,
+            args: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.send_command(command, args)
+        }
+
+        async fn send_pixels_async<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            self.send_pixels(pixels)
+        }
+
+        async fn send_repeated_pixel_async<const N: usize>(
+            &mut self,
+            pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            self.send_repeated_pixel(pixel, count)
+        }
+    }
+
+    impl ReadInterface for MockDisplayInterface {
+        fn read_command(&mut self, _command: u8, params: &mut [u8]) -> Result<(), Self::Error> {
+            params.fill(0);
+            Ok(())
+        }
+    }
 }
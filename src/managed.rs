@@ -0,0 +1,253 @@
+use core::ops::{Deref, DerefMut};
+
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Display with managed shutdown.
+///
+/// A managed display owns the backlight pin and a delay source and shuts
+/// down the display when it is dropped: the backlight is turned off and the
+/// display is turned off and put to sleep. Without this the last image stays
+/// visible, or slowly fades into garbage, after the firmware stops driving
+/// the display.
+///
+/// The backlight pin is driven high to turn the backlight on and low to turn
+/// it off.
+///
+/// A managed display is created by [`Display::into_managed`] and can be used
+/// like a normal [`Display`], because it dereferences to the wrapped display.
+pub struct ManagedDisplay<DI, M, RST, BL, D>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: OutputPin,
+    D: DelayNs,
+{
+    display: Display<DI, M, RST>,
+    backlight: BL,
+    delay: D,
+    shut_down: bool,
+}
+
+/// Error returned by [`ManagedDisplay::shutdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownError<DI, BL> {
+    /// Interface error
+    Interface(DI),
+    /// Backlight pin error
+    Backlight(BL),
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Converts the display into a display with managed shutdown.
+    ///
+    /// The backlight is turned on and will be turned off again, together
+    /// with the display, when the returned [`ManagedDisplay`] is dropped or
+    /// [`ManagedDisplay::shutdown`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    ///
+    /// # let display = mipidsi::_mock::new_mock_display();
+    /// # let backlight = mipidsi::_mock::MockOutputPin;
+    /// # let delay = mipidsi::_mock::MockDelay;
+    /// let mut display = display.into_managed(backlight, delay).unwrap();
+    /// display.clear(Rgb565::BLACK).unwrap();
+    ///
+    /// // the display and backlight are turned off here
+    /// drop(display);
+    /// ```
+    pub fn into_managed<BL, D>(
+        self,
+        mut backlight: BL,
+        delay: D,
+    ) -> Result<ManagedDisplay<DI, M, RST, BL, D>, BL::Error>
+    where
+        BL: OutputPin,
+        D: DelayNs,
+    {
+        backlight.set_high()?;
+
+        Ok(ManagedDisplay {
+            display: self,
+            backlight,
+            delay,
+            shut_down: false,
+        })
+    }
+}
+
+impl<DI, M, RST, BL, D> ManagedDisplay<DI, M, RST, BL, D>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: OutputPin,
+    D: DelayNs,
+{
+    /// Shuts down the display.
+    ///
+    /// The backlight is turned off first, to hide the display contents
+    /// immediately, and then the display is turned off and put to sleep.
+    /// The shutdown is only performed once, dropping the display after this
+    /// method was called doesn't send any additional commands.
+    pub fn shutdown(&mut self) -> Result<(), ShutdownError<DI::Error, BL::Error>> {
+        if self.shut_down {
+            return Ok(());
+        }
+        self.shut_down = true;
+
+        self.backlight.set_low().map_err(ShutdownError::Backlight)?;
+        self.display
+            .shutdown(&mut self.delay)
+            .map_err(ShutdownError::Interface)
+    }
+
+    /// Returns `true` if the display was shut down.
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down
+    }
+
+    /// Releases the display, backlight pin and delay source without
+    /// shutting down the display.
+    pub fn release(self) -> (Display<DI, M, RST>, BL, D) {
+        let this = core::mem::ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used or dropped after the fields are moved
+        // out of it.
+        unsafe {
+            (
+                core::ptr::read(&this.display),
+                core::ptr::read(&this.backlight),
+                core::ptr::read(&this.delay),
+            )
+        }
+    }
+}
+
+impl<DI, M, RST, BL, D> Deref for ManagedDisplay<DI, M, RST, BL, D>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: OutputPin,
+    D: DelayNs,
+{
+    type Target = Display<DI, M, RST>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.display
+    }
+}
+
+impl<DI, M, RST, BL, D> DerefMut for ManagedDisplay<DI, M, RST, BL, D>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: OutputPin,
+    D: DelayNs,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.display
+    }
+}
+
+impl<DI, M, RST, BL, D> Drop for ManagedDisplay<DI, M, RST, BL, D>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: OutputPin,
+    D: DelayNs,
+{
+    fn drop(&mut self) {
+        // errors can't be reported during drop
+        let _ = self.shutdown();
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use core::cell::Cell;
+
+    use embedded_hal::digital::{ErrorType, OutputPin};
+
+    use crate::_mock::{new_mock_display, MockDelay};
+
+    /// Pin which stores its level in a shared cell.
+    struct SharedPin<'a>(&'a Cell<bool>);
+
+    impl ErrorType for SharedPin<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for SharedPin<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.set(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.set(true);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn drop_shuts_down() {
+        let level = Cell::new(false);
+        let display = new_mock_display()
+            .into_managed(SharedPin(&level), MockDelay)
+            .unwrap();
+        assert!(level.get());
+        assert!(!display.is_sleeping());
+
+        drop(display);
+        assert!(!level.get());
+    }
+
+    #[test]
+    fn release_doesnt_shut_down() {
+        let level = Cell::new(false);
+        let display = new_mock_display()
+            .into_managed(SharedPin(&level), MockDelay)
+            .unwrap();
+
+        let (display, _, _) = display.release();
+        assert!(level.get());
+        assert!(!display.is_sleeping());
+    }
+
+    #[test]
+    fn shutdown() {
+        let level = Cell::new(false);
+        let mut display = new_mock_display()
+            .into_managed(SharedPin(&level), MockDelay)
+            .unwrap();
+
+        display.shutdown().unwrap();
+        assert!(!level.get());
+        assert!(display.is_sleeping());
+        assert!(display.is_shut_down());
+    }
+}
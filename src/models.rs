@@ -1,4 +1,49 @@
 //! Display models.
+//!
+//! # Implementing models
+//!
+//! External crates can add support for new controllers by implementing the
+//! [`Model`] trait. Controllers which are compatible with one of the builtin
+//! model families can reuse their init routines, like
+//! [`ili934x::init_common`] or [`ili948x::init_common`]. Model specific power
+//! and gamma settings can be passed as a [`dcs_seq`](crate::dcs_seq) table to
+//! [`ili934x::init_with_sequence`] or [`ili948x::init_with_sequence`]. The
+//! MADCTL value is computed from the [`ModelOptions`] by using
+//! [`SetAddressMode::from`](dcs::SetAddressMode) and the pixel format by
+//! using [`PixelFormat::with_all`](dcs::PixelFormat::with_all).
+//!
+//! ```
+//! use embedded_graphics_core::pixelcolor::Rgb565;
+//! use embedded_hal::delay::DelayNs;
+//! use mipidsi::{
+//!     dcs::{BitsPerPixel, PixelFormat, SetAddressMode},
+//!     interface::Interface,
+//!     models::{ili934x, Model, ModelInitError},
+//!     options::ModelOptions,
+//! };
+//!
+//! /// ILI9341 compatible controller with a 320x480 framebuffer.
+//! struct MyController;
+//!
+//! impl Model for MyController {
+//!     type ColorFormat = Rgb565;
+//!     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+//!
+//!     fn init<DELAY, DI>(
+//!         &mut self,
+//!         di: &mut DI,
+//!         delay: &mut DELAY,
+//!         options: &ModelOptions,
+//!     ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+//!     where
+//!         DELAY: DelayNs,
+//!         DI: Interface,
+//!     {
+//!         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+//!         ili934x::init_common(di, delay, options, pf).map_err(Into::into)
+//!     }
+//! }
+//! ```
 
 use crate::{
     dcs::{self, InterfaceExt, SetAddressMode},
@@ -10,30 +55,126 @@ use embedded_graphics_core::prelude::RgbColor;
 use embedded_hal::delay::DelayNs;
 
 // existing model implementations
+#[cfg(feature = "any-model")]
+mod any;
+mod configured;
+mod custom;
+#[cfg(feature = "gc9106")]
+mod gc9106;
+#[cfg(feature = "gc9107")]
 mod gc9107;
+#[cfg(feature = "gc9503v")]
+mod gc9503v;
+#[cfg(feature = "gc9a01")]
 mod gc9a01;
+#[cfg(feature = "hx8347d")]
+mod hx8347d;
+#[cfg(feature = "ili9163c")]
+mod ili9163c;
+#[cfg(feature = "ili9225")]
 mod ili9225;
+#[cfg(feature = "ili9328")]
+mod ili9328;
+#[cfg(any(feature = "ili9328", feature = "spfd5408"))]
+mod ili932x;
+#[cfg(feature = "ili9341")]
 mod ili9341;
+#[cfg(feature = "ili9342c")]
 mod ili9342c;
-mod ili934x;
+pub mod ili934x;
+#[cfg(feature = "ili9481")]
+mod ili9481;
+#[cfg(feature = "ili9486")]
 mod ili9486;
+#[cfg(feature = "ili9488")]
 mod ili9488;
-mod ili948x;
+pub mod ili948x;
+#[cfg(feature = "ili9806e")]
+mod ili9806e;
+#[cfg(feature = "nt35510")]
+mod nt35510;
+#[cfg(feature = "nv3041a")]
+mod nv3041a;
+#[cfg(feature = "r61529")]
+mod r61529;
+#[cfg(feature = "rm67162")]
 mod rm67162;
+#[cfg(feature = "sh8601")]
+mod sh8601;
+#[cfg(feature = "spfd5408")]
+mod spfd5408;
+#[cfg(feature = "ssd1331")]
+mod ssd1331;
+#[cfg(feature = "ssd1351")]
+mod ssd1351;
+#[cfg(feature = "st7305")]
+mod st7305;
+#[cfg(feature = "st7701s")]
+mod st7701s;
+#[cfg(feature = "st7735s")]
 mod st7735s;
+#[cfg(feature = "st7789")]
 mod st7789;
+#[cfg(feature = "st7796")]
 mod st7796;
 
+#[cfg(feature = "any-model")]
+pub use any::*;
+pub use configured::*;
+pub use custom::*;
+#[cfg(feature = "gc9106")]
+pub use gc9106::*;
+#[cfg(feature = "gc9107")]
 pub use gc9107::*;
+#[cfg(feature = "gc9503v")]
+pub use gc9503v::*;
+#[cfg(feature = "gc9a01")]
 pub use gc9a01::*;
+#[cfg(feature = "hx8347d")]
+pub use hx8347d::*;
+#[cfg(feature = "ili9163c")]
+pub use ili9163c::*;
+#[cfg(feature = "ili9225")]
 pub use ili9225::*;
+#[cfg(feature = "ili9328")]
+pub use ili9328::*;
+#[cfg(feature = "ili9341")]
 pub use ili9341::*;
+#[cfg(feature = "ili9342c")]
 pub use ili9342c::*;
+#[cfg(feature = "ili9481")]
+pub use ili9481::*;
+#[cfg(feature = "ili9486")]
 pub use ili9486::*;
+#[cfg(feature = "ili9488")]
 pub use ili9488::*;
+#[cfg(feature = "ili9806e")]
+pub use ili9806e::*;
+#[cfg(feature = "nt35510")]
+pub use nt35510::*;
+#[cfg(feature = "nv3041a")]
+pub use nv3041a::*;
+#[cfg(feature = "r61529")]
+pub use r61529::*;
+#[cfg(feature = "rm67162")]
 pub use rm67162::*;
+#[cfg(feature = "sh8601")]
+pub use sh8601::*;
+#[cfg(feature = "spfd5408")]
+pub use spfd5408::*;
+#[cfg(feature = "ssd1331")]
+pub use ssd1331::*;
+#[cfg(feature = "ssd1351")]
+pub use ssd1351::*;
+#[cfg(feature = "st7305")]
+pub use st7305::*;
+#[cfg(feature = "st7701s")]
+pub use st7701s::*;
+#[cfg(feature = "st7735s")]
 pub use st7735s::*;
+#[cfg(feature = "st7789")]
 pub use st7789::*;
+#[cfg(feature = "st7796")]
 pub use st7796::*;
 
 /// Display model.
@@ -47,6 +188,73 @@ pub trait Model {
     /// Duration of the active low reset pulse in µs.
     const RESET_DURATION: u32 = 10;
 
+    /// Number of dummy bytes which precede the pixel data of memory reads.
+    ///
+    /// Used by [`Display::read_pixels`](crate::Display::read_pixels).
+    const MEMORY_READ_DUMMY_BYTES: usize = 1;
+
+    /// Number of dummy clock cycles which precede the parameters of register
+    /// reads with more than one parameter byte.
+    ///
+    /// MIPI DCS serial interfaces insert a single dummy clock cycle, which
+    /// shifts the returned parameters by one bit.
+    const READ_DUMMY_CYCLES: u8 = 1;
+
+    /// Instructions of the positive and negative gamma correction tables.
+    ///
+    /// `None` if the controller doesn't support
+    /// [`Display::set_gamma_tables`](crate::Display::set_gamma_tables).
+    const GAMMA_TABLES: Option<(u8, u8)> = None;
+
+    /// Returns the framebuffer size in pixels.
+    ///
+    /// Defaults to [`FRAMEBUFFER_SIZE`](Self::FRAMEBUFFER_SIZE). Models that
+    /// select the controller at runtime, like `AnyModel`, override this
+    /// method to return the size of the selected controller.
+    fn framebuffer_size(&self) -> (u16, u16) {
+        Self::FRAMEBUFFER_SIZE
+    }
+
+    /// Returns the default options used by [`Builder::new`](crate::Builder::new).
+    ///
+    /// Defaults to the whole framebuffer with RGB color order and no color
+    /// inversion. Models whose panels are commonly wired differently override
+    /// this method, so that the builder works without additional settings.
+    fn default_options(&self) -> ModelOptions {
+        ModelOptions::with_all(self.framebuffer_size(), (0, 0))
+    }
+
+    /// Returns the instructions of the gamma correction tables.
+    ///
+    /// Defaults to [`GAMMA_TABLES`](Self::GAMMA_TABLES).
+    fn gamma_tables(&self) -> Option<(u8, u8)> {
+        Self::GAMMA_TABLES
+    }
+
+    /// Returns the number of dummy bytes which precede memory reads.
+    ///
+    /// Defaults to [`MEMORY_READ_DUMMY_BYTES`](Self::MEMORY_READ_DUMMY_BYTES).
+    fn memory_read_dummy_bytes(&self) -> usize {
+        Self::MEMORY_READ_DUMMY_BYTES
+    }
+
+    /// Returns the number of dummy clock cycles which precede the `len`
+    /// parameter bytes returned by the read `command`.
+    ///
+    /// Used by the register reads of [`Display`](crate::Display), e.g.
+    /// [`Display::display_id`](crate::Display::display_id). Defaults to
+    /// [`READ_DUMMY_CYCLES`](Self::READ_DUMMY_CYCLES) for reads of more than
+    /// one byte and no dummy cycles for single byte reads. Models whose
+    /// controllers use a different number of dummy cycles for some commands
+    /// override this method.
+    fn read_dummy_cycles(&self, _command: u8, len: usize) -> u8 {
+        if len > 1 {
+            Self::READ_DUMMY_CYCLES
+        } else {
+            0
+        }
+    }
+
     /// Initializes the display for this model with MADCTL from [crate::Display]
     /// and returns the value of MADCTL set by init
     fn init<DELAY, DI>(
@@ -102,6 +310,35 @@ pub trait Model {
         Ok(())
     }
     ///
+    /// Turns the display on
+    ///
+    fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetDisplayOn)
+    }
+    ///
+    /// Turns the display off without entering the sleep mode
+    ///
+    fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetDisplayOff)
+    }
+    ///
+    /// Turns the display off and puts it to sleep before the driver is released
+    ///
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_command(dcs::SetDisplayOff)?;
+        Self::sleep(di, delay)
+    }
+    ///
     /// We need WriteMemoryStart befor write pixel
     ///
     fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
@@ -111,6 +348,15 @@ pub trait Model {
         di.write_command(dcs::WriteMemoryStart)
     }
     ///
+    /// Continues a memory write at the position following the last pixel
+    ///
+    fn write_memory_continue<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::WriteMemoryContinue)
+    }
+    ///
     /// SoftReset
     ///
     fn software_reset<DI>(di: &mut DI) -> Result<(), DI::Error>
@@ -130,6 +376,30 @@ pub trait Model {
         di.write_command(madctl)
     }
 
+    ///
+    /// Sets the pixel format of the interface to the color format of the model.
+    ///
+    /// Used by a warm start, which doesn't run the init sequence.
+    ///
+    fn set_pixel_format<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        let pf =
+            dcs::PixelFormat::with_all(dcs::BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(dcs::SetPixelFormat::new(pf))
+    }
+
+    ///
+    /// Applies the color inversion setting in `options`.
+    ///
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetInvertMode::new(options.invert_colors))
+    }
+
     ///
     /// Configures the tearing effect output.
     ///
@@ -144,6 +414,94 @@ pub trait Model {
         di.write_command(dcs::SetTearingEffect::new(tearing_effect))
     }
 
+    /// Selects one of the predefined gamma curves.
+    fn set_gamma_curve<DI>(di: &mut DI, curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetGammaCurve::new(curve))
+    }
+
+    /// Enters the partial display mode with the rows from `start_row` to
+    /// `end_row` (inclusive) visible.
+    fn set_partial_area<DI>(di: &mut DI, start_row: u16, end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetPartialArea::new(start_row, end_row))?;
+        di.write_command(dcs::EnterPartialMode)
+    }
+
+    /// Exits the partial display mode and returns to the normal display mode.
+    fn exit_partial_mode<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::EnterNormalMode)
+    }
+
+    /// Enables or disables the idle mode with reduced color depth.
+    fn set_idle_mode<DI>(di: &mut DI, idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        if idle {
+            di.write_command(dcs::EnterIdleMode)
+        } else {
+            di.write_command(dcs::ExitIdleMode)
+        }
+    }
+
+    /// Sets the frame rate of the display in Hz.
+    ///
+    /// Frame rate control isn't part of the DCS user command set. Models with
+    /// frame rate control registers override this method and select the
+    /// closest supported frame rate, all other models ignore this setting.
+    fn set_frame_rate<DI>(&self, _di: &mut DI, _frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        Ok(())
+    }
+
+    /// Sets the display brightness.
+    fn set_brightness<DI>(di: &mut DI, brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetDisplayBrightness::new(brightness))
+    }
+
+    /// Configures the brightness control block and backlight.
+    fn set_backlight_control<DI>(di: &mut DI, mode: options::BacklightMode) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetDisplayControl::new(mode))
+    }
+
+    /// Configures the content adaptive brightness control.
+    fn set_adaptive_brightness<DI>(
+        di: &mut DI,
+        mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetAdaptiveBrightness::new(mode))
+    }
+
+    /// Sets the scanline at which the tearing effect output is activated.
+    ///
+    /// The tearing effect output must be enabled with
+    /// [`set_tearing_effect`](Self::set_tearing_effect) to use this setting.
+    fn set_tear_scanline<DI>(di: &mut DI, scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(dcs::SetTearScanline::new(scanline))
+    }
+
     /// Sets the vertical scroll region.
     ///
     /// The `top_fixed_area` and `bottom_fixed_area` arguments can be used to
@@ -160,6 +518,7 @@ pub trait Model {
     /// After the scrolling region is defined the [`set_vertical_scroll_offset`](Self::set_vertical_scroll_offset) can be
     /// used to scroll the display.
     fn set_vertical_scroll_region<DI>(
+        &self,
         di: &mut DI,
         top_fixed_area: u16,
         bottom_fixed_area: u16,
@@ -167,9 +526,9 @@ pub trait Model {
     where
         DI: Interface,
     {
-        let rows = Self::FRAMEBUFFER_SIZE.1;
+        let rows = self.framebuffer_size().1;
 
-        let vscrdef = if top_fixed_area + bottom_fixed_area > rows {
+        let vscrdef = if top_fixed_area.saturating_add(bottom_fixed_area) > rows {
             dcs::SetScrollArea::new(rows, 0, 0)
         } else {
             dcs::SetScrollArea::new(
@@ -182,26 +541,28 @@ pub trait Model {
         di.write_command(vscrdef)
     }
 
-    /// Sets the vertical scroll offset.
+    /// Sets the vertical scroll start address.
     ///
-    /// Setting the vertical scroll offset shifts the vertical scroll region
-    /// upwards by `offset` pixels.
+    /// `start_row` is the framebuffer row which is shown at the top of the
+    /// vertical scroll region. It must be inside the scroll region, which
+    /// starts at the top fixed area and ends before the bottom fixed area.
+    /// [`Display::set_vertical_scroll_offset`](crate::Display::set_vertical_scroll_offset)
+    /// maps the scroll offset into this range.
     ///
     /// Use [`set_vertical_scroll_region`](Self::set_vertical_scroll_region) to setup the scroll region, before
     /// using this method.
-    fn set_vertical_scroll_offset<DI>(di: &mut DI, offset: u16) -> Result<(), DI::Error>
+    fn set_vertical_scroll_offset<DI>(&self, di: &mut DI, start_row: u16) -> Result<(), DI::Error>
     where
         DI: Interface,
     {
-        let vscad = dcs::SetScrollStart::new(offset);
-        di.write_command(vscad)
+        di.write_command(dcs::SetScrollStart::new(start_row))
     }
 }
 
 /// Error returned by [`Model::init`].
 ///
-/// This error type is used internally by implementations of the [`Model`]
-/// trait.
+/// This error type is used by implementations of the [`Model`] trait.
+#[derive(Debug)]
 pub enum ModelInitError<DiError> {
     /// Error caused by the display interface.
     Interface(DiError),
@@ -222,14 +583,13 @@ impl<DiError> From<DiError> for ModelInitError<DiError> {
 
 #[cfg(test)]
 mod tests {
-    use embedded_graphics::pixelcolor::Rgb565;
+    use embedded_graphics::pixelcolor::{Rgb565, Rgb666};
 
     use crate::{
-        Builder,
         _mock::{MockDelay, MockDisplayInterface},
         dcs::SetAddressMode,
         interface::InterfaceKind,
-        ConfigurationError, InitError,
+        Builder, ConfigurationError, InitError,
     };
 
     use super::*;
@@ -284,4 +644,588 @@ mod tests {
             ))
         ));
     }
+
+    /// Model with a configurable number of memory read dummy bytes.
+    struct DummyBytesModel(usize);
+
+    impl Model for DummyBytesModel {
+        type ColorFormat = Rgb565;
+
+        const FRAMEBUFFER_SIZE: (u16, u16) = (16, 16);
+
+        fn memory_read_dummy_bytes(&self) -> usize {
+            self.0
+        }
+
+        fn init<DELAY, DI>(
+            &mut self,
+            _di: &mut DI,
+            _delay: &mut DELAY,
+            _options: &ModelOptions,
+        ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+        where
+            DELAY: DelayNs,
+            DI: Interface,
+        {
+            Ok(SetAddressMode::default())
+        }
+    }
+
+    fn read_pixels_with_dummy_bytes(
+        dummy: usize,
+    ) -> Result<usize, crate::ReadPixelsError<core::convert::Infallible>> {
+        use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+        let mut display = Builder::new(DummyBytesModel(dummy), MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+        let mut pixels = [Rgb565::BLACK; 4];
+        display.read_pixels(&Rectangle::new(Point::zero(), Size::new(2, 2)), &mut pixels)
+    }
+
+    #[test]
+    fn read_pixels_with_many_dummy_bytes() {
+        assert_eq!(read_pixels_with_dummy_bytes(61), Ok(4));
+    }
+
+    #[cfg(feature = "no-panic")]
+    #[test]
+    fn read_pixels_with_too_many_dummy_bytes_is_error() {
+        assert_eq!(
+            read_pixels_with_dummy_bytes(62),
+            Err(crate::ReadPixelsError::TooManyDummyBytes)
+        );
+        assert_eq!(
+            read_pixels_with_dummy_bytes(100),
+            Err(crate::ReadPixelsError::TooManyDummyBytes)
+        );
+    }
+
+    #[cfg(not(feature = "no-panic"))]
+    #[test]
+    #[should_panic]
+    fn read_pixels_with_too_many_dummy_bytes_panics() {
+        let _ = read_pixels_with_dummy_bytes(62);
+    }
+
+    /// 8 bit parallel interface which records COLMOD, RASET and the last pixel.
+    #[derive(Default)]
+    struct Parallel8BitInterface {
+        colmod: u8,
+        madctl: u8,
+        inverted: bool,
+        raset: [u8; 4],
+        registers: [u8; 8],
+        pixel: [u8; 3],
+    }
+
+    impl Interface for Parallel8BitInterface {
+        type Word = u8;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+
+        fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+            match command {
+                0x02..=0x09 => self.registers[usize::from(command - 0x02)] = args[0],
+                0x20 => self.inverted = false,
+                0x21 => self.inverted = true,
+                0x36 => self.madctl = args[0],
+                0x3A => self.colmod = args[0],
+                0x2B => self.raset.copy_from_slice(args),
+                _ => {}
+            }
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            for pixel in pixels {
+                self.pixel[..N].copy_from_slice(&pixel);
+            }
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            self.pixel[..N].copy_from_slice(&pixel);
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "ili9341")]
+    #[test]
+    fn invert_colors_at_runtime() {
+        use crate::options::ColorInversion;
+
+        let mut display = Builder::new(ILI9341Rgb565, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        assert!(!unsafe { display.dcs() }.inverted);
+
+        display.set_invert_colors(ColorInversion::Inverted).unwrap();
+        assert!(unsafe { display.dcs() }.inverted);
+
+        display.set_invert_colors(ColorInversion::Normal).unwrap();
+        assert!(!unsafe { display.dcs() }.inverted);
+    }
+
+    #[cfg(feature = "ili9341")]
+    #[test]
+    fn vertical_scroll_is_clamped() {
+        use alloc::vec;
+
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let mut di = RecordingInterface::new();
+        ILI9341Rgb565
+            .set_vertical_scroll_region(&mut di, 300, u16::MAX)
+            .unwrap();
+        ILI9341Rgb565
+            .set_vertical_scroll_offset(&mut di, 10)
+            .unwrap();
+
+        assert_eq!(
+            di.take_transfers(),
+            [
+                Transfer::Command {
+                    instruction: 0x33,
+                    params: vec![0x01, 0x40, 0, 0, 0, 0],
+                },
+                Transfer::Command {
+                    instruction: 0x37,
+                    params: vec![0, 10],
+                },
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "ili9341", feature = "st7789"))]
+    #[test]
+    fn frame_rate_control() {
+        use alloc::vec;
+
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let mut di = RecordingInterface::new();
+        ILI9341Rgb565.set_frame_rate(&mut di, 70).unwrap();
+        ILI9341Rgb565.set_frame_rate(&mut di, 0).unwrap();
+        ST7789.set_frame_rate(&mut di, 60).unwrap();
+
+        assert_eq!(
+            di.take_transfers(),
+            [
+                Transfer::Command {
+                    instruction: 0xB1,
+                    params: vec![0x00, 27],
+                },
+                Transfer::Command {
+                    instruction: 0xB1,
+                    params: vec![0x00, 31],
+                },
+                Transfer::Command {
+                    instruction: 0xB2,
+                    params: vec![0x0C, 0x0C, 0x00, 0x33, 0x33],
+                },
+                Transfer::Command {
+                    instruction: 0xC6,
+                    params: vec![0x0F],
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "st7789")]
+    #[test]
+    fn configured_model_writes_settings_after_init() {
+        use alloc::vec;
+
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let config = St7789Config {
+            vcom: Some(0x1A),
+            vrh: Some(0x12),
+            ..St7789Config::default()
+        };
+        let mut model = Configured::new(ST7789, config);
+        let mut di = RecordingInterface::new();
+        model
+            .init(&mut di, &mut MockDelay, &model.default_options())
+            .unwrap();
+
+        let transfers = di.take_transfers();
+        assert_eq!(
+            transfers[transfers.len() - 3..],
+            [
+                Transfer::Command {
+                    instruction: 0xBB,
+                    params: vec![0x1A],
+                },
+                Transfer::Command {
+                    instruction: 0xC2,
+                    params: vec![0x01],
+                },
+                Transfer::Command {
+                    instruction: 0xC3,
+                    params: vec![0x12],
+                },
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "ili9341", feature = "ssd1331"))]
+    #[test]
+    fn partial_mode_uses_model_commands() {
+        use alloc::vec;
+
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let mut di = RecordingInterface::new();
+        SSD1331::set_partial_area(&mut di, 0, 19).unwrap();
+        SSD1331::exit_partial_mode(&mut di).unwrap();
+        SSD1331::set_idle_mode(&mut di, true).unwrap();
+        assert_eq!(di.take_transfers(), []);
+
+        ILI9341Rgb565::set_partial_area(&mut di, 0, 19).unwrap();
+        ILI9341Rgb565::exit_partial_mode(&mut di).unwrap();
+        assert_eq!(
+            di.take_transfers(),
+            [
+                Transfer::Command {
+                    instruction: 0x30,
+                    params: vec![0, 0, 0, 19],
+                },
+                Transfer::Command {
+                    instruction: 0x12,
+                    params: vec![],
+                },
+                Transfer::Command {
+                    instruction: 0x13,
+                    params: vec![],
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "ili9488")]
+    #[test]
+    fn rgb888_over_8bit_parallel() {
+        use embedded_graphics::pixelcolor::Rgb888;
+
+        let mut display = Builder::new(ILI9488Rgb888, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_pixel(0, 0, Rgb888::new(1, 2, 3)).unwrap();
+
+        let di = display.release().0;
+        assert_eq!(di.colmod, 0x77);
+        assert_eq!(di.pixel, [1, 2, 3]);
+
+        assert!(matches!(
+            Builder::new(ILI9488Rgb888, MockDisplayInterface).init(&mut MockDelay),
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface
+            ))
+        ));
+    }
+
+    #[cfg(feature = "ili9163c")]
+    #[test]
+    fn ili9163c_row_offset() {
+        use embedded_graphics::pixelcolor::Rgb565;
+
+        use crate::options::{Orientation, Rotation};
+
+        let mut display = Builder::new(ILI9163C, Parallel8BitInterface::default())
+            .display_size(128, 128)
+            .display_offset(0, 32)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.set_pixel(0, 0, Rgb565::BLACK).unwrap();
+        assert_eq!(unsafe { display.dcs() }.raset, [0, 32, 0, 32]);
+
+        // the unused rows are at the start of the framebuffer after flipping
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg180))
+            .unwrap();
+        display.set_pixel(0, 0, Rgb565::BLACK).unwrap();
+        assert_eq!(unsafe { display.dcs() }.raset, [0, 0, 0, 0]);
+    }
+
+    #[cfg(feature = "gc9106")]
+    #[test]
+    fn gc9106_default_panel() {
+        use embedded_graphics::prelude::{OriginDimensions, Size};
+
+        let display = Builder::new(GC9106, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(display.size(), Size::new(80, 160));
+    }
+
+    #[cfg(feature = "hx8347d")]
+    #[test]
+    fn hx8347d_address_window_registers() {
+        let mut display = Builder::new(HX8347D, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        display
+            .set_pixels(0x12, 0x134, 0x1E, 0x13F, [Rgb565::BLACK])
+            .unwrap();
+
+        assert_eq!(
+            unsafe { display.dcs() }.registers,
+            [0x00, 0x12, 0x00, 0x1E, 0x01, 0x34, 0x01, 0x3F]
+        );
+    }
+
+    #[cfg(feature = "ili9342c")]
+    #[test]
+    fn ili9342c_defaults() {
+        let di = Builder::new(ILI9342CRgb565, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap()
+            .release()
+            .0;
+        assert_eq!(di.madctl, 0b0000_1000);
+        assert!(di.inverted);
+    }
+
+    #[cfg(feature = "st7735s")]
+    #[test]
+    fn st7735s_mini_offsets() {
+        use crate::options::{Orientation, Rotation};
+        use embedded_graphics::prelude::{OriginDimensions, Size};
+
+        let mut display = Builder::new(ST7735sMini, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(display.size(), Size::new(80, 160));
+        assert!(unsafe { display.dcs() }.inverted);
+
+        display.set_pixel(0, 0, Rgb565::BLACK).unwrap();
+        assert_eq!(unsafe { display.dcs() }.raset, [0, 1, 0, 1]);
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg90))
+            .unwrap();
+        display.set_pixel(0, 0, Rgb565::BLACK).unwrap();
+        assert_eq!(unsafe { display.dcs() }.raset, [0, 26, 0, 26]);
+    }
+
+    #[cfg(feature = "st7789")]
+    #[test]
+    fn st7789_panel_offsets() {
+        use crate::options::{Orientation, Rotation};
+
+        let mut display = Builder::new(ST7789Panel::Size135x240, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_pixel(0, 0, Rgb565::BLACK).unwrap();
+        assert_eq!(unsafe { display.dcs() }.raset, [0, 40, 0, 40]);
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg90))
+            .unwrap();
+        display.set_pixel(0, 0, Rgb565::BLACK).unwrap();
+        assert_eq!(unsafe { display.dcs() }.raset, [0, 53, 0, 53]);
+    }
+
+    #[cfg(feature = "r61529")]
+    #[test]
+    fn r61529_color_formats() {
+        let di = Builder::new(R61529Rgb888, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap()
+            .release()
+            .0;
+        assert_eq!(di.colmod, 0x77);
+
+        let di = Builder::new(R61529Rgb565, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap()
+            .release()
+            .0;
+        assert_eq!(di.colmod, 0x55);
+
+        assert!(matches!(
+            Builder::new(R61529Rgb888, MockDisplayInterface).init(&mut MockDelay),
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface
+            ))
+        ));
+    }
+
+    #[test]
+    fn custom_model_sequence() {
+        const INIT: &[dcs::SequenceEntry] = crate::dcs_seq![cmd(0x02, 0xAB), delay_ms(10)];
+
+        let di = Builder::new(
+            CustomModel::<Rgb666, 320, 480>::new(INIT),
+            Parallel8BitInterface::default(),
+        )
+        .invert_colors(options::ColorInversion::Inverted)
+        .init(&mut MockDelay)
+        .unwrap()
+        .release()
+        .0;
+
+        assert_eq!(di.registers[0], 0xAB);
+        assert_eq!(di.colmod, 0x66);
+        assert!(di.inverted);
+    }
+
+    #[cfg(feature = "ili9481")]
+    #[test]
+    fn ili9481_color_formats() {
+        assert!(matches!(
+            Builder::new(ILI9481Rgb565, MockDisplayInterface).init(&mut MockDelay),
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface
+            ))
+        ));
+
+        let di = Builder::new(ILI9481Rgb565, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap()
+            .release()
+            .0;
+        assert_eq!(di.colmod, 0x55);
+
+        Builder::new(ILI9481Rgb666, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+    }
+
+    #[cfg(feature = "ili9486")]
+    #[test]
+    fn ili9486_spi_requires_rgb666() {
+        assert!(matches!(
+            Builder::new(ILI9486Rgb565, MockDisplayInterface).init(&mut MockDelay),
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface
+            ))
+        ));
+
+        Builder::new(ILI9486Rgb666, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+    }
+
+    #[cfg(feature = "ili9488")]
+    #[test]
+    fn ili9488_spi_requires_rgb666() {
+        assert!(matches!(
+            Builder::new(ILI9488Rgb565, MockDisplayInterface).init(&mut MockDelay),
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface
+            ))
+        ));
+
+        Builder::new(ILI9488Rgb666, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+        Builder::new(ILI9488Rgb565, Parallel8BitInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+    }
+
+    /// Model which reads the display ID with 8 dummy cycles.
+    struct ReadDummyModel;
+
+    impl Model for ReadDummyModel {
+        type ColorFormat = Rgb565;
+
+        const FRAMEBUFFER_SIZE: (u16, u16) = (16, 16);
+
+        fn init<DELAY, DI>(
+            &mut self,
+            _di: &mut DI,
+            _delay: &mut DELAY,
+            _options: &ModelOptions,
+        ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+        where
+            DELAY: DelayNs,
+            DI: Interface,
+        {
+            Ok(SetAddressMode::default())
+        }
+
+        fn read_dummy_cycles(&self, command: u8, _len: usize) -> u8 {
+            if command == dcs::GET_DISPLAY_ID {
+                8
+            } else {
+                0
+            }
+        }
+    }
+
+    /// Interface which records the dummy cycles of register reads.
+    #[derive(Default)]
+    struct DummyCyclesInterface(alloc::vec::Vec<(u8, u8)>);
+
+    impl Interface for DummyCyclesInterface {
+        type Word = u8;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            _pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl crate::interface::ReadInterface for DummyCyclesInterface {
+        fn read_command(&mut self, _command: u8, _params: &mut [u8]) -> Result<(), Self::Error> {
+            unreachable!()
+        }
+
+        fn read_command_with_dummy_cycles(
+            &mut self,
+            command: u8,
+            dummy_cycles: u8,
+            _params: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.0.push((command, dummy_cycles));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn read_dummy_cycles_per_command() {
+        let mut display = Builder::new(ReadDummyModel, DummyCyclesInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.display_id().unwrap();
+        display.display_status().unwrap();
+        assert_eq!(
+            display.release().0 .0,
+            [(dcs::GET_DISPLAY_ID, 8), (dcs::GET_DISPLAY_STATUS, 0)]
+        );
+
+        let model = OnlyOneKindModel(InterfaceKind::Serial4Line);
+        assert_eq!(model.read_dummy_cycles(dcs::GET_DISPLAY_ID, 3), 1);
+        assert_eq!(model.read_dummy_cycles(dcs::GET_POWER_MODE, 1), 0);
+    }
 }
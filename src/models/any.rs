@@ -0,0 +1,332 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::SetAddressMode,
+    interface::Interface,
+    models::{
+        ILI9341Rgb565, ILI9342CRgb565, Model, ModelInitError, ST7735s, GC9107, GC9A01, RM67162,
+        ST7789, ST7796,
+    },
+    options::ModelOptions,
+};
+
+/// Runtime selected Rgb565 display model.
+///
+/// `AnyModel` makes it possible to select the display controller at runtime,
+/// e.g. based on a configuration byte stored in flash, for firmware which is
+/// used with panels from different vendors. All DCS compatible models in
+/// this crate which support the Rgb565 color format on every interface are
+/// available. The ILI9486 and ILI9488 are not, because their serial
+/// interface only supports 18 bit colors.
+///
+/// The discriminant of each variant is stable and can be used to select the
+/// model by using [`AnyModel::try_from`].
+///
+/// [`FRAMEBUFFER_SIZE`](Model::FRAMEBUFFER_SIZE) is the smallest size which
+/// contains the framebuffers of all available models; use
+/// [`framebuffer_size`](Model::framebuffer_size) for the size of the
+/// selected model. The model specific settings and all methods of [`Model`]
+/// which take `&self`, like the init sequence, scrolling and frame rate
+/// control, are dispatched to the selected model.
+///
+/// The associated functions without `self`, like [`Model::sleep`],
+/// [`Model::set_partial_area`] or [`Model::update_address_window`], can't be
+/// dispatched and always send the standard MIPI DCS commands. This is correct
+/// for all available models, which don't override these functions.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{models::AnyModel, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let config_byte = 3;
+/// let model = AnyModel::try_from(config_byte).unwrap_or(AnyModel::ST7789);
+///
+/// let mut display = Builder::new(model, di).init(&mut delay).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum AnyModel {
+    /// [`GC9107`] display.
+    GC9107 = 0,
+    /// [`GC9A01`] display.
+    GC9A01 = 1,
+    /// [`ILI9341Rgb565`] display.
+    ILI9341 = 2,
+    /// [`ILI9342CRgb565`] display.
+    ILI9342C = 3,
+    /// [`RM67162`] display.
+    RM67162 = 4,
+    /// [`ST7735s`] display.
+    ST7735s = 5,
+    /// [`ST7789`] display.
+    ST7789 = 6,
+    /// [`ST7796`] display.
+    ST7796 = 7,
+}
+
+impl AnyModel {
+    /// All available models.
+    pub const ALL: [AnyModel; 8] = [
+        Self::GC9107,
+        Self::GC9A01,
+        Self::ILI9341,
+        Self::ILI9342C,
+        Self::RM67162,
+        Self::ST7735s,
+        Self::ST7789,
+        Self::ST7796,
+    ];
+}
+
+/// Error returned by [`AnyModel::try_from`] for unknown model IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownModelError(pub u8);
+
+impl TryFrom<u8> for AnyModel {
+    type Error = UnknownModelError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|model| *model as u8 == value)
+            .ok_or(UnknownModelError(value))
+    }
+}
+
+impl From<AnyModel> for u8 {
+    fn from(model: AnyModel) -> Self {
+        model as u8
+    }
+}
+
+macro_rules! dispatch {
+    ($self:expr, $model:ident => $e:expr) => {
+        match $self {
+            AnyModel::GC9107 => {
+                let $model = GC9107;
+                $e
+            }
+            AnyModel::GC9A01 => {
+                let $model = GC9A01;
+                $e
+            }
+            AnyModel::ILI9341 => {
+                let $model = ILI9341Rgb565;
+                $e
+            }
+            AnyModel::ILI9342C => {
+                let $model = ILI9342CRgb565;
+                $e
+            }
+            AnyModel::RM67162 => {
+                let $model = RM67162;
+                $e
+            }
+            AnyModel::ST7735s => {
+                let $model = ST7735s;
+                $e
+            }
+            AnyModel::ST7789 => {
+                let $model = ST7789;
+                $e
+            }
+            AnyModel::ST7796 => {
+                let $model = ST7796;
+                $e
+            }
+        }
+    };
+}
+
+/// Returns the smallest size which contains all `sizes`.
+const fn bounding_size(sizes: &[(u16, u16)]) -> (u16, u16) {
+    let mut size = (0, 0);
+    let mut i = 0;
+    while i < sizes.len() {
+        if sizes[i].0 > size.0 {
+            size.0 = sizes[i].0;
+        }
+        if sizes[i].1 > size.1 {
+            size.1 = sizes[i].1;
+        }
+        i += 1;
+    }
+    size
+}
+
+impl Model for AnyModel {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = bounding_size(&[
+        GC9107::FRAMEBUFFER_SIZE,
+        GC9A01::FRAMEBUFFER_SIZE,
+        ILI9341Rgb565::FRAMEBUFFER_SIZE,
+        ILI9342CRgb565::FRAMEBUFFER_SIZE,
+        RM67162::FRAMEBUFFER_SIZE,
+        ST7735s::FRAMEBUFFER_SIZE,
+        ST7789::FRAMEBUFFER_SIZE,
+        ST7796::FRAMEBUFFER_SIZE,
+    ]);
+
+    fn framebuffer_size(&self) -> (u16, u16) {
+        dispatch!(self, model => model.framebuffer_size())
+    }
+
+    fn default_options(&self) -> ModelOptions {
+        dispatch!(self, model => model.default_options())
+    }
+
+    fn gamma_tables(&self) -> Option<(u8, u8)> {
+        dispatch!(self, model => model.gamma_tables())
+    }
+
+    fn memory_read_dummy_bytes(&self) -> usize {
+        dispatch!(self, model => model.memory_read_dummy_bytes())
+    }
+
+    fn read_dummy_cycles(&self, command: u8, len: usize) -> u8 {
+        dispatch!(self, model => model.read_dummy_cycles(command, len))
+    }
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        dispatch!(self, model => {
+            let mut model = model;
+            model.init(di, delay, options)
+        })
+    }
+
+    fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        dispatch!(self, model => model.update_options(di, options))
+    }
+
+    fn set_pixel_format<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        dispatch!(self, model => model.set_pixel_format(di))
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        dispatch!(self, model => model.set_invert_colors(di, options))
+    }
+
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        dispatch!(self, model => model.set_frame_rate(di, frame_rate))
+    }
+
+    fn set_vertical_scroll_region<DI>(
+        &self,
+        di: &mut DI,
+        top_fixed_area: u16,
+        bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        dispatch!(self, model => model.set_vertical_scroll_region(di, top_fixed_area, bottom_fixed_area))
+    }
+
+    fn set_vertical_scroll_offset<DI>(&self, di: &mut DI, start_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        dispatch!(self, model => model.set_vertical_scroll_offset(di, start_row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id_round_trip() {
+        for model in AnyModel::ALL {
+            assert_eq!(AnyModel::try_from(u8::from(model)), Ok(model));
+        }
+
+        assert_eq!(AnyModel::try_from(200), Err(UnknownModelError(200)));
+    }
+
+    #[test]
+    fn framebuffer_size_fits() {
+        for model in AnyModel::ALL {
+            let (width, height) = model.framebuffer_size();
+            assert!(width <= AnyModel::FRAMEBUFFER_SIZE.0);
+            assert!(height <= AnyModel::FRAMEBUFFER_SIZE.1);
+        }
+
+        assert_eq!(AnyModel::FRAMEBUFFER_SIZE, (320, 536));
+        assert_eq!(AnyModel::GC9A01.framebuffer_size(), (240, 240));
+    }
+
+    #[test]
+    fn models_support_serial_interfaces() {
+        use crate::{_mock::MockDelay, interface::RecordingInterface, options::ModelOptions};
+
+        for mut model in AnyModel::ALL {
+            let options = ModelOptions::with_all(model.framebuffer_size(), (0, 0));
+            model
+                .init(&mut RecordingInterface::new(), &mut MockDelay, &options)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn model_specific_commands_are_dispatched() {
+        use alloc::vec;
+
+        use crate::interface::{RecordingInterface, Transfer};
+
+        let mut di = RecordingInterface::new();
+        AnyModel::GC9A01.set_frame_rate(&mut di, 60).unwrap();
+        AnyModel::GC9A01
+            .set_vertical_scroll_region(&mut di, 0, 40)
+            .unwrap();
+        AnyModel::GC9A01
+            .set_vertical_scroll_offset(&mut di, 10)
+            .unwrap();
+        AnyModel::ILI9341.set_frame_rate(&mut di, 70).unwrap();
+
+        assert_eq!(
+            di.take_transfers(),
+            [
+                Transfer::Command {
+                    instruction: 0x33,
+                    params: vec![0, 0, 0, 200, 0, 40],
+                },
+                Transfer::Command {
+                    instruction: 0x37,
+                    params: vec![0, 10],
+                },
+                Transfer::Command {
+                    instruction: 0xB1,
+                    params: vec![0x00, 27],
+                },
+            ]
+        );
+        assert_eq!(AnyModel::GC9A01.gamma_tables(), None);
+        assert_eq!(AnyModel::ST7789.gamma_tables(), Some((0xE0, 0xE1)));
+    }
+}
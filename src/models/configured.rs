@@ -0,0 +1,306 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::SetAddressMode,
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::{self, ModelOptions, Rotation},
+};
+
+/// Model specific controller settings.
+///
+/// Implemented by the configuration types of models which expose power and
+/// VCOM settings, like `Ili9341Config` or `St7789Config`. The settings are
+/// applied by [`Configured`].
+pub trait ModelConfig<M: Model> {
+    /// Writes the settings to the controller.
+    ///
+    /// This method is called after [`Model::init`], every time the display
+    /// is initialized.
+    fn write<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface;
+}
+
+/// Display model with model specific controller settings.
+///
+/// Clone panels often need different VCOM or power settings than the
+/// controller defaults to avoid flicker or washed out colors. `Configured`
+/// wraps a model and writes the settings of a [`ModelConfig`] after the
+/// model was initialized. All other methods are forwarded to the wrapped
+/// model.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{
+///     models::{Configured, St7789Config, ST7789},
+///     Builder,
+/// };
+///
+/// let config = St7789Config {
+///     vcom: Some(0x1A),
+///     ..St7789Config::default()
+/// };
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let model = Configured::new(ST7789, config);
+/// let mut display = Builder::new(model, di).init(&mut delay).unwrap();
+/// ```
+pub struct Configured<M, C> {
+    model: M,
+    config: C,
+}
+
+impl<M, C> Configured<M, C> {
+    /// Creates a new configured model.
+    pub const fn new(model: M, config: C) -> Self {
+        Self { model, config }
+    }
+
+    /// Returns the controller settings.
+    pub fn config(&self) -> &C {
+        &self.config
+    }
+}
+
+impl<M, C> Model for Configured<M, C>
+where
+    M: Model,
+    C: ModelConfig<M>,
+{
+    type ColorFormat = M::ColorFormat;
+    const FRAMEBUFFER_SIZE: (u16, u16) = M::FRAMEBUFFER_SIZE;
+    const RESET_DURATION: u32 = M::RESET_DURATION;
+    const MEMORY_READ_DUMMY_BYTES: usize = M::MEMORY_READ_DUMMY_BYTES;
+    const READ_DUMMY_CYCLES: u8 = M::READ_DUMMY_CYCLES;
+    const GAMMA_TABLES: Option<(u8, u8)> = M::GAMMA_TABLES;
+
+    fn framebuffer_size(&self) -> (u16, u16) {
+        self.model.framebuffer_size()
+    }
+
+    fn default_options(&self) -> ModelOptions {
+        self.model.default_options()
+    }
+
+    fn gamma_tables(&self) -> Option<(u8, u8)> {
+        self.model.gamma_tables()
+    }
+
+    fn memory_read_dummy_bytes(&self) -> usize {
+        self.model.memory_read_dummy_bytes()
+    }
+
+    fn read_dummy_cycles(&self, command: u8, len: usize) -> u8 {
+        self.model.read_dummy_cycles(command, len)
+    }
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = self.model.init(di, delay, options)?;
+        self.config.write(di)?;
+
+        Ok(madctl)
+    }
+
+    fn update_address_window<DI>(
+        di: &mut DI,
+        rotation: Rotation,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::update_address_window(di, rotation, sx, sy, ex, ey)
+    }
+
+    fn sleep<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        M::sleep(di, delay)
+    }
+
+    fn wake<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        M::wake(di, delay)
+    }
+
+    fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::display_on(di)
+    }
+
+    fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::display_off(di)
+    }
+
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        M::shutdown(di, delay)
+    }
+
+    fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::write_memory_start(di)
+    }
+
+    fn write_memory_continue<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::write_memory_continue(di)
+    }
+
+    fn software_reset<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::software_reset(di)
+    }
+
+    fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.model.update_options(di, options)
+    }
+
+    fn set_pixel_format<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.model.set_pixel_format(di)
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.model.set_invert_colors(di, options)
+    }
+
+    fn set_tearing_effect<DI>(
+        di: &mut DI,
+        tearing_effect: options::TearingEffect,
+        options: &ModelOptions,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_tearing_effect(di, tearing_effect, options)
+    }
+
+    fn set_gamma_curve<DI>(di: &mut DI, curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_gamma_curve(di, curve)
+    }
+
+    fn set_partial_area<DI>(di: &mut DI, start_row: u16, end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_partial_area(di, start_row, end_row)
+    }
+
+    fn exit_partial_mode<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::exit_partial_mode(di)
+    }
+
+    fn set_idle_mode<DI>(di: &mut DI, idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_idle_mode(di, idle)
+    }
+
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.model.set_frame_rate(di, frame_rate)
+    }
+
+    fn set_brightness<DI>(di: &mut DI, brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_brightness(di, brightness)
+    }
+
+    fn set_backlight_control<DI>(di: &mut DI, mode: options::BacklightMode) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_backlight_control(di, mode)
+    }
+
+    fn set_adaptive_brightness<DI>(
+        di: &mut DI,
+        mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_adaptive_brightness(di, mode)
+    }
+
+    fn set_tear_scanline<DI>(di: &mut DI, scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        M::set_tear_scanline(di, scanline)
+    }
+
+    fn set_vertical_scroll_region<DI>(
+        &self,
+        di: &mut DI,
+        top_fixed_area: u16,
+        bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.model
+            .set_vertical_scroll_region(di, top_fixed_area, bottom_fixed_area)
+    }
+
+    fn set_vertical_scroll_offset<DI>(&self, di: &mut DI, start_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.model.set_vertical_scroll_offset(di, start_row)
+    }
+}
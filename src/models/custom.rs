@@ -0,0 +1,97 @@
+use core::marker::PhantomData;
+
+use embedded_graphics_core::pixelcolor::RgbColor;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SequenceEntry, SetAddressMode,
+        SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+};
+
+/// Display model built from a user supplied init sequence.
+///
+/// `CustomModel` can be used for DCS compatible controllers which aren't
+/// supported by this crate, without implementing the [`Model`] trait. The
+/// color format is selected by the `C` type parameter and the framebuffer
+/// size by the `WIDTH` and `HEIGHT` parameters.
+///
+/// During init the vendor specific `sequence` is sent first, which usually
+/// contains the power, gamma and panel settings of the controller. Afterwards
+/// the controller exits the sleep mode, the MADCTL, color inversion and pixel
+/// format are set from the [`ModelOptions`] and the display is turned on.
+/// All other commands use the MIPI DCS defaults of the [`Model`] trait.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::Rgb565;
+/// use mipidsi::{dcs::SequenceEntry, dcs_seq, models::CustomModel, Builder};
+///
+/// const INIT: &[SequenceEntry] = dcs_seq![
+///     cmd(0x01), // SWRESET
+///     delay_ms(150),
+///     cmd(0xB2, 0x0C, 0x0C, 0x00, 0x33, 0x33),
+///     cmd(0xB7, 0x35),
+/// ];
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let model = CustomModel::<Rgb565, 240, 320>::new(INIT);
+/// let mut display = Builder::new(model, di).init(&mut delay).unwrap();
+/// ```
+pub struct CustomModel<C, const WIDTH: u16, const HEIGHT: u16> {
+    sequence: &'static [SequenceEntry],
+    color_format: PhantomData<C>,
+}
+
+impl<C, const WIDTH: u16, const HEIGHT: u16> CustomModel<C, WIDTH, HEIGHT> {
+    /// Creates a new custom model with the given init sequence.
+    ///
+    /// Sequences can be created by using the [`dcs_seq`](crate::dcs_seq)
+    /// macro.
+    pub const fn new(sequence: &'static [SequenceEntry]) -> Self {
+        Self {
+            sequence,
+            color_format: PhantomData,
+        }
+    }
+}
+
+impl<C, const WIDTH: u16, const HEIGHT: u16> Model for CustomModel<C, WIDTH, HEIGHT>
+where
+    C: RgbColor,
+{
+    type ColorFormat = C;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (WIDTH, HEIGHT);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<C>());
+
+        di.write_sequence(self.sequence, delay)?;
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        di.write_command(madctl)?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+        di.write_command(SetPixelFormat::new(pf))?;
+        di.write_command(SetDisplayOn)?;
+
+        Ok(madctl)
+    }
+}
@@ -0,0 +1,96 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
+        SetInvertMode, SetPixelFormat,
+    },
+    interface::{Interface, InterfaceKind},
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+    ConfigurationError,
+};
+
+/// GC9106 display in Rgb565 color mode.
+///
+/// The GC9106 has a 128x160 framebuffer and is mostly used with 0.96" 80x160
+/// panels, which are centered horizontally in the framebuffer. The display
+/// size and offset of these panels are used by default. The offsets in the
+/// other orientations are derived automatically.
+pub struct GC9106;
+
+impl Model for GC9106 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (128, 160);
+
+    fn default_options(&self) -> ModelOptions {
+        ModelOptions::with_all((80, 160), (24, 0))
+    }
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(
+            DI::KIND,
+            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit
+        ) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        delay.delay_ms(120);
+
+        di.write_raw(0xFE, &[])?; // inter register enable 1
+        di.write_raw(0xEF, &[])?; // inter register enable 2
+
+        di.write_raw(0xB3, &[0x03])?;
+
+        let madctl = SetAddressMode::from(options);
+        di.write_command(madctl)?;
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?;
+
+        di.write_raw(0xB6, &[0x11])?; // display function control
+        di.write_raw(0xAC, &[0x0B])?;
+        di.write_raw(0xB4, &[0x21])?; // display inversion control
+        di.write_raw(0xB1, &[0xC0])?; // frame rate control
+        di.write_raw(0xE6, &[0x50, 0x43])?;
+        di.write_raw(0xE7, &[0x56, 0x43])?;
+
+        di.write_raw(
+            0xF0,
+            &[
+                0x1F, 0x41, 0x1B, 0x55, 0x36, 0x3D, 0x3E, 0x00, 0x16, 0x08, 0x09, 0x15, 0x14, 0x0F,
+            ],
+        )?;
+
+        di.write_raw(
+            0xF1,
+            &[
+                0x1F, 0x41, 0x1B, 0x55, 0x36, 0x3D, 0x3E, 0x00, 0x16, 0x08, 0x09, 0x15, 0x14, 0x0F,
+            ],
+        )?;
+
+        di.write_raw(0xFE, &[])?;
+        di.write_raw(0xFF, &[])?;
+
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+        di.write_command(ExitSleepMode)?; // turn off sleep
+        delay.delay_ms(120);
+
+        di.write_command(SetDisplayOn)?; // turn on display
+
+        Ok(madctl)
+    }
+}
@@ -13,6 +13,23 @@ use crate::{
 };
 
 /// GC9107 display in Rgb565 color mode.
+///
+/// The GC9107 has a 128x160 framebuffer. The 0.85" 128x128 panels only use
+/// the bottom 128 rows of the framebuffer and need to be configured with
+/// their display size and offset. The offsets in the other orientations are
+/// derived automatically:
+///
+/// ```
+/// use mipidsi::{models::GC9107, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(GC9107, di)
+///     .display_size(128, 128)
+///     .display_offset(0, 32)
+///     .init(&mut delay)
+///     .unwrap();
+/// ```
 pub struct GC9107;
 
 impl Model for GC9107 {
@@ -0,0 +1,130 @@
+use embedded_graphics_core::pixelcolor::Rgb666;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SequenceEntry, SetAddressMode,
+        SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
+    dcs_seq,
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+};
+
+/// GC9503V display in Rgb666 color mode.
+///
+/// The GC9503V is used in 480x480 square and bar panels. Like the
+/// [`ST7701S`](crate::models::ST7701S), the controller is configured over SPI,
+/// but the pixel data is usually sent over a separate RGB (DPI) interface,
+/// which isn't handled by this crate. In this case the display can only be
+/// used to initialize the controller and to change the orientation, sleep and
+/// tearing effect settings.
+///
+/// The color format selects the width of the RGB interface.
+pub struct GC9503V;
+
+// Command page 2: power, source and gate driver (GIP) settings.
+const PAGE2: &[SequenceEntry] = dcs_seq![
+    cmd(0xF0, 0x55, 0xAA, 0x52, 0x08, 0x00), // enable command page 2
+    cmd(0xF6, 0x5A, 0x87),
+    cmd(0xC1, 0x3F), // VGH
+    cmd(0xC2, 0x0E), // VGL
+    cmd(0xC6, 0xF8),
+    cmd(0xC9, 0x10),
+    cmd(0xCD, 0x25),
+    cmd(0xF8, 0x8A),
+    cmd(0xAC, 0x45),
+    cmd(0xA0, 0xDD),
+    cmd(0xA7, 0x47),
+    cmd(0xFA, 0x00, 0x00, 0x00, 0x04),
+    cmd(0x86, 0x99, 0xA3, 0xA3, 0x51),
+    cmd(0xA3, 0xEE),
+    cmd(0xFD, 0x3C, 0x3C, 0x00),
+    cmd(0x71, 0x48),
+    cmd(0x72, 0x48),
+    cmd(0x73, 0x00, 0x44),
+    cmd(0x97, 0xEE),
+    cmd(0x83, 0x93),
+    cmd(0x9A, 0x72),       // GVDDP
+    cmd(0x9B, 0x5A),       // GVDDN
+    cmd(0x82, 0x2C, 0x2C), // VCOM
+    cmd(0xB1, 0x10),       // source scan direction
+    // GIP mapping
+    cmd(
+        0x6D, 0x00, 0x1F, 0x19, 0x1A, 0x10, 0x0E, 0x0C, 0x0A, 0x02, 0x07, 0x1E, 0x1E, 0x1E, 0x1E,
+        0x1E, 0x1E, 0x1E, 0x1E, 0x1E, 0x1E, 0x1E, 0x1E, 0x08, 0x01, 0x09, 0x0B, 0x0D, 0x0F, 0x1A,
+        0x19, 0x1F, 0x00
+    ),
+    // GIP timing
+    cmd(
+        0x64, 0x38, 0x05, 0x01, 0xDB, 0x03, 0x03, 0x38, 0x04, 0x01, 0xDC, 0x03, 0x03, 0x7A, 0x7A,
+        0x7A, 0x7A
+    ),
+    cmd(
+        0x65, 0x38, 0x03, 0x01, 0xDD, 0x03, 0x03, 0x38, 0x02, 0x01, 0xDE, 0x03, 0x03, 0x7A, 0x7A,
+        0x7A, 0x7A
+    ),
+    cmd(
+        0x66, 0x38, 0x01, 0x01, 0xDF, 0x03, 0x03, 0x38, 0x00, 0x01, 0xE0, 0x03, 0x03, 0x7A, 0x7A,
+        0x7A, 0x7A
+    ),
+    cmd(
+        0x67, 0x30, 0x01, 0x01, 0xE1, 0x03, 0x03, 0x30, 0x02, 0x01, 0xE2, 0x03, 0x03, 0x7A, 0x7A,
+        0x7A, 0x7A
+    ),
+    cmd(0x68, 0x00, 0x08, 0x15, 0x08, 0x15, 0x7A, 0x7A, 0x08, 0x15, 0x08, 0x15, 0x7A, 0x7A),
+    cmd(0x60, 0x38, 0x08, 0x7A, 0x7A, 0x38, 0x09, 0x7A, 0x7A),
+    cmd(0x63, 0x31, 0xE4, 0x7A, 0x7A, 0x31, 0xE5, 0x7A, 0x7A),
+    cmd(0x69, 0x04, 0x22, 0x14, 0x22, 0x14, 0x22, 0x08),
+    cmd(0x6B, 0x07),
+    cmd(0x7A, 0x08, 0x13),
+    cmd(0x7B, 0x08, 0x13),
+];
+
+// Gamma curve, which is written to the positive and negative gamma
+// registers of all three colors (0xD1 to 0xD6).
+const GAMMA: &[u8] = &[
+    0x00, 0x00, 0x00, 0x04, 0x00, 0x12, 0x00, 0x18, 0x00, 0x21, 0x00, 0x2A, 0x00, 0x35, 0x00, 0x47,
+    0x00, 0x56, 0x00, 0x90, 0x00, 0xE5, 0x01, 0x68, 0x01, 0xD5, 0x01, 0xD7, 0x02, 0x36, 0x02, 0xA6,
+    0x02, 0xEE, 0x03, 0x48, 0x03, 0xA0, 0x03, 0xBA, 0x03, 0xC5, 0x03, 0xD0, 0x03, 0xE0, 0x03, 0xEA,
+    0x03, 0xFA, 0x03, 0xFF,
+];
+
+impl Model for GC9503V {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (480, 480);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        di.write_sequence(PAGE2, delay)?;
+        for register in 0xD1..=0xD6 {
+            di.write_raw(register, GAMMA)?;
+        }
+
+        di.write_command(madctl)?;
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(200_000);
+
+        di.write_command(SetDisplayOn)?;
+        delay.delay_us(100_000);
+
+        Ok(madctl)
+    }
+}
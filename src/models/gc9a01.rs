@@ -8,17 +8,29 @@ use crate::{
     },
     interface::{Interface, InterfaceKind},
     models::{Model, ModelInitError},
-    options::ModelOptions,
+    options::{ColorInversion, ColorOrder, ModelOptions},
     ConfigurationError,
 };
 
 /// GC9A01 display in Rgb565 color mode.
+///
+/// The round 240x240 GC9A01 panels use BGR subpixels and need color
+/// inversion. Both settings are enabled by default and can be changed with
+/// [`Builder::color_order`](crate::Builder::color_order) and
+/// [`Builder::invert_colors`](crate::Builder::invert_colors).
 pub struct GC9A01;
 
 impl Model for GC9A01 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 240);
 
+    fn default_options(&self) -> ModelOptions {
+        let mut options = ModelOptions::full_size::<Self>();
+        options.color_order = ColorOrder::Bgr;
+        options.invert_colors = ColorInversion::Inverted;
+        options
+    }
+
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
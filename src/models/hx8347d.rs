@@ -0,0 +1,352 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{InterfaceExt, SequenceEntry, SetAddressMode},
+    dcs_seq,
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::{self, ColorInversion, ModelOptions, Rotation},
+};
+
+/// HX8347D display in Rgb565 color mode.
+///
+/// The HX8347D uses register index addressing instead of DCS commands. Each
+/// register holds a single byte, 16 bit values like the address window are
+/// split into a high and a low byte register.
+pub struct HX8347D;
+
+const HX8347D_DISPLAY_MODE: u8 = 0x01; // Display Mode Control
+const HX8347D_COLUMN_START: u8 = 0x02; // Column Address Start, high and low byte
+const HX8347D_ROW_START: u8 = 0x06; // Row Address Start, high and low byte
+const HX8347D_MEMORY_ACCESS: u8 = 0x16; // Memory Access Control
+const HX8347D_COLMOD: u8 = 0x17; // COLMOD Control
+const HX8347D_POWER_CTRL6: u8 = 0x1F; // Power Control 6
+const HX8347D_SRAM_WRITE: u8 = 0x22; // SRAM Write Control
+const HX8347D_DISPLAY_CTRL3: u8 = 0x28; // Display Control 3
+
+// Oscillator, source timing, gamma and power voltage settings.
+const INIT: &[SequenceEntry] = dcs_seq![
+    cmd(0xEA, 0x00), // PTBA
+    cmd(0xEB, 0x20),
+    cmd(0xEC, 0x0C), // STBA
+    cmd(0xED, 0xC4),
+    cmd(0xE8, 0x40), // OPON
+    cmd(0xE9, 0x38),
+    cmd(0xF1, 0x01),
+    cmd(0xF2, 0x10),
+    cmd(0x27, 0xA3), // Display Control 2
+    // gamma
+    cmd(0x40, 0x01),
+    cmd(0x41, 0x00),
+    cmd(0x42, 0x00),
+    cmd(0x43, 0x10),
+    cmd(0x44, 0x0E),
+    cmd(0x45, 0x24),
+    cmd(0x46, 0x04),
+    cmd(0x47, 0x50),
+    cmd(0x48, 0x02),
+    cmd(0x49, 0x13),
+    cmd(0x4A, 0x19),
+    cmd(0x4B, 0x19),
+    cmd(0x4C, 0x16),
+    cmd(0x50, 0x1B),
+    cmd(0x51, 0x31),
+    cmd(0x52, 0x2F),
+    cmd(0x53, 0x3F),
+    cmd(0x54, 0x3F),
+    cmd(0x55, 0x3E),
+    cmd(0x56, 0x2F),
+    cmd(0x57, 0x7B),
+    cmd(0x58, 0x09),
+    cmd(0x59, 0x06),
+    cmd(0x5A, 0x06),
+    cmd(0x5B, 0x0C),
+    cmd(0x5C, 0x1D),
+    cmd(0x5D, 0xCC),
+    // power voltage
+    cmd(0x1B, 0x1B), // VRH
+    cmd(0x1A, 0x01), // BT
+    cmd(0x24, 0x2F), // VMH
+    cmd(0x25, 0x57), // VML
+    cmd(0x23, 0x88), // VMF
+    cmd(0x18, 0x36), // OSC_EN
+    cmd(0x19, 0x01),
+];
+
+// Power on sequence, also used to wake the display from standby.
+const POWER_ON: &[SequenceEntry] = dcs_seq![
+    cmd(HX8347D_POWER_CTRL6, 0x88), // GAS = 1, DK = 1, STB = 0
+    delay_ms(5),
+    cmd(HX8347D_POWER_CTRL6, 0x80), // DK = 0
+    delay_ms(5),
+    cmd(HX8347D_POWER_CTRL6, 0x90), // PON = 1
+    delay_ms(5),
+    cmd(HX8347D_POWER_CTRL6, 0xD0), // VCOMG = 1
+    delay_ms(5),
+];
+
+fn display_mode(options: &ModelOptions) -> u8 {
+    // NORON, INVON
+    match options.invert_colors {
+        ColorInversion::Normal => 0b0000_0010,
+        ColorInversion::Inverted => 0b0000_0110,
+    }
+}
+
+fn display_on<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+where
+    DI: Interface,
+    DELAY: DelayNs,
+{
+    di.write_raw(HX8347D_DISPLAY_CTRL3, &[0x38])?; // GON = 1, DTE = 1, D = 10
+    delay.delay_us(40_000);
+    di.write_raw(HX8347D_DISPLAY_CTRL3, &[0x3C]) // D = 11
+}
+
+impl Model for HX8347D {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        di.write_sequence(INIT, delay)?;
+        di.write_raw(HX8347D_DISPLAY_MODE, &[display_mode(options)])?;
+        di.write_sequence(POWER_ON, delay)?;
+
+        di.write_raw(HX8347D_COLMOD, &[0x05])?; // 16 bit/pixel
+        di.write_raw(HX8347D_MEMORY_ACCESS, &[madctl.bits()])?;
+
+        display_on(di, delay)?;
+
+        Ok(madctl)
+    }
+
+    fn update_address_window<DI>(
+        di: &mut DI,
+        _rotation: Rotation,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        let [sx_h, sx_l] = sx.to_be_bytes();
+        let [ex_h, ex_l] = ex.to_be_bytes();
+        di.write_registers(HX8347D_COLUMN_START, &[sx_h, sx_l, ex_h, ex_l])?;
+
+        let [sy_h, sy_l] = sy.to_be_bytes();
+        let [ey_h, ey_l] = ey.to_be_bytes();
+        di.write_registers(HX8347D_ROW_START, &[sy_h, sy_l, ey_h, ey_l])
+    }
+
+    fn sleep<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_raw(HX8347D_DISPLAY_CTRL3, &[0x38])?; // D = 10
+        delay.delay_us(40_000);
+        di.write_raw(HX8347D_DISPLAY_CTRL3, &[0x04])?; // GON = 0, DTE = 0, D = 01
+        di.write_raw(HX8347D_POWER_CTRL6, &[0x90])?; // VCOMG = 0
+        delay.delay_us(5_000);
+        di.write_raw(HX8347D_POWER_CTRL6, &[0x88])?; // PON = 0, DK = 1
+        di.write_raw(HX8347D_POWER_CTRL6, &[0x89]) // STB = 1
+    }
+
+    fn wake<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_sequence(POWER_ON, delay)?;
+        display_on(di, delay)
+    }
+
+    fn display_on<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the display on sequence requires a delay, use wake instead
+        Ok(())
+    }
+
+    fn display_off<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the display off sequence requires a delay, use sleep instead
+        Ok(())
+    }
+
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        // sleep already turns the display off
+        Self::sleep(di, delay)
+    }
+
+    fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(HX8347D_SRAM_WRITE, &[])
+    }
+
+    fn write_memory_continue<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The address counter isn't reset by the memory write command
+        Self::write_memory_start(di)
+    }
+
+    fn software_reset<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The HX8347D doesn't have a software reset command
+        Ok(())
+    }
+
+    fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+        di.write_raw(HX8347D_MEMORY_ACCESS, &[madctl.bits()])?;
+        di.write_raw(HX8347D_DISPLAY_MODE, &[display_mode(options)])
+    }
+
+    fn set_pixel_format<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(HX8347D_COLMOD, &[0x05]) // 16 bit/pixel
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the inversion is part of the options registers
+        self.update_options(di, options)
+    }
+
+    fn set_tearing_effect<DI>(
+        _di: &mut DI,
+        _tearing_effect: options::TearingEffect,
+        _options: &ModelOptions,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_gamma_curve<DI>(_di: &mut DI, _curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_partial_area<DI>(_di: &mut DI, _start_row: u16, _end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn exit_partial_mode<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_idle_mode<DI>(_di: &mut DI, _idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_brightness<DI>(_di: &mut DI, _brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_backlight_control<DI>(
+        _di: &mut DI,
+        _mode: options::BacklightMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_adaptive_brightness<DI>(
+        _di: &mut DI,
+        _mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_tear_scanline<DI>(_di: &mut DI, _scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_vertical_scroll_region<DI>(
+        &self,
+        _di: &mut DI,
+        _top_fixed_area: u16,
+        _bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_vertical_scroll_offset<DI>(&self, _di: &mut DI, _start_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+}
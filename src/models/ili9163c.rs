@@ -0,0 +1,93 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+};
+
+/// ILI9163C display in Rgb565 color mode.
+///
+/// The ILI9163C has a 128x160 framebuffer, which is fully used by the
+/// 128x160 modules. Most 128x128 "red board" modules only use the bottom
+/// 128 rows of the framebuffer and need to be configured with a row offset
+/// of 32. The offsets in the other orientations are derived automatically:
+///
+/// ```
+/// use mipidsi::{models::ILI9163C, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(ILI9163C, di)
+///     .display_size(128, 128)
+///     .display_offset(0, 32)
+///     .init(&mut delay)
+///     .unwrap();
+/// ```
+pub struct ILI9163C;
+
+impl Model for ILI9163C {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (128, 160);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?;
+
+        di.write_raw(0x26, &[0x04])?; // GAMSET: gamma curve 3
+        di.write_raw(0xF2, &[0x01])?; // enable gamma adjustment
+        di.write_raw(
+            0xE0,
+            &[
+                0x3F, 0x25, 0x1C, 0x1E, 0x20, 0x12, 0x2A, 0x90, 0x24, 0x11, 0x00, 0x00, 0x00, 0x00,
+                0x00,
+            ],
+        )?; // PGAMCTRL: positive gamma
+        di.write_raw(
+            0xE1,
+            &[
+                0x20, 0x20, 0x20, 0x20, 0x05, 0x00, 0x15, 0xA7, 0x3D, 0x18, 0x25, 0x2A, 0x2B, 0x2B,
+                0x3A,
+            ],
+        )?; // NGAMCTRL: negative gamma
+
+        di.write_raw(0xB1, &[0x08, 0x08])?; // FRMCTR1: frame rate
+        di.write_raw(0xB4, &[0x07])?; // INVCTR: display inversion
+        di.write_raw(0xC0, &[0x0A, 0x02])?; // PWCTR1
+        di.write_raw(0xC1, &[0x02])?; // PWCTR2
+        di.write_raw(0xC5, &[0x50, 0x5B])?; // VMCTR1
+        di.write_raw(0xC7, &[0x40])?; // VMOFCTR
+
+        di.write_command(madctl)?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+        di.write_command(EnterNormalMode)?;
+        di.write_command(SetDisplayOn)?;
+
+        // DISPON requires some time otherwise we risk SPI data issues
+        delay.delay_us(120_000);
+
+        Ok(madctl)
+    }
+}
@@ -1,4 +1,3 @@
-use crate::dcs::DcsCommand;
 use crate::dcs::InterfaceExt;
 use crate::dcs::SetAddressMode;
 use crate::options;
@@ -230,6 +229,29 @@ impl Model for ILI9225Rgb565 {
         di.write_raw(ILI9225_DISP_CTRL1, &[0x10, 0x17])
     }
 
+    fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(ILI9225_DISP_CTRL1, &[0x10, 0x17])
+    }
+
+    fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(ILI9225_DISP_CTRL1, &[0x00, 0x00])
+    }
+
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        // sleep already turns the display off
+        Self::sleep(di, delay)
+    }
+
     fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
     where
         DI: Interface,
@@ -237,12 +259,36 @@ impl Model for ILI9225Rgb565 {
         di.write_command(WriteMemoryStartILI9225)
     }
 
+    fn write_memory_continue<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The address counter isn't reset by the memory write command
+        Self::write_memory_start(di)
+    }
+
     fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
     where
         DI: Interface,
     {
         options_write_cmd(di, options)
     }
+
+    fn set_pixel_format<DI>(&self, _di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the controller only supports 16 bit pixels
+        Ok(())
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the inversion is part of the options registers
+        self.update_options(di, options)
+    }
     fn set_tearing_effect<DI>(
         di: &mut DI,
         tearing_effect: options::TearingEffect,
@@ -261,7 +307,78 @@ impl Model for ILI9225Rgb565 {
 
         di.write_raw(ILI9225_DISP_CTRL1, &[high, low])
     }
+    fn set_gamma_curve<DI>(_di: &mut DI, _curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_partial_area<DI>(_di: &mut DI, _start_row: u16, _end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn exit_partial_mode<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_idle_mode<DI>(_di: &mut DI, _idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_brightness<DI>(_di: &mut DI, _brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_backlight_control<DI>(
+        _di: &mut DI,
+        _mode: options::BacklightMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_adaptive_brightness<DI>(
+        _di: &mut DI,
+        _mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_tear_scanline<DI>(_di: &mut DI, _scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
     fn set_vertical_scroll_region<DI>(
+        &self,
         _di: &mut DI,
         _top_fixed_area: u16,
         _bottom_fixed_area: u16,
@@ -272,7 +389,7 @@ impl Model for ILI9225Rgb565 {
         // Not support, ignore it
         Ok(())
     }
-    fn set_vertical_scroll_offset<DI>(_di: &mut DI, _offset: u16) -> Result<(), DI::Error>
+    fn set_vertical_scroll_offset<DI>(&self, _di: &mut DI, _start_row: u16) -> Result<(), DI::Error>
     where
         DI: Interface,
     {
@@ -281,10 +398,6 @@ impl Model for ILI9225Rgb565 {
     }
 }
 
-#[path = "../dcs/macros.rs"]
-#[macro_use]
-mod dcs_macros;
-
 dcs_basic_command!(
     /// Initiate Framebuffer Memory Write
     WriteMemoryStartILI9225,
@@ -0,0 +1,239 @@
+//! Shared code for ILI932x compatible controllers with 16 bit registers.
+
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::InterfaceExt,
+    interface::{Interface, InterfaceKind},
+    models::ModelInitError,
+    options::{ColorInversion, ColorOrder, MemoryMapping, ModelOptions, Rotation},
+    ConfigurationError,
+};
+
+const DRIVER_OUTPUT_CTRL: u16 = 0x01; // Driver Output Control 1
+const ENTRY_MODE: u16 = 0x03; // Entry Mode
+const DISPLAY_CTRL1: u16 = 0x07; // Display Control 1
+const POWER_CTRL1: u16 = 0x10; // Power Control 1
+const GRAM_ADDR_HORIZONTAL: u16 = 0x20; // Horizontal GRAM Address Set
+const GRAM_ADDR_VERTICAL: u16 = 0x21; // Vertical GRAM Address Set
+const GRAM_WRITE: u16 = 0x22; // Write Data to GRAM
+const HORIZONTAL_START: u16 = 0x50; // Horizontal Address Start Position
+const HORIZONTAL_END: u16 = 0x51; // Horizontal Address End Position
+const VERTICAL_START: u16 = 0x52; // Vertical Address Start Position
+const VERTICAL_END: u16 = 0x53; // Vertical Address End Position
+const GATE_SCAN_CTRL: u16 = 0x60; // Driver Output Control 2
+const BASE_IMAGE_CTRL: u16 = 0x61; // Base Image Display Control
+
+/// Register entry in an init table.
+pub(crate) enum Entry {
+    /// Writes a value to a register.
+    Write(u16, u16),
+    /// Delay in ms.
+    Delay(u32),
+}
+
+/// Checks that the interface is a parallel interface.
+///
+/// The serial interface of these controllers uses a start byte instead of a
+/// D/C pin and isn't supported.
+pub(crate) fn check_interface<DI: Interface>() -> Result<(), ModelInitError<DI::Error>> {
+    if !matches!(
+        DI::KIND,
+        InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+    ) {
+        return Err(ModelInitError::InvalidConfiguration(
+            ConfigurationError::UnsupportedInterface,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Writes an init table.
+pub(crate) fn write_table<DI, DELAY>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    table: &[Entry],
+) -> Result<(), DI::Error>
+where
+    DI: Interface,
+    DELAY: DelayNs,
+{
+    for entry in table {
+        match *entry {
+            Entry::Write(index, value) => di.write_register(index, value)?,
+            Entry::Delay(ms) => delay.delay_us(ms * 1_000),
+        }
+    }
+
+    Ok(())
+}
+
+/// Register values which depend on the model options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OptionRegisters {
+    pub driver_output: u16,
+    pub entry_mode: u16,
+    pub gate_scan: u16,
+    pub base_image: u16,
+}
+
+impl OptionRegisters {
+    pub fn new(options: &ModelOptions) -> Self {
+        let mapping = MemoryMapping::from(options.orientation);
+
+        // The source (SS) and gate (GS) scan directions are reversed on the
+        // common panels, which is the default orientation.
+        let driver_output = if mapping.reverse_columns {
+            0x0000
+        } else {
+            0x0100
+        };
+        let gate_scan = if mapping.reverse_rows { 0x2700 } else { 0xA700 };
+
+        // horizontal and vertical address increment
+        let mut entry_mode = 0x0030;
+        if mapping.swap_rows_and_columns {
+            entry_mode |= 0x0008; // AM: vertical address update
+        }
+        if options.color_order == ColorOrder::Bgr {
+            entry_mode |= 0x1000;
+        }
+
+        // REV: the panels are normally black
+        let base_image = match options.invert_colors {
+            ColorInversion::Normal => 0x0001,
+            ColorInversion::Inverted => 0x0000,
+        };
+
+        Self {
+            driver_output,
+            entry_mode,
+            gate_scan,
+            base_image,
+        }
+    }
+
+    pub fn write<DI: Interface>(&self, di: &mut DI) -> Result<(), DI::Error> {
+        di.write_register(DRIVER_OUTPUT_CTRL, self.driver_output)?;
+        di.write_register(ENTRY_MODE, self.entry_mode)?;
+        di.write_register(GATE_SCAN_CTRL, self.gate_scan)?;
+        di.write_register(BASE_IMAGE_CTRL, self.base_image)
+    }
+}
+
+/// Sets the address window and the GRAM address.
+///
+/// The window registers always use the native orientation of the panel, the
+/// coordinates are swapped for vertical rotations.
+pub(crate) fn update_address_window<DI>(
+    di: &mut DI,
+    rotation: Rotation,
+    sx: u16,
+    sy: u16,
+    ex: u16,
+    ey: u16,
+) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    let (sx, sy, ex, ey) = if rotation.is_vertical() {
+        (sy, sx, ey, ex)
+    } else {
+        (sx, sy, ex, ey)
+    };
+
+    di.write_register(HORIZONTAL_START, sx)?;
+    di.write_register(HORIZONTAL_END, ex)?;
+    di.write_register(VERTICAL_START, sy)?;
+    di.write_register(VERTICAL_END, ey)?;
+    di.write_register(GRAM_ADDR_HORIZONTAL, sx)?;
+    di.write_register(GRAM_ADDR_VERTICAL, sy)
+}
+
+/// Turns the display off and enters the sleep mode.
+pub(crate) fn sleep<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+where
+    DI: Interface,
+    DELAY: DelayNs,
+{
+    di.write_register(DISPLAY_CTRL1, 0x0000)?;
+    delay.delay_us(50_000);
+    di.write_register(POWER_CTRL1, 0x0002) // SLP
+}
+
+/// Exits the sleep mode with the given power control 1 value and turns the
+/// display on.
+pub(crate) fn wake<DI, DELAY>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    power_ctrl1: u16,
+) -> Result<(), DI::Error>
+where
+    DI: Interface,
+    DELAY: DelayNs,
+{
+    di.write_register(POWER_CTRL1, power_ctrl1)?;
+    delay.delay_us(50_000);
+    di.write_register(DISPLAY_CTRL1, 0x0133)
+}
+
+/// Turns the display on.
+pub(crate) fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    di.write_register(DISPLAY_CTRL1, 0x0133)
+}
+
+/// Turns the display off.
+pub(crate) fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    di.write_register(DISPLAY_CTRL1, 0x0000)
+}
+
+/// Selects the GRAM write register.
+pub(crate) fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    di.write_wide_raw(GRAM_WRITE, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{Orientation, Rotation};
+
+    use super::*;
+
+    #[test]
+    fn option_registers() {
+        let mut options = ModelOptions::with_all((240, 320), (0, 0));
+        options.color_order = ColorOrder::Bgr;
+
+        assert_eq!(
+            OptionRegisters::new(&options),
+            OptionRegisters {
+                driver_output: 0x0100,
+                entry_mode: 0x1030,
+                gate_scan: 0xA700,
+                base_image: 0x0001,
+            }
+        );
+
+        options.color_order = ColorOrder::Rgb;
+        options.invert_colors = ColorInversion::Inverted;
+        options.orientation = Orientation::new().rotate(Rotation::Deg90);
+        assert_eq!(
+            OptionRegisters::new(&options),
+            OptionRegisters {
+                driver_output: 0x0000,
+                entry_mode: 0x0038,
+                gate_scan: 0xA700,
+                base_image: 0x0000,
+            }
+        );
+    }
+}
@@ -2,9 +2,9 @@ use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
 use embedded_hal::delay::DelayNs;
 
 use crate::{
-    dcs::{BitsPerPixel, PixelFormat, SetAddressMode},
+    dcs::{BitsPerPixel, InterfaceExt, PixelFormat, SetAddressMode},
     interface::{Interface, InterfaceKind},
-    models::{ili934x, Model, ModelInitError},
+    models::{ili934x, Model, ModelConfig, ModelInitError},
     options::ModelOptions,
     ConfigurationError,
 };
@@ -15,9 +15,59 @@ pub struct ILI9341Rgb565;
 /// ILI9341 display in Rgb666 color mode.
 pub struct ILI9341Rgb666;
 
+/// Power and VCOM settings of the ILI9341.
+///
+/// Settings which are `None` keep the controller defaults. The settings are
+/// applied by using the
+/// [`Configured`](crate::models::Configured) model.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::models::{Configured, ILI9341Rgb565, Ili9341Config};
+///
+/// let model = Configured::new(
+///     ILI9341Rgb565,
+///     Ili9341Config {
+///         vcom: Some((0x3E, 0x28)),
+///         ..Ili9341Config::default()
+///     },
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Ili9341Config {
+    /// GVDD level (`PWCTRL1`, `C0h`).
+    pub power_control_1: Option<u8>,
+    /// Step up factor of the power circuit (`PWCTRL2`, `C1h`).
+    pub power_control_2: Option<u8>,
+    /// VCOMH and VCOML voltages (`VMCTRL1`, `C5h`).
+    pub vcom: Option<(u8, u8)>,
+    /// VCOM offset (`VMCTRL2`, `C7h`).
+    pub vcom_offset: Option<u8>,
+}
+
+impl ModelConfig<ILI9341Rgb565> for Ili9341Config {
+    fn write<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        write_config_ili9341(di, self)
+    }
+}
+
+impl ModelConfig<ILI9341Rgb666> for Ili9341Config {
+    fn write<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        write_config_ili9341(di, self)
+    }
+}
+
 impl Model for ILI9341Rgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -41,11 +91,19 @@ impl Model for ILI9341Rgb565 {
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
         ili934x::init_common(di, delay, options, pf).map_err(Into::into)
     }
+
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_frame_rate_ili9341(di, frame_rate)
+    }
 }
 
 impl Model for ILI9341Rgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -69,4 +127,48 @@ impl Model for ILI9341Rgb666 {
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
         ili934x::init_common(di, delay, options, pf).map_err(Into::into)
     }
+
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_frame_rate_ili9341(di, frame_rate)
+    }
+}
+
+/// Writes `FRMCTR1` with the clocks per line closest to `frame_rate`.
+///
+/// frame rate = fosc / (RTNA * (320 + VFP + VBP)), with fosc = 615 kHz and
+/// the default front and back porch of 2 lines each.
+fn set_frame_rate_ili9341<DI>(di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    const FOSC: u32 = 615_000;
+    const LINES: u32 = 320 + 2 + 2;
+
+    let frame_rate = u32::from(frame_rate.max(1));
+    let rtna = ((FOSC + frame_rate * LINES / 2) / (frame_rate * LINES)).clamp(16, 31);
+
+    di.write_raw(0xB1, &[0x00, rtna as u8]) // set frame rate, DIVA = fosc
+}
+
+fn write_config_ili9341<DI>(di: &mut DI, config: &Ili9341Config) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    if let Some(power_control_1) = config.power_control_1 {
+        di.write_raw(0xC0, &[power_control_1])?; // set power control 1
+    }
+    if let Some(power_control_2) = config.power_control_2 {
+        di.write_raw(0xC1, &[power_control_2])?; // set power control 2
+    }
+    if let Some((vcomh, vcoml)) = config.vcom {
+        di.write_raw(0xC5, &[vcomh, vcoml])?; // set VCOM control 1
+    }
+    if let Some(vcom_offset) = config.vcom_offset {
+        di.write_raw(0xC7, &[vcom_offset])?; // set VCOM control 2
+    }
+
+    Ok(())
 }
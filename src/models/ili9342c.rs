@@ -5,19 +5,39 @@ use crate::{
     dcs::{BitsPerPixel, PixelFormat, SetAddressMode},
     interface::{Interface, InterfaceKind},
     models::{ili934x, Model, ModelInitError},
-    options::ModelOptions,
+    options::{ColorInversion, ColorOrder, ModelOptions},
     ConfigurationError,
 };
 
 /// ILI9342C display in Rgb565 color mode.
+///
+/// The ILI9342C is the landscape native variant of the ILI9341, which is used
+/// in the M5Stack Core. Its panels use BGR subpixels and need color inversion.
+/// Both settings are enabled by default and can be changed with
+/// [`Builder::color_order`](crate::Builder::color_order) and
+/// [`Builder::invert_colors`](crate::Builder::invert_colors).
 pub struct ILI9342CRgb565;
 
 /// ILI9342C display in Rgb666 color mode.
+///
+/// Uses the same BGR and color inversion defaults as [`ILI9342CRgb565`].
 pub struct ILI9342CRgb666;
 
+fn default_options<M: Model>() -> ModelOptions {
+    let mut options = ModelOptions::full_size::<M>();
+    options.color_order = ColorOrder::Bgr;
+    options.invert_colors = ColorInversion::Inverted;
+    options
+}
+
 impl Model for ILI9342CRgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 240);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
+
+    fn default_options(&self) -> ModelOptions {
+        default_options::<Self>()
+    }
 
     fn init<DELAY, DI>(
         &mut self,
@@ -46,6 +66,11 @@ impl Model for ILI9342CRgb565 {
 impl Model for ILI9342CRgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 240);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
+
+    fn default_options(&self) -> ModelOptions {
+        default_options::<Self>()
+    }
 
     fn init<DELAY, DI>(
         &mut self,
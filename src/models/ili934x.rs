@@ -1,9 +1,11 @@
+//! Shared init routines for ILI934x compatible controllers.
+
 use embedded_hal::delay::DelayNs;
 
 use crate::{
     dcs::{
-        EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SequenceEntry, SetAddressMode,
+        SetDisplayOn, SetInvertMode, SetPixelFormat,
     },
     interface::Interface,
     options::ModelOptions,
@@ -16,6 +18,25 @@ pub fn init_common<DELAY, DI>(
     options: &ModelOptions,
     pixel_format: PixelFormat,
 ) -> Result<SetAddressMode, DI::Error>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    init_with_sequence(di, delay, options, pixel_format, &[])
+}
+
+/// Common init for ILI934x controllers with model specific settings.
+///
+/// The `sequence` is sent before the controller exits the sleep mode and
+/// usually contains the power and gamma settings of the model. Sequences can
+/// be created by using the [`dcs_seq`](crate::dcs_seq) macro.
+pub fn init_with_sequence<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    pixel_format: PixelFormat,
+    sequence: &[SequenceEntry],
+) -> Result<SetAddressMode, DI::Error>
 where
     DELAY: DelayNs,
     DI: Interface,
@@ -31,6 +52,7 @@ where
     di.write_command(SetInvertMode::new(options.invert_colors))?;
     di.write_command(SetPixelFormat::new(pixel_format))?;
 
+    di.write_sequence(sequence, delay)?;
     di.write_command(EnterNormalMode)?;
 
     // 8.2.12: It will be necessary to wait 120msec after sending Sleep In command (when in Sleep Out mode)
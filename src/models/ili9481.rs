@@ -0,0 +1,93 @@
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{BitsPerPixel, PixelFormat, SequenceEntry, SetAddressMode},
+    dcs_seq,
+    interface::{Interface, InterfaceKind},
+    models::{ili948x, Model, ModelInitError},
+    options::ModelOptions,
+    ConfigurationError,
+};
+
+/// ILI9481 display in Rgb565 color mode.
+///
+/// The serial interface of the ILI9481 doesn't support the 16 bit color mode,
+/// use [`ILI9481Rgb666`] with SPI instead.
+pub struct ILI9481Rgb565;
+
+/// ILI9481 display in Rgb666 color mode.
+pub struct ILI9481Rgb666;
+
+// Power, VCOM, panel driving and gamma settings.
+const INIT: &[SequenceEntry] = dcs_seq![
+    delay_ms(20),
+    cmd(0xD0, 0x07, 0x42, 0x18),             // power setting
+    cmd(0xD1, 0x00, 0x07, 0x10),             // VCOM control
+    cmd(0xD2, 0x01, 0x02),                   // power setting for normal mode
+    cmd(0xC0, 0x10, 0x3B, 0x00, 0x02, 0x11), // panel driving setting
+    cmd(0xC5, 0x03),                         // frame rate and inversion control
+    // gamma setting
+    cmd(0xC8, 0x00, 0x32, 0x36, 0x45, 0x06, 0x16, 0x37, 0x75, 0x77, 0x54, 0x0C, 0x00),
+];
+
+impl Model for ILI9481Rgb565 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        // the serial interface only supports 18 bit colors
+        if !matches!(
+            DI::KIND,
+            InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+        ) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        ili948x::init_with_sequence(di, delay, options, pf, INIT)
+    }
+}
+
+impl Model for ILI9481Rgb666 {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(
+            DI::KIND,
+            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+        ) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        ili948x::init_with_sequence(di, delay, options, pf, INIT)
+    }
+}
@@ -10,14 +10,37 @@ use crate::{
 };
 
 /// ILI9486 display in Rgb565 color mode.
+///
+/// The serial interface of the ILI9486 doesn't support the 16 bit color mode,
+/// use [`ILI9486Rgb666`] with SPI instead.
 pub struct ILI9486Rgb565;
 
 /// ILI9486 display in Rgb666 color mode.
+///
+/// This model must be used with the SPI based 320x480 shields, because the
+/// serial interface of the ILI9486 only supports 18 bit colors, which are
+/// sent as three bytes per pixel. Rgb565 content can still be drawn by
+/// converting it on the fly:
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+/// use mipidsi::{models::ILI9486Rgb666, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(ILI9486Rgb666, di).init(&mut delay).unwrap();
+///
+/// display
+///     .color_converted::<Rgb565>()
+///     .clear(Rgb565::BLUE)
+///     .unwrap();
+/// ```
 pub struct ILI9486Rgb666;
 
 impl Model for ILI9486Rgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -29,6 +52,7 @@ impl Model for ILI9486Rgb565 {
         DELAY: DelayNs,
         DI: Interface,
     {
+        // the serial interface only supports 18 bit colors
         if !matches!(
             DI::KIND,
             InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
@@ -48,6 +72,7 @@ impl Model for ILI9486Rgb565 {
 impl Model for ILI9486Rgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
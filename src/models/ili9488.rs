@@ -1,4 +1,4 @@
-use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666, Rgb888};
 use embedded_hal::delay::DelayNs;
 
 use crate::{
@@ -10,14 +10,42 @@ use crate::{
 };
 
 /// ILI9488 display in Rgb565 color mode.
+///
+/// The serial interface of the ILI9488 doesn't support the 16 bit color mode,
+/// use [`ILI9488Rgb666`] with SPI instead.
 pub struct ILI9488Rgb565;
 
 /// ILI9488 display in Rgb666 color mode.
+///
+/// This model must be used with SPI, because the serial interface of the
+/// ILI9488 only supports 18 bit colors, which are sent as three bytes per
+/// pixel. Rgb565 content can still be drawn by converting it on the fly:
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+/// use mipidsi::{models::ILI9488Rgb666, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(ILI9488Rgb666, di).init(&mut delay).unwrap();
+///
+/// display
+///     .color_converted::<Rgb565>()
+///     .clear(Rgb565::BLUE)
+///     .unwrap();
+/// ```
 pub struct ILI9488Rgb666;
 
+/// ILI9488 display in Rgb888 color mode.
+///
+/// The 24 bit color mode is only supported by the 8 bit parallel interface,
+/// which transfers each pixel in three write cycles.
+pub struct ILI9488Rgb888;
+
 impl Model for ILI9488Rgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -29,9 +57,10 @@ impl Model for ILI9488Rgb565 {
         DELAY: DelayNs,
         DI: Interface,
     {
+        // the serial interface only supports 18 bit colors
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -48,6 +77,7 @@ impl Model for ILI9488Rgb565 {
 impl Model for ILI9488Rgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -74,3 +104,31 @@ impl Model for ILI9488Rgb666 {
         ili948x::init_common(di, delay, options, pf)
     }
 }
+
+impl Model for ILI9488Rgb888 {
+    type ColorFormat = Rgb888;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(DI::KIND, InterfaceKind::Parallel8Bit) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        ili948x::init_common(di, delay, options, pf)
+    }
+}
@@ -1,9 +1,11 @@
+//! Shared init routines for ILI948x compatible controllers.
+
 use embedded_hal::delay::DelayNs;
 
 use crate::{
     dcs::{
-        EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SequenceEntry, SetAddressMode,
+        SetDisplayOn, SetInvertMode, SetPixelFormat,
     },
     interface::Interface,
     models::ModelInitError,
@@ -17,12 +19,32 @@ pub fn init_common<DELAY, DI>(
     options: &ModelOptions,
     pixel_format: PixelFormat,
 ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    init_with_sequence(di, delay, options, pixel_format, &[])
+}
+
+/// Common init for ILI948x controllers with model specific settings.
+///
+/// The `sequence` is sent after the controller exits the sleep mode and
+/// usually contains the power and gamma settings of the model. Sequences can
+/// be created by using the [`dcs_seq`](crate::dcs_seq) macro.
+pub fn init_with_sequence<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    pixel_format: PixelFormat,
+    sequence: &[SequenceEntry],
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
 where
     DELAY: DelayNs,
     DI: Interface,
 {
     let madctl = SetAddressMode::from(options);
     di.write_command(ExitSleepMode)?; // turn off sleep
+    di.write_sequence(sequence, delay)?;
     di.write_command(SetPixelFormat::new(pixel_format))?; // pixel format
     di.write_command(madctl)?; // left -> right, bottom -> top RGB
                                // dcs.write_command(Instruction::VCMOFSET, &[0x00, 0x48, 0x00, 0x48])?; //VCOM  Control 1 [00 40 00 40]
@@ -0,0 +1,183 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SequenceEntry,
+        SetAddressMode, SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
+    dcs_seq,
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+};
+
+/// ILI9806E display in Rgb565 color mode.
+pub struct ILI9806E;
+
+// Page 1 of the extended command set: power, timing and gamma settings.
+const PAGE1: &[SequenceEntry] = dcs_seq![
+    cmd(0xFF, 0xFF, 0x98, 0x06, 0x04, 0x01), // EXTC: select page 1
+    cmd(0x08, 0x10),                         // interface mode: SDO output
+    cmd(0x21, 0x01),                         // display function: DE active high
+    cmd(0x30, 0x01),                         // resolution: 480x854
+    cmd(0x31, 0x00),                         // inversion: column
+    cmd(0x40, 0x14),                         // power control 1: BT
+    cmd(0x41, 0x33),                         // power control 2: DVDDH/DVDDL clamp
+    cmd(0x42, 0x02),                         // power control 3: VGH/VGL
+    cmd(0x43, 0x09),                         // power control 4: VGH clamp
+    cmd(0x44, 0x06),                         // power control 5: VGL clamp
+    cmd(0x50, 0x70),                         // VGMP
+    cmd(0x51, 0x70),                         // VGMN
+    cmd(0x52, 0x00),                         // flicker MSB
+    cmd(0x53, 0x48),                         // flicker LSB
+    cmd(0x60, 0x07),                         // source timing
+    cmd(0x61, 0x00),
+    cmd(0x62, 0x08),
+    cmd(0x63, 0x00),
+    // positive gamma
+    cmd(0xA0, 0x00),
+    cmd(0xA1, 0x03),
+    cmd(0xA2, 0x09),
+    cmd(0xA3, 0x0D),
+    cmd(0xA4, 0x06),
+    cmd(0xA5, 0x16),
+    cmd(0xA6, 0x09),
+    cmd(0xA7, 0x08),
+    cmd(0xA8, 0x03),
+    cmd(0xA9, 0x07),
+    cmd(0xAA, 0x06),
+    cmd(0xAB, 0x05),
+    cmd(0xAC, 0x0D),
+    cmd(0xAD, 0x2C),
+    cmd(0xAE, 0x26),
+    cmd(0xAF, 0x00),
+    // negative gamma
+    cmd(0xC0, 0x00),
+    cmd(0xC1, 0x04),
+    cmd(0xC2, 0x0B),
+    cmd(0xC3, 0x0F),
+    cmd(0xC4, 0x09),
+    cmd(0xC5, 0x18),
+    cmd(0xC6, 0x07),
+    cmd(0xC7, 0x08),
+    cmd(0xC8, 0x05),
+    cmd(0xC9, 0x09),
+    cmd(0xCA, 0x07),
+    cmd(0xCB, 0x05),
+    cmd(0xCC, 0x0C),
+    cmd(0xCD, 0x2D),
+    cmd(0xCE, 0x28),
+    cmd(0xCF, 0x00),
+];
+
+// Page 6 of the extended command set: gate driver (GIP) timing.
+const PAGE6: &[SequenceEntry] = dcs_seq![
+    cmd(0xFF, 0xFF, 0x98, 0x06, 0x04, 0x06), // EXTC: select page 6
+    cmd(0x00, 0x21),
+    cmd(0x01, 0x09),
+    cmd(0x02, 0x00),
+    cmd(0x03, 0x00),
+    cmd(0x04, 0x01),
+    cmd(0x05, 0x01),
+    cmd(0x06, 0x80),
+    cmd(0x07, 0x05),
+    cmd(0x08, 0x02),
+    cmd(0x09, 0x80),
+    cmd(0x0A, 0x00),
+    cmd(0x0B, 0x00),
+    cmd(0x0C, 0x0A),
+    cmd(0x0D, 0x0A),
+    cmd(0x0E, 0x00),
+    cmd(0x0F, 0x00),
+    cmd(0x10, 0xE0),
+    cmd(0x11, 0xE4),
+    cmd(0x12, 0x04),
+    cmd(0x13, 0x00),
+    cmd(0x14, 0x00),
+    cmd(0x15, 0xC0),
+    cmd(0x16, 0x08),
+    cmd(0x17, 0x00),
+    cmd(0x18, 0x00),
+    cmd(0x19, 0x00),
+    cmd(0x1A, 0x00),
+    cmd(0x1B, 0x00),
+    cmd(0x1C, 0x00),
+    cmd(0x1D, 0x00),
+    cmd(0x20, 0x01),
+    cmd(0x21, 0x23),
+    cmd(0x22, 0x45),
+    cmd(0x23, 0x67),
+    cmd(0x24, 0x01),
+    cmd(0x25, 0x23),
+    cmd(0x26, 0x45),
+    cmd(0x27, 0x67),
+    cmd(0x30, 0x01),
+    cmd(0x31, 0x11),
+    cmd(0x32, 0x00),
+    cmd(0x33, 0xEE),
+    cmd(0x34, 0xFF),
+    cmd(0x35, 0xBB),
+    cmd(0x36, 0xCA),
+    cmd(0x37, 0xDD),
+    cmd(0x38, 0xAC),
+    cmd(0x39, 0x76),
+    cmd(0x3A, 0x67),
+    cmd(0x3B, 0x22),
+    cmd(0x3C, 0x22),
+    cmd(0x3D, 0x22),
+    cmd(0x3E, 0x22),
+    cmd(0x3F, 0x22),
+    cmd(0x40, 0x22),
+    cmd(0x52, 0x10),
+    cmd(0x53, 0x10),
+];
+
+// Page 7 of the extended command set, followed by switching back to the
+// user command set on page 0.
+const PAGE7: &[SequenceEntry] = dcs_seq![
+    cmd(0xFF, 0xFF, 0x98, 0x06, 0x04, 0x07), // EXTC: select page 7
+    cmd(0x17, 0x22),                         // VGL_REG
+    cmd(0x02, 0x77),
+    cmd(0xE1, 0x79),
+    cmd(0xFF, 0xFF, 0x98, 0x06, 0x04, 0x00), // EXTC: select page 0
+];
+
+impl Model for ILI9806E {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (480, 854);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        di.write_sequence(PAGE1, delay)?;
+        di.write_sequence(PAGE6, delay)?;
+        di.write_sequence(PAGE7, delay)?;
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?;
+        di.write_command(madctl)?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+        di.write_command(EnterNormalMode)?;
+        di.write_command(SetDisplayOn)?;
+
+        // DISPON requires some time otherwise we risk SPI data issues
+        delay.delay_us(120_000);
+
+        Ok(madctl)
+    }
+}
@@ -0,0 +1,305 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterIdleMode, EnterNormalMode, EnterPartialMode, EnterSleepMode,
+        ExitIdleMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAdaptiveBrightness,
+        SetAddressMode, SetColumnAddress, SetDisplayBrightness, SetDisplayControl, SetDisplayOff,
+        SetDisplayOn, SetGammaCurve, SetInvertMode, SetPageAddress, SetPartialArea, SetPixelFormat,
+        SetScrollArea, SetScrollStart, SetTearScanline, SetTearingEffect, SoftReset,
+        WriteMemoryContinue, WriteMemoryStart,
+    },
+    interface::{Interface, InterfaceKind},
+    models::{Model, ModelInitError},
+    options::{self, ModelOptions, Rotation},
+    ConfigurationError,
+};
+
+/// NT35510 display in Rgb565 color mode.
+///
+/// The NT35510 uses 16 bit register addresses on its parallel interfaces.
+/// DCS commands are sent with the instruction code as the high byte of the
+/// address, e.g. `0x2A00` for `CASET`, and every parameter is written to its
+/// own register. See [`Interface::send_wide_command`] for more information.
+///
+/// The framebuffer is 480x800 pixels, use [`Rotation::Deg90`] for the common
+/// 800x480 landscape modules.
+pub struct NT35510;
+
+impl Model for NT35510 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (480, 800);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(
+            DI::KIND,
+            InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+        ) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        // enable page 1 of the manufacturer command set
+        di.write_wide_raw(0xF000, &[0x55, 0xAA, 0x52, 0x08, 0x01])?;
+        di.write_wide_raw(0xB000, &[0x0D, 0x0D, 0x0D])?; // AVDD: 5.2V
+        di.write_wide_raw(0xB600, &[0x34, 0x34, 0x34])?; // AVDD ratio
+        di.write_wide_raw(0xB100, &[0x0D, 0x0D, 0x0D])?; // AVEE: -5.2V
+        di.write_wide_raw(0xB700, &[0x34, 0x34, 0x34])?; // AVEE ratio
+        di.write_wide_raw(0xB200, &[0x00, 0x00, 0x00])?; // VCL: -2.5V
+        di.write_wide_raw(0xB800, &[0x24, 0x24, 0x24])?; // VCL ratio
+        di.write_wide_raw(0xBF00, &[0x01])?; // VGH: 15V
+        di.write_wide_raw(0xB300, &[0x0F, 0x0F, 0x0F])?;
+        di.write_wide_raw(0xB900, &[0x34, 0x34, 0x34])?; // VGH ratio
+        di.write_wide_raw(0xB500, &[0x08, 0x08, 0x08])?; // VGL_REG: -10V
+        di.write_wide_raw(0xBA00, &[0x24, 0x24, 0x24])?; // VGL ratio
+        di.write_wide_raw(0xBC00, &[0x00, 0x78, 0x00])?; // VGMP/VGSP: 4.5V/0V
+        di.write_wide_raw(0xBD00, &[0x00, 0x78, 0x00])?; // VGMN/VGSN: -4.5V/0V
+        di.write_wide_raw(0xBE00, &[0x00, 0x64])?; // VCOM: -1.325V
+
+        // enable page 0 of the manufacturer command set
+        di.write_wide_raw(0xF000, &[0x55, 0xAA, 0x52, 0x08, 0x00])?;
+        di.write_wide_raw(0xB100, &[0xCC, 0x00])?; // RAM keep
+        di.write_wide_raw(0xB600, &[0x05])?; // source output data hold time
+        di.write_wide_raw(0xB700, &[0x70, 0x70])?; // gate EQ
+        di.write_wide_raw(0xB800, &[0x01, 0x03, 0x03, 0x03])?; // source EQ
+        di.write_wide_raw(0xBC00, &[0x02, 0x00, 0x00])?; // column inversion
+        di.write_wide_raw(0xC900, &[0xD0, 0x02, 0x50, 0x50, 0x50])?; // display timing
+
+        di.write_wide_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_wide_command(SetPixelFormat::new(pf))?;
+        di.write_wide_command(madctl)?;
+        di.write_wide_command(SetInvertMode::new(options.invert_colors))?;
+        di.write_wide_command(EnterNormalMode)?;
+        di.write_wide_command(SetDisplayOn)?;
+
+        // DISPON requires some time otherwise we risk SPI data issues
+        delay.delay_us(120_000);
+
+        Ok(madctl)
+    }
+
+    fn update_address_window<DI>(
+        di: &mut DI,
+        _rotation: Rotation,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetColumnAddress::new(sx, ex))?;
+        di.write_wide_command(SetPageAddress::new(sy, ey))
+    }
+
+    fn sleep<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_wide_command(EnterSleepMode)?;
+        delay.delay_us(120_000);
+        Ok(())
+    }
+
+    fn wake<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_wide_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+        Ok(())
+    }
+
+    fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetDisplayOn)
+    }
+
+    fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetDisplayOff)
+    }
+
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_wide_command(SetDisplayOff)?;
+        Self::sleep(di, delay)
+    }
+
+    fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(WriteMemoryStart)
+    }
+
+    fn write_memory_continue<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(WriteMemoryContinue)
+    }
+
+    fn software_reset<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SoftReset)
+    }
+
+    fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetAddressMode::from(options))
+    }
+
+    fn set_tearing_effect<DI>(
+        di: &mut DI,
+        tearing_effect: options::TearingEffect,
+        _options: &ModelOptions,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetTearingEffect::new(tearing_effect))
+    }
+
+    fn set_pixel_format<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_wide_command(SetPixelFormat::new(pf))
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetInvertMode::new(options.invert_colors))
+    }
+
+    fn set_gamma_curve<DI>(di: &mut DI, curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetGammaCurve::new(curve))
+    }
+
+    fn set_partial_area<DI>(di: &mut DI, start_row: u16, end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetPartialArea::new(start_row, end_row))?;
+        di.write_wide_command(EnterPartialMode)
+    }
+
+    fn exit_partial_mode<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(EnterNormalMode)
+    }
+
+    fn set_idle_mode<DI>(di: &mut DI, idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        if idle {
+            di.write_wide_command(EnterIdleMode)
+        } else {
+            di.write_wide_command(ExitIdleMode)
+        }
+    }
+
+    fn set_brightness<DI>(di: &mut DI, brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetDisplayBrightness::new(brightness))
+    }
+
+    fn set_backlight_control<DI>(di: &mut DI, mode: options::BacklightMode) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetDisplayControl::new(mode))
+    }
+
+    fn set_adaptive_brightness<DI>(
+        di: &mut DI,
+        mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetAdaptiveBrightness::new(mode))
+    }
+
+    fn set_tear_scanline<DI>(di: &mut DI, scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetTearScanline::new(scanline))
+    }
+
+    fn set_vertical_scroll_region<DI>(
+        &self,
+        di: &mut DI,
+        top_fixed_area: u16,
+        bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        let rows = self.framebuffer_size().1;
+
+        let vscrdef = if top_fixed_area.saturating_add(bottom_fixed_area) > rows {
+            SetScrollArea::new(rows, 0, 0)
+        } else {
+            SetScrollArea::new(
+                top_fixed_area,
+                rows - top_fixed_area - bottom_fixed_area,
+                bottom_fixed_area,
+            )
+        };
+
+        di.write_wide_command(vscrdef)
+    }
+
+    fn set_vertical_scroll_offset<DI>(&self, di: &mut DI, start_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_wide_command(SetScrollStart::new(start_row))
+    }
+}
@@ -0,0 +1,166 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        ExitSleepMode, InterfaceExt, SequenceEntry, SetAddressMode, SetDisplayOn, SetInvertMode,
+    },
+    dcs_seq,
+    interface::{Interface, InterfaceKind},
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+    ConfigurationError,
+};
+
+/// NV3041A display in Rgb565 color mode.
+///
+/// Used in the 4.3" 480x272 panels, which are connected over SPI or QSPI.
+/// The pixel data throughput of QSPI is four times higher than SPI, use
+/// [`QspiInterface`](crate::interface::QspiInterface) with a
+/// [`QspiBus`](crate::interface::QspiBus) implementation for the QSPI
+/// peripheral of the MCU to use it.
+pub struct NV3041A;
+
+// Vendor init sequence: power, timing and gamma settings.
+const VENDOR_INIT: &[SequenceEntry] = dcs_seq![
+    cmd(0xFF, 0xA5), // unlock vendor commands
+    cmd(0x41, 0x03), // interface bus width: 16 bit
+    cmd(0x44, 0x15), // VBP
+    cmd(0x45, 0x15), // VFP
+    cmd(0x7D, 0x03), // vdds_trim
+    cmd(0xC1, 0xBB), // avdd_clp_en, avcl_clp_en
+    cmd(0xC2, 0x05), // vgl_clp_en
+    cmd(0xC3, 0x10), // vgl_clp_en
+    cmd(0xC6, 0x3E), // avdd_ratio_sel, avcl_ratio_sel
+    cmd(0xC7, 0x25), // mv_clk_sel
+    cmd(0xC8, 0x11), // VGL_CLK_sel
+    cmd(0x7A, 0x5F), // user_vgsp
+    cmd(0x6F, 0x44), // user_gvdd
+    cmd(0x78, 0x70), // user_gvcl
+    cmd(0xC9, 0x00),
+    cmd(0x67, 0x21),
+    // gate timing
+    cmd(0x51, 0x0A), // gate_st_o
+    cmd(0x52, 0x76), // gate_ed_o
+    cmd(0x53, 0x0A), // gate_st_e
+    cmd(0x54, 0x76), // gate_ed_e
+    // source timing
+    cmd(0x46, 0x0A), // fsm_hbp_o
+    cmd(0x47, 0x2A), // fsm_hfp_o
+    cmd(0x48, 0x0A), // fsm_hbp_e
+    cmd(0x49, 0x1A), // fsm_hfp_e
+    cmd(0x56, 0x43), // src_ld_wd
+    cmd(0x57, 0x42), // pre_ld_wd
+    cmd(0x58, 0x3C), // src_ld_st
+    cmd(0x59, 0x64), // pre_ld_st
+    cmd(0x5A, 0x41), // src_ld_wd_e
+    cmd(0x5B, 0x3C), // src_ld_st_e
+    cmd(0x5C, 0x02), // src_ld_wd_o
+    cmd(0x5D, 0x3C), // src_ld_st_o
+    cmd(0x5E, 0x1F), // src_pre_wd
+    cmd(0x60, 0x80), // src_pre_st
+    cmd(0x61, 0x3F), // src_pre_ed
+    cmd(0x62, 0x21), // src_pre_o
+    cmd(0x63, 0x07), // src_pre_e
+    cmd(0x64, 0xE0), // src_pre_e
+    cmd(0x65, 0x02), // chopper
+    cmd(0xCA, 0x20), // avdd_mux_st_o
+    cmd(0xCB, 0x52), // avdd_mux_ed_o
+    cmd(0xCC, 0x10), // avdd_mux_st_e
+    cmd(0xCD, 0x42), // avdd_mux_ed_e
+    cmd(0xD0, 0x20), // avcl_mux_st_o
+    cmd(0xD1, 0x52), // avcl_mux_ed_o
+    cmd(0xD2, 0x10), // avcl_mux_st_e
+    cmd(0xD3, 0x42), // avcl_mux_ed_e
+    cmd(0xD4, 0x0A), // vgh_mux_st
+    cmd(0xD5, 0x32), // vgh_mux_ed
+    // gamma, the 0x8x registers are the positive and 0xAx the negative curve
+    cmd(0x80, 0x00),
+    cmd(0xA0, 0x00),
+    cmd(0x81, 0x07),
+    cmd(0xA1, 0x06),
+    cmd(0x82, 0x02),
+    cmd(0xA2, 0x01),
+    cmd(0x86, 0x11),
+    cmd(0xA6, 0x10),
+    cmd(0x87, 0x27),
+    cmd(0xA7, 0x27),
+    cmd(0x83, 0x37),
+    cmd(0xA3, 0x37),
+    cmd(0x84, 0x35),
+    cmd(0xA4, 0x35),
+    cmd(0x85, 0x3F),
+    cmd(0xA5, 0x3F),
+    cmd(0x88, 0x0B),
+    cmd(0xA8, 0x0B),
+    cmd(0x89, 0x14),
+    cmd(0xA9, 0x14),
+    cmd(0x8A, 0x1A),
+    cmd(0xAA, 0x1A),
+    cmd(0x8B, 0x0A),
+    cmd(0xAB, 0x0A),
+    cmd(0x8C, 0x14),
+    cmd(0xAC, 0x08),
+    cmd(0x8D, 0x17),
+    cmd(0xAD, 0x07),
+    cmd(0x8E, 0x16),
+    cmd(0xAE, 0x06),
+    cmd(0x8F, 0x1B),
+    cmd(0xAF, 0x07),
+    cmd(0x90, 0x04),
+    cmd(0xB0, 0x04),
+    cmd(0x91, 0x0A),
+    cmd(0xB1, 0x0A),
+    cmd(0x92, 0x16),
+    cmd(0xB2, 0x15),
+    cmd(0xFF, 0x00), // lock vendor commands
+];
+
+impl Model for NV3041A {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (480, 272);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(DI::KIND, InterfaceKind::Serial4Line) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        di.write_sequence(VENDOR_INIT, delay)?;
+
+        // The NV3041A doesn't use the DCS pixel format values, 0x01 selects
+        // 16 bit and 0x00 selects 18 bit colors.
+        di.write_raw(0x3A, &[0x01])?;
+        di.write_command(madctl)?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        di.write_command(SetDisplayOn)?;
+        delay.delay_us(20_000);
+
+        Ok(madctl)
+    }
+
+    fn set_pixel_format<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(0x3A, &[0x01]) // 16 bit colors
+    }
+}
@@ -0,0 +1,136 @@
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb888};
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
+    interface::{Interface, InterfaceKind},
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+    ConfigurationError,
+};
+
+/// R61529 display in Rgb565 color mode.
+pub struct R61529Rgb565;
+
+/// R61529 display in Rgb888 color mode.
+///
+/// The 24 bit color mode is only supported by the 8 bit parallel interface,
+/// which transfers each pixel in three write cycles.
+pub struct R61529Rgb888;
+
+impl Model for R61529Rgb565 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(
+            DI::KIND,
+            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+        ) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        init_r61529(di, delay, options, pf)
+    }
+}
+
+impl Model for R61529Rgb888 {
+    type ColorFormat = Rgb888;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(DI::KIND, InterfaceKind::Parallel8Bit) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        init_r61529(di, delay, options, pf)
+    }
+}
+
+// Init sequence for the R61529. The panel and power registers are protected
+// by the manufacturer command access protect (MCAP) register and can only be
+// written after MCAP has been disabled.
+fn init_r61529<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    pixel_format: PixelFormat,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    let madctl = SetAddressMode::from(options);
+
+    delay.delay_us(120_000);
+
+    di.write_command(ExitSleepMode)?;
+    delay.delay_us(120_000);
+
+    di.write_raw(0xB0, &[0x04])?; // MCAP: allow access to manufacturer commands
+
+    di.write_raw(0xC0, &[0x01, 0xDF, 0x40, 0x10, 0x00, 0x01, 0x00, 0x33])?; // panel driving
+    di.write_raw(0xC1, &[0x07, 0x27, 0x08, 0x08, 0x00])?; // display timing
+    di.write_raw(0xC4, &[0x57, 0x00, 0x05, 0x03])?; // source/gate driving timing
+    di.write_raw(0xC6, &[0x04])?; // DPI polarity
+
+    // gamma set A, B and C
+    let gamma = [
+        0x03, 0x12, 0x1A, 0x24, 0x32, 0x4B, 0x3B, 0x29, 0x1F, 0x18, 0x12, 0x04, 0x03, 0x12, 0x1A,
+        0x24, 0x32, 0x4B, 0x3B, 0x29, 0x1F, 0x18, 0x12, 0x04,
+    ];
+    di.write_raw(0xC8, &gamma)?;
+    di.write_raw(0xC9, &gamma)?;
+    di.write_raw(0xCA, &gamma)?;
+
+    // power setting
+    di.write_raw(
+        0xD0,
+        &[
+            0x99, 0x06, 0x08, 0x20, 0x29, 0x04, 0x01, 0x00, 0x08, 0x01, 0x00, 0x06, 0x01, 0x00,
+            0x00, 0x20,
+        ],
+    )?;
+    di.write_raw(0xD1, &[0x00, 0x20, 0x20, 0x15])?; // VCOM setting
+    delay.delay_us(20_000);
+
+    di.write_raw(0xB0, &[0x03])?; // MCAP: protect manufacturer commands
+
+    di.write_command(madctl)?;
+    di.write_command(SetPixelFormat::new(pixel_format))?;
+    di.write_command(SetInvertMode::new(options.invert_colors))?;
+    di.write_command(EnterNormalMode)?;
+    di.write_command(SetDisplayOn)?;
+
+    // DISPON requires some time otherwise we risk SPI data issues
+    delay.delay_us(120_000);
+
+    Ok(madctl)
+}
@@ -25,6 +25,11 @@ use crate::{
 /// While it may work with other display sizes, this is untested and could lead to unexpected behavior.
 /// If you encounter issues with different display sizes, please report them.
 ///
+/// The T-Display-S3 AMOLED connects the display over QSPI, use
+/// [`QspiInterface`](crate::interface::QspiInterface) with a
+/// [`QspiBus`](crate::interface::QspiBus) implementation for the QSPI
+/// peripheral of the MCU.
+///
 pub struct RM67162;
 
 impl Model for RM67162 {
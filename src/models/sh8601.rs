@@ -0,0 +1,72 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetDisplayBrightness, SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
+    interface::{Interface, InterfaceKind},
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+    ConfigurationError,
+};
+
+/// SH8601 AMOLED display in Rgb565 color mode.
+///
+/// Used in the round 1.43" 466x466 AMOLED modules, which are usually
+/// connected over QSPI by using
+/// [`QspiInterface`](crate::interface::QspiInterface).
+///
+/// AMOLED panels start with a brightness of 0 and appear to be dead until
+/// the brightness is set. The init sequence turns the display on at full
+/// brightness, which can be changed by using
+/// [`Display::set_brightness`](crate::Display::set_brightness).
+pub struct SH8601;
+
+impl Model for SH8601 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (466, 466);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if !matches!(
+            DI::KIND,
+            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit
+        ) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface,
+            ));
+        }
+
+        let madctl = SetAddressMode::from(options);
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?;
+        di.write_command(madctl)?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+        di.write_raw(0x53, &[0x20])?; // WRCTRLD: enable brightness control
+        delay.delay_us(10_000);
+        di.write_command(SetDisplayBrightness::new(0))?;
+
+        di.write_command(SetDisplayOn)?;
+        // the panel needs to settle before the brightness is raised
+        delay.delay_us(20_000);
+
+        di.write_command(SetDisplayBrightness::new(u8::MAX))?;
+
+        Ok(madctl)
+    }
+}
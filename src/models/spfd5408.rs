@@ -0,0 +1,307 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::SetAddressMode,
+    interface::Interface,
+    models::{
+        ili932x::{self, Entry, OptionRegisters},
+        Model, ModelInitError,
+    },
+    options::{self, ColorOrder, ModelOptions, Rotation},
+};
+
+/// SPFD5408 display in Rgb565 color mode.
+///
+/// The SPFD5408 is used on many older 2.4" 240x320 shields. It uses the same
+/// register set as the ILI9328 and doesn't use DCS commands, instead all
+/// settings are written to 16 bit registers by using
+/// [`Interface::send_register`]. Only the 8 and 16 bit parallel interfaces are
+/// supported.
+///
+/// The panels use BGR subpixels, which is the default color order of this
+/// model.
+pub struct SPFD5408;
+
+/// Power control 1 value in normal operation.
+const POWER_CTRL1: u16 = 0x14B0;
+
+// Display control, power on and gamma settings.
+const INIT: &[Entry] = &[
+    Entry::Write(0x02, 0x0700), // LCD driving control: line inversion
+    Entry::Write(0x04, 0x0000), // resize control
+    Entry::Write(0x08, 0x0207), // display control 2: back and front porch
+    Entry::Write(0x09, 0x0000), // display control 3
+    Entry::Write(0x0A, 0x0000), // display control 4
+    Entry::Write(0x0C, 0x0000), // RGB interface control 1
+    Entry::Write(0x0D, 0x0000), // frame marker position
+    Entry::Write(0x0F, 0x0000), // RGB interface control 2
+    // power on sequence
+    Entry::Write(0x07, 0x0101),
+    Entry::Write(0x10, POWER_CTRL1),
+    Entry::Delay(10),
+    Entry::Write(0x11, 0x0007),
+    Entry::Write(0x17, 0x0001), // power control 5
+    Entry::Write(0x12, 0x01B8),
+    Entry::Write(0x13, 0x1300),
+    Entry::Write(0x29, 0x000F), // VCOMH
+    Entry::Delay(50),
+    // gamma
+    Entry::Write(0x30, 0x0102),
+    Entry::Write(0x31, 0x0C18),
+    Entry::Write(0x32, 0x0F1C),
+    Entry::Write(0x33, 0x1C0F),
+    Entry::Write(0x34, 0x180C),
+    Entry::Write(0x35, 0x0201),
+    Entry::Write(0x36, 0x0D1F),
+    Entry::Write(0x37, 0x0404),
+    Entry::Write(0x38, 0x0404),
+    Entry::Write(0x39, 0x0000),
+    Entry::Write(0x3A, 0x1F0D),
+    Entry::Write(0x3B, 0x0100),
+    Entry::Write(0x3C, 0x0202),
+    Entry::Write(0x3D, 0x0101),
+    Entry::Write(0x6A, 0x0000), // vertical scroll
+    Entry::Write(0x90, 0x0010), // panel interface control 1
+    Entry::Write(0x92, 0x0000), // panel interface control 2
+    Entry::Write(0x93, 0x0003), // panel interface control 3
+    Entry::Write(0x95, 0x0110), // panel interface control 4
+    Entry::Write(0x97, 0x0000), // panel interface control 5
+    Entry::Write(0x98, 0x0000), // panel interface control 6
+    Entry::Write(0x07, 0x0112),
+    Entry::Delay(50),
+];
+
+impl Model for SPFD5408 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+
+    fn default_options(&self) -> ModelOptions {
+        let mut options = ModelOptions::full_size::<Self>();
+        options.color_order = ColorOrder::Bgr;
+        options
+    }
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        ili932x::check_interface::<DI>()?;
+
+        let madctl = SetAddressMode::from(options);
+
+        OptionRegisters::new(options).write(di)?;
+        ili932x::write_table(di, delay, INIT)?;
+        ili932x::wake(di, delay, POWER_CTRL1)?;
+
+        Ok(madctl)
+    }
+
+    fn update_address_window<DI>(
+        di: &mut DI,
+        rotation: Rotation,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        ili932x::update_address_window(di, rotation, sx, sy, ex, ey)
+    }
+
+    fn sleep<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        ili932x::sleep(di, delay)
+    }
+
+    fn wake<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        ili932x::wake(di, delay, POWER_CTRL1)
+    }
+
+    fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        ili932x::display_on(di)
+    }
+
+    fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        ili932x::display_off(di)
+    }
+
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        // sleep already turns the display off
+        Self::sleep(di, delay)
+    }
+
+    fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        ili932x::write_memory_start(di)
+    }
+
+    fn write_memory_continue<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The address counter isn't reset by the memory write command
+        Self::write_memory_start(di)
+    }
+
+    fn software_reset<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The SPFD5408 doesn't have a software reset command
+        Ok(())
+    }
+
+    fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        OptionRegisters::new(options).write(di)
+    }
+
+    fn set_pixel_format<DI>(&self, _di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the controller only supports 16 bit pixels
+        Ok(())
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the inversion is part of the options registers
+        self.update_options(di, options)
+    }
+
+    fn set_tearing_effect<DI>(
+        _di: &mut DI,
+        _tearing_effect: options::TearingEffect,
+        _options: &ModelOptions,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_gamma_curve<DI>(_di: &mut DI, _curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_partial_area<DI>(_di: &mut DI, _start_row: u16, _end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn exit_partial_mode<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_idle_mode<DI>(_di: &mut DI, _idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_brightness<DI>(_di: &mut DI, _brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_backlight_control<DI>(
+        _di: &mut DI,
+        _mode: options::BacklightMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_adaptive_brightness<DI>(
+        _di: &mut DI,
+        _mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_tear_scanline<DI>(_di: &mut DI, _scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_vertical_scroll_region<DI>(
+        &self,
+        _di: &mut DI,
+        _top_fixed_area: u16,
+        _bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_vertical_scroll_offset<DI>(&self, _di: &mut DI, _start_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+}
@@ -0,0 +1,371 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{BitsPerPixel, SetAddressMode},
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::{self, ColorInversion, ModelOptions, Rotation},
+};
+
+/// SSD1331 OLED display in Rgb565 color mode.
+///
+/// The SSD1331 uses its own command set instead of DCS commands. The MADCTL
+/// value and the pixel format are mapped onto the remap and color depth
+/// command of the controller.
+pub struct SSD1331;
+
+const SSD1331_SET_COLUMN: u8 = 0x15; // Set Column Address
+const SSD1331_SET_ROW: u8 = 0x75; // Set Row Address
+const SSD1331_CONTRAST_A: u8 = 0x81; // Set Contrast for Color A
+const SSD1331_CONTRAST_B: u8 = 0x82; // Set Contrast for Color B
+const SSD1331_CONTRAST_C: u8 = 0x83; // Set Contrast for Color C
+const SSD1331_MASTER_CURRENT: u8 = 0x87; // Master Current Control
+const SSD1331_PRECHARGE_A: u8 = 0x8A; // Set Second Pre-charge Speed for Color A
+const SSD1331_PRECHARGE_B: u8 = 0x8B; // Set Second Pre-charge Speed for Color B
+const SSD1331_PRECHARGE_C: u8 = 0x8C; // Set Second Pre-charge Speed for Color C
+const SSD1331_SET_REMAP: u8 = 0xA0; // Set Re-map & Data Format
+const SSD1331_START_LINE: u8 = 0xA1; // Set Display Start Line
+const SSD1331_DISPLAY_OFFSET: u8 = 0xA2; // Set Display Offset
+const SSD1331_NORMAL_DISPLAY: u8 = 0xA4; // Normal Display
+const SSD1331_INVERT_DISPLAY: u8 = 0xA7; // Inverse Display
+const SSD1331_MUX_RATIO: u8 = 0xA8; // Set Multiplex Ratio
+const SSD1331_MASTER_CONFIG: u8 = 0xAD; // Set Master Configuration
+const SSD1331_DISPLAY_OFF: u8 = 0xAE; // Display Off (Sleep Mode)
+const SSD1331_DISPLAY_ON: u8 = 0xAF; // Display On in Normal Mode
+const SSD1331_POWER_MODE: u8 = 0xB0; // Power Save Mode
+const SSD1331_PRECHARGE: u8 = 0xB1; // Phase 1 and 2 Period Adjustment
+const SSD1331_CLOCK_DIV: u8 = 0xB3; // Display Clock Divider / Oscillator Frequency
+const SSD1331_PRECHARGE_LEVEL: u8 = 0xBB; // Set Pre-charge Level
+const SSD1331_VCOMH: u8 = 0xBE; // Set VCOMH
+
+// Unlike DCS controllers, the SSD1331 expects the command parameters to be
+// sent with the D/C signal low. Every byte is therefore sent as a command.
+fn write_cmd<DI>(di: &mut DI, bytes: &[u8]) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    for &byte in bytes {
+        di.send_command(byte, &[])?;
+    }
+
+    Ok(())
+}
+
+fn remap(madctl: SetAddressMode, bpp: BitsPerPixel) -> u8 {
+    let madctl = madctl.bits();
+
+    // odd/even split of the COM pins
+    let mut value = 0b0010_0000;
+    value |= match bpp {
+        BitsPerPixel::Eight => 0b0000_0000,
+        _ => 0b0100_0000,
+    };
+    if madctl & (1 << 5) != 0 {
+        value |= 0b0000_0001; // MV: vertical address increment
+    }
+    if madctl & (1 << 6) == 0 {
+        // the SEG pins of the common modules are wired right to left
+        value |= 0b0000_0010;
+    }
+    if madctl & (1 << 3) != 0 {
+        value |= 0b0000_0100; // BGR: C -> B -> A color sequence
+    }
+    if madctl & (1 << 7) == 0 {
+        // the COM pins of the common modules are wired bottom to top
+        value |= 0b0001_0000;
+    }
+
+    value
+}
+
+fn options_write_cmd<DI>(di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    let bpp = BitsPerPixel::from_rgb_color::<Rgb565>();
+    let remap = remap(SetAddressMode::from(options), bpp);
+    write_cmd(di, &[SSD1331_SET_REMAP, remap])?;
+
+    let display_mode = match options.invert_colors {
+        ColorInversion::Normal => SSD1331_NORMAL_DISPLAY,
+        ColorInversion::Inverted => SSD1331_INVERT_DISPLAY,
+    };
+    write_cmd(di, &[display_mode])
+}
+
+impl Model for SSD1331 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (96, 64);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        write_cmd(di, &[SSD1331_DISPLAY_OFF])?;
+        options_write_cmd(di, options)?;
+
+        write_cmd(di, &[SSD1331_START_LINE, 0x00])?;
+        write_cmd(di, &[SSD1331_DISPLAY_OFFSET, 0x00])?;
+        write_cmd(di, &[SSD1331_MUX_RATIO, 0x3F])?; // 64 rows
+        write_cmd(di, &[SSD1331_MASTER_CONFIG, 0x8E])?; // external VCC supply
+        write_cmd(di, &[SSD1331_POWER_MODE, 0x0B])?; // disable power save mode
+        write_cmd(di, &[SSD1331_PRECHARGE, 0x31])?;
+        write_cmd(di, &[SSD1331_CLOCK_DIV, 0xF0])?;
+        write_cmd(di, &[SSD1331_PRECHARGE_A, 0x64])?;
+        write_cmd(di, &[SSD1331_PRECHARGE_B, 0x78])?;
+        write_cmd(di, &[SSD1331_PRECHARGE_C, 0x64])?;
+        write_cmd(di, &[SSD1331_PRECHARGE_LEVEL, 0x3A])?;
+        write_cmd(di, &[SSD1331_VCOMH, 0x3E])?;
+        write_cmd(di, &[SSD1331_MASTER_CURRENT, 0x06])?;
+        write_cmd(di, &[SSD1331_CONTRAST_A, 0x91])?;
+        write_cmd(di, &[SSD1331_CONTRAST_B, 0x50])?;
+        write_cmd(di, &[SSD1331_CONTRAST_C, 0x7D])?;
+
+        write_cmd(di, &[SSD1331_DISPLAY_ON])?;
+        delay.delay_us(120_000);
+
+        Ok(madctl)
+    }
+
+    fn update_address_window<DI>(
+        di: &mut DI,
+        rotation: Rotation,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the address window isn't swapped in vertical address increment mode
+        let (sx, sy, ex, ey) = if rotation.is_vertical() {
+            (sy, sx, ey, ex)
+        } else {
+            (sx, sy, ex, ey)
+        };
+
+        write_cmd(di, &[SSD1331_SET_COLUMN, sx as u8, ex as u8])?;
+        write_cmd(di, &[SSD1331_SET_ROW, sy as u8, ey as u8])
+    }
+
+    fn sleep<DI, DELAY>(di: &mut DI, _delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        write_cmd(di, &[SSD1331_DISPLAY_OFF])
+    }
+
+    fn wake<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        write_cmd(di, &[SSD1331_DISPLAY_ON])?;
+        delay.delay_us(120_000);
+        Ok(())
+    }
+
+    fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        write_cmd(di, &[SSD1331_DISPLAY_ON])
+    }
+
+    fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        write_cmd(di, &[SSD1331_DISPLAY_OFF])
+    }
+
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        // sleep already turns the display off
+        Self::sleep(di, delay)
+    }
+
+    fn write_memory_start<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // data is written to the RAM without a preceding command
+        Ok(())
+    }
+
+    fn write_memory_continue<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // data is written to the RAM without a preceding command
+        Ok(())
+    }
+
+    fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        options_write_cmd(di, options)
+    }
+
+    fn set_pixel_format<DI>(&self, _di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the color depth is written together with the options registers by
+        // `update_options`
+        Ok(())
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the inversion is part of the options registers
+        self.update_options(di, options)
+    }
+
+    fn set_tearing_effect<DI>(
+        _di: &mut DI,
+        _tearing_effect: options::TearingEffect,
+        _options: &ModelOptions,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_gamma_curve<DI>(_di: &mut DI, _curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_partial_area<DI>(_di: &mut DI, _start_row: u16, _end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn exit_partial_mode<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_idle_mode<DI>(_di: &mut DI, _idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_brightness<DI>(_di: &mut DI, _brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_backlight_control<DI>(
+        _di: &mut DI,
+        _mode: options::BacklightMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_adaptive_brightness<DI>(
+        _di: &mut DI,
+        _mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_tear_scanline<DI>(_di: &mut DI, _scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_vertical_scroll_region<DI>(
+        &self,
+        _di: &mut DI,
+        _top_fixed_area: u16,
+        _bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the whole display is always scrolled
+        Ok(())
+    }
+
+    fn set_vertical_scroll_offset<DI>(&self, di: &mut DI, start_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        write_cmd(di, &[SSD1331_START_LINE, start_row as u8])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{ColorOrder, Orientation};
+
+    use super::*;
+
+    #[test]
+    fn remap_matches_address_mode() {
+        let remap_for = |rotation, color_order| {
+            let options = ModelOptions {
+                orientation: Orientation::new().rotate(rotation),
+                color_order,
+                ..ModelOptions::with_all((96, 64), (0, 0))
+            };
+            remap(SetAddressMode::from(&options), BitsPerPixel::Sixteen)
+        };
+
+        assert_eq!(remap_for(Rotation::Deg0, ColorOrder::Rgb), 0b0111_0010);
+        assert_eq!(remap_for(Rotation::Deg90, ColorOrder::Rgb), 0b0111_0001);
+        assert_eq!(remap_for(Rotation::Deg180, ColorOrder::Rgb), 0b0110_0000);
+        assert_eq!(remap_for(Rotation::Deg270, ColorOrder::Bgr), 0b0110_0111);
+        assert_eq!(
+            remap(SetAddressMode::default(), BitsPerPixel::Eight),
+            0b0011_0010
+        );
+    }
+}
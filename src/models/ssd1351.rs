@@ -0,0 +1,341 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{InterfaceExt, SetAddressMode},
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::{self, ColorInversion, ColorOrder, MemoryMapping, ModelOptions, Rotation},
+};
+
+/// SSD1351 OLED display in Rgb565 color mode.
+///
+/// The SSD1351 uses its own command set instead of DCS commands. Orientation,
+/// color order and color inversion are mapped onto the remap and display
+/// mode commands of the controller.
+pub struct SSD1351;
+
+const SSD1351_SET_COLUMN: u8 = 0x15; // Set Column Address
+const SSD1351_SET_ROW: u8 = 0x75; // Set Row Address
+const SSD1351_WRITE_RAM: u8 = 0x5C; // Write RAM
+const SSD1351_SET_REMAP: u8 = 0xA0; // Set Re-map & Color Depth
+const SSD1351_START_LINE: u8 = 0xA1; // Set Display Start Line
+const SSD1351_DISPLAY_OFFSET: u8 = 0xA2; // Set Display Offset
+const SSD1351_NORMAL_DISPLAY: u8 = 0xA6; // Normal Display
+const SSD1351_INVERT_DISPLAY: u8 = 0xA7; // Inverse Display
+const SSD1351_FUNCTION_SELECT: u8 = 0xAB; // Function Selection
+const SSD1351_DISPLAY_OFF: u8 = 0xAE; // Sleep Mode On
+const SSD1351_DISPLAY_ON: u8 = 0xAF; // Sleep Mode Off
+const SSD1351_PRECHARGE: u8 = 0xB1; // Set Phase Length
+const SSD1351_CLOCK_DIV: u8 = 0xB3; // Front Clock Divider / Oscillator Frequency
+const SSD1351_SET_VSL: u8 = 0xB4; // Set Segment Low Voltage
+const SSD1351_SET_GPIO: u8 = 0xB5; // Set GPIO
+const SSD1351_PRECHARGE2: u8 = 0xB6; // Set Second Pre-charge Period
+const SSD1351_VCOMH: u8 = 0xBE; // Set VCOMH Voltage
+const SSD1351_CONTRAST_ABC: u8 = 0xC1; // Set Contrast Current for Color A, B, C
+const SSD1351_CONTRAST_MASTER: u8 = 0xC7; // Master Contrast Current Control
+const SSD1351_MUX_RATIO: u8 = 0xCA; // Set MUX Ratio
+const SSD1351_COMMAND_LOCK: u8 = 0xFD; // Set Command Lock
+
+fn remap(options: &ModelOptions) -> u8 {
+    let mapping = MemoryMapping::from(options.orientation);
+
+    // 65k colors, odd/even split of the COM pins
+    let mut value = 0b0110_0000;
+    if mapping.swap_rows_and_columns {
+        value |= 0b0000_0001; // vertical address increment
+    }
+    if mapping.reverse_columns {
+        value |= 0b0000_0010; // column address 127 is mapped to SEG0
+    }
+    if options.color_order == ColorOrder::Rgb {
+        value |= 0b0000_0100; // C -> B -> A color sequence
+    }
+    if !mapping.reverse_rows {
+        // the COM pins of the common modules are wired bottom to top
+        value |= 0b0001_0000;
+    }
+
+    value
+}
+
+fn options_write_cmd<DI>(di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    di.write_raw(SSD1351_SET_REMAP, &[remap(options)])?;
+
+    let display_mode = match options.invert_colors {
+        ColorInversion::Normal => SSD1351_NORMAL_DISPLAY,
+        ColorInversion::Inverted => SSD1351_INVERT_DISPLAY,
+    };
+    di.write_raw(display_mode, &[])
+}
+
+impl Model for SSD1351 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (128, 128);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        di.write_raw(SSD1351_COMMAND_LOCK, &[0x12])?; // unlock the command interface
+        di.write_raw(SSD1351_COMMAND_LOCK, &[0xB1])?; // unlock A2, B1, B3, BB, BE and C1
+        di.write_raw(SSD1351_DISPLAY_OFF, &[])?;
+
+        di.write_raw(SSD1351_CLOCK_DIV, &[0xF1])?;
+        di.write_raw(SSD1351_MUX_RATIO, &[0x7F])?; // 128 rows
+        di.write_raw(SSD1351_DISPLAY_OFFSET, &[0x00])?;
+        di.write_raw(SSD1351_START_LINE, &[0x00])?;
+        di.write_raw(SSD1351_SET_GPIO, &[0x00])?;
+        di.write_raw(SSD1351_FUNCTION_SELECT, &[0x01])?; // internal VDD regulator
+        di.write_raw(SSD1351_PRECHARGE, &[0x32])?;
+        di.write_raw(SSD1351_VCOMH, &[0x05])?;
+        di.write_raw(SSD1351_CONTRAST_ABC, &[0xC8, 0x80, 0xC8])?;
+        di.write_raw(SSD1351_CONTRAST_MASTER, &[0x0F])?;
+        di.write_raw(SSD1351_SET_VSL, &[0xA0, 0xB5, 0x55])?;
+        di.write_raw(SSD1351_PRECHARGE2, &[0x01])?;
+
+        options_write_cmd(di, options)?;
+
+        di.write_raw(SSD1351_DISPLAY_ON, &[])?;
+        delay.delay_us(120_000);
+
+        Ok(madctl)
+    }
+
+    fn update_address_window<DI>(
+        di: &mut DI,
+        rotation: Rotation,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the address window isn't swapped in vertical address increment mode
+        let (sx, sy, ex, ey) = if rotation.is_vertical() {
+            (sy, sx, ey, ex)
+        } else {
+            (sx, sy, ex, ey)
+        };
+
+        di.write_raw(SSD1351_SET_COLUMN, &[sx as u8, ex as u8])?;
+        di.write_raw(SSD1351_SET_ROW, &[sy as u8, ey as u8])
+    }
+
+    fn sleep<DI, DELAY>(di: &mut DI, _delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_raw(SSD1351_DISPLAY_OFF, &[])
+    }
+
+    fn wake<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        di.write_raw(SSD1351_DISPLAY_ON, &[])?;
+        delay.delay_us(120_000);
+        Ok(())
+    }
+
+    fn display_on<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(SSD1351_DISPLAY_ON, &[])
+    }
+
+    fn display_off<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(SSD1351_DISPLAY_OFF, &[])
+    }
+
+    fn shutdown<DI, DELAY>(di: &mut DI, delay: &mut DELAY) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DELAY: DelayNs,
+    {
+        // sleep already turns the display off
+        Self::sleep(di, delay)
+    }
+
+    fn write_memory_start<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(SSD1351_WRITE_RAM, &[])
+    }
+
+    fn write_memory_continue<DI>(di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The address counter isn't reset by the memory write command
+        Self::write_memory_start(di)
+    }
+
+    fn update_options<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        options_write_cmd(di, options)
+    }
+
+    fn set_pixel_format<DI>(&self, _di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the color depth is written together with the options registers by
+        // `update_options`
+        Ok(())
+    }
+
+    fn set_invert_colors<DI>(&self, di: &mut DI, options: &ModelOptions) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the inversion is part of the options registers
+        self.update_options(di, options)
+    }
+
+    fn set_tearing_effect<DI>(
+        _di: &mut DI,
+        _tearing_effect: options::TearingEffect,
+        _options: &ModelOptions,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_gamma_curve<DI>(_di: &mut DI, _curve: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_partial_area<DI>(_di: &mut DI, _start_row: u16, _end_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn exit_partial_mode<DI>(_di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_idle_mode<DI>(_di: &mut DI, _idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_brightness<DI>(_di: &mut DI, _brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_backlight_control<DI>(
+        _di: &mut DI,
+        _mode: options::BacklightMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_adaptive_brightness<DI>(
+        _di: &mut DI,
+        _mode: options::AdaptiveBrightness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_tear_scanline<DI>(_di: &mut DI, _scanline: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+
+    fn set_vertical_scroll_region<DI>(
+        &self,
+        _di: &mut DI,
+        _top_fixed_area: u16,
+        _bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // the whole display is always scrolled
+        Ok(())
+    }
+
+    fn set_vertical_scroll_offset<DI>(&self, di: &mut DI, start_row: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(SSD1351_START_LINE, &[start_row as u8])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{Orientation, Rotation};
+
+    use super::*;
+
+    #[test]
+    fn remap_matches_orientation() {
+        let remap_for = |rotation| {
+            remap(&ModelOptions {
+                orientation: Orientation::new().rotate(rotation),
+                ..ModelOptions::with_all((128, 128), (0, 0))
+            })
+        };
+
+        assert_eq!(remap_for(Rotation::Deg0), 0b0111_0100);
+        assert_eq!(remap_for(Rotation::Deg90), 0b0111_0111);
+        assert_eq!(remap_for(Rotation::Deg180), 0b0110_0110);
+        assert_eq!(remap_for(Rotation::Deg270), 0b0110_0101);
+    }
+}
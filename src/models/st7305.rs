@@ -0,0 +1,312 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::{BinaryColor, Rgb565},
+    Pixel,
+};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    dcs::{
+        ExitSleepMode, InterfaceExt, SequenceEntry, SetAddressMode, SetDisplayOn, SetInvertMode,
+        WriteMemoryStart,
+    },
+    dcs_seq,
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+    Display,
+};
+
+/// ST7305 reflective LCD in monochrome mode.
+///
+/// The ST7305 is used in low power reflective 2.9" 168x384 panels. The
+/// controller uses a packed pixel format, each byte contains the pixels of a
+/// block of 4 columns and 2 rows, and the column addresses count in units of
+/// 12 pixels. This format can't be produced by the regular drawing methods of
+/// [`Display`], use a [`St7305Framebuffer`] to draw to the display instead.
+///
+/// The `ColorFormat` of this model is only used to satisfy the [`Model`]
+/// trait.
+pub struct ST7305;
+
+/// First column address of the panel.
+const COLUMN_OFFSET: u16 = 0x17;
+/// Number of pixels per column address.
+const PIXELS_PER_COLUMN: u16 = 12;
+/// Number of pixels per row address.
+const PIXELS_PER_ROW: u16 = 2;
+
+// Power, gate and source settings of the 2.9" 168x384 panels.
+const INIT: &[SequenceEntry] = dcs_seq![
+    cmd(0xD6, 0x17, 0x02),             // NVM load control
+    cmd(0xD1, 0x01),                   // booster enable
+    cmd(0xC0, 0x12, 0x0A),             // gate voltage
+    cmd(0xC1, 0x73, 0x3E, 0x3C, 0x3C), // VSHP
+    cmd(0xC2, 0x00, 0x21, 0x23, 0x23), // VSLP
+    cmd(0xC4, 0x32, 0x5C, 0x5A, 0x5A), // VSHN
+    cmd(0xC5, 0x32, 0x35, 0x37, 0x37), // VSLN
+    cmd(0xD8, 0x80, 0xE9),             // OSC setting
+    cmd(0xB2, 0x12),                   // frame rate
+    // gate EQ, high and low power mode
+    cmd(0xB3, 0xE5, 0xF6, 0x17, 0x77, 0x77, 0x77, 0x77, 0x77, 0x77, 0x71),
+    cmd(0xB4, 0x05, 0x46, 0x77, 0x77, 0x77, 0x77, 0x76, 0x45),
+    cmd(0x62, 0x32, 0x03, 0x1F), // gate timing
+    cmd(0xB7, 0x13),             // source EQ
+    cmd(0xB0, 0x60),             // gate lines: 384
+];
+
+impl Model for ST7305 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (168, 384);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        di.write_sequence(INIT, delay)?;
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        di.write_raw(0xC9, &[0x00])?; // source voltage select
+        di.write_command(madctl)?;
+        di.write_raw(0x3A, &[0x11])?; // data format: packed pixels
+        di.write_raw(0xB9, &[0x20])?; // monochrome mode
+        di.write_raw(0xB8, &[0x29])?; // panel setting
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+        di.write_raw(0xD0, &[0xFF])?; // auto power down
+        di.write_raw(0x38, &[])?; // high power mode
+
+        di.write_command(SetDisplayOn)?;
+
+        Ok(madctl)
+    }
+
+    fn set_pixel_format<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(0x3A, &[0x11]) // data format: packed pixels
+    }
+}
+
+/// Packed monochrome framebuffer for the [`ST7305`].
+///
+/// The framebuffer can be any buffer which implements `AsRef<[u8]>` and
+/// `AsMut<[u8]>` and must contain at least `width * height / 8` bytes. The
+/// width must be a multiple of 12 and the height a multiple of 2.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{
+///     pixelcolor::BinaryColor,
+///     prelude::*,
+///     primitives::{Circle, PrimitiveStyle},
+/// };
+/// use mipidsi::{
+///     models::{St7305Framebuffer, ST7305},
+///     Builder,
+/// };
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut display = Builder::new(ST7305, di).init(&mut delay).unwrap();
+///
+/// let mut framebuffer = St7305Framebuffer::new([0u8; 168 * 384 / 8], 168, 384);
+/// Circle::new(Point::new(10, 10), 50)
+///     .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+///     .draw(&mut framebuffer)
+///     .unwrap();
+///
+/// framebuffer.flush(&mut display).unwrap();
+/// ```
+pub struct St7305Framebuffer<B> {
+    buffer: B,
+    width: u16,
+    height: u16,
+}
+
+impl<B> St7305Framebuffer<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Creates a new framebuffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is too small or the size isn't a multiple of the
+    /// 12x2 pixel address units of the controller.
+    pub fn new(buffer: B, width: u16, height: u16) -> Self {
+        assert!(Self::is_valid(&buffer, width, height));
+
+        Self {
+            buffer,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a new framebuffer.
+    ///
+    /// Returns the buffer if it is too small or the size isn't a multiple of
+    /// the 12x2 pixel address units of the controller.
+    pub fn try_new(buffer: B, width: u16, height: u16) -> Result<Self, B> {
+        if !Self::is_valid(&buffer, width, height) {
+            return Err(buffer);
+        }
+
+        Ok(Self {
+            buffer,
+            width,
+            height,
+        })
+    }
+
+    // Checks the framebuffer size and the buffer length.
+    fn is_valid(buffer: &B, width: u16, height: u16) -> bool {
+        width % PIXELS_PER_COLUMN == 0
+            && height % PIXELS_PER_ROW == 0
+            && buffer.as_ref().len() >= usize::from(width) * usize::from(height) / 8
+    }
+
+    /// Returns the packed framebuffer content.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_ref()[..usize::from(self.width) * usize::from(self.height) / 8]
+    }
+
+    /// Releases the buffer.
+    pub fn release(self) -> B {
+        self.buffer
+    }
+
+    /// Returns the byte index and bit mask of a pixel.
+    fn position(&self, x: u16, y: u16) -> (usize, u8) {
+        let index = usize::from(y / 2) * usize::from(self.width / 4) + usize::from(x / 4);
+        let bit = 7 - ((x % 4) * 2 + y % 2);
+        (index, 1 << bit)
+    }
+
+    /// Sends the framebuffer to the display.
+    pub fn flush<DI, RST>(&self, display: &mut Display<DI, ST7305, RST>) -> Result<(), DI::Error>
+    where
+        DI: Interface<Word = u8>,
+        RST: OutputPin,
+    {
+        let di = &mut display.di;
+
+        let ex = COLUMN_OFFSET + self.width / PIXELS_PER_COLUMN - 1;
+        let ey = self.height / PIXELS_PER_ROW - 1;
+        di.write_raw(0x2A, &[COLUMN_OFFSET as u8, ex as u8])?;
+        di.write_raw(0x2B, &[0x00, ey as u8])?;
+        di.write_command(WriteMemoryStart)?;
+
+        di.send_pixels(self.as_bytes().iter().map(|byte| [*byte]))
+    }
+}
+
+impl<B> OriginDimensions for St7305Framebuffer<B> {
+    fn size(&self) -> Size {
+        Size::new(self.width.into(), self.height.into())
+    }
+}
+
+impl<B> DrawTarget for St7305Framebuffer<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let (Ok(x), Ok(y)) = (u16::try_from(point.x), u16::try_from(point.y)) else {
+                continue;
+            };
+            if x >= self.width || y >= self.height {
+                continue;
+            }
+
+            let (index, mask) = self.position(x, y);
+            let byte = &mut self.buffer.as_mut()[index];
+            if color.is_on() {
+                *byte |= mask;
+            } else {
+                *byte &= !mask;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let len = usize::from(self.width) * usize::from(self.height) / 8;
+        let value = if color.is_on() { 0xFF } else { 0x00 };
+        self.buffer.as_mut()[..len].fill(value);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::geometry::Point;
+
+    use super::*;
+
+    #[test]
+    fn try_new_checks_size() {
+        assert!(St7305Framebuffer::try_new([0u8; 24 * 4 / 8], 24, 4).is_ok());
+        assert!(St7305Framebuffer::try_new([0u8; 24 * 4 / 8 - 1], 24, 4).is_err());
+        assert!(St7305Framebuffer::try_new([0u8; 24 * 4 / 8], 20, 4).is_err());
+    }
+
+    #[test]
+    fn packs_4x2_blocks() {
+        let mut fb = St7305Framebuffer::new([0u8; 24 * 4 / 8], 24, 4);
+
+        fb.draw_iter([
+            Pixel(Point::new(0, 0), BinaryColor::On),
+            Pixel(Point::new(0, 1), BinaryColor::On),
+            Pixel(Point::new(3, 1), BinaryColor::On),
+            Pixel(Point::new(4, 0), BinaryColor::On),
+            Pixel(Point::new(23, 3), BinaryColor::On),
+            Pixel(Point::new(24, 0), BinaryColor::On),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            fb.as_bytes(),
+            &[
+                0b1100_0001,
+                0b1000_0000,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0b0000_0001
+            ]
+        );
+
+        fb.draw_iter([Pixel(Point::new(0, 1), BinaryColor::Off)])
+            .unwrap();
+        assert_eq!(fb.as_bytes()[0], 0b1000_0001);
+    }
+}
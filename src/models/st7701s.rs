@@ -0,0 +1,119 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SequenceEntry,
+        SetAddressMode, SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
+    dcs_seq,
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+};
+
+/// ST7701S display in Rgb565 color mode.
+///
+/// The ST7701S is used in 480x480 square panels. The controller is
+/// configured over SPI, but the pixel data is usually sent over a separate
+/// RGB (DPI) interface, which isn't handled by this crate. In this case the
+/// display can only be used to initialize the controller and to change the
+/// orientation, sleep and tearing effect settings.
+///
+/// The color format selects the width of the RGB interface.
+pub struct ST7701S;
+
+// Command bank 0: display line, porch, inversion and gamma settings.
+const BK0: &[SequenceEntry] = dcs_seq![
+    cmd(0xFF, 0x77, 0x01, 0x00, 0x00, 0x10), // CND2BKxSEL: select bank 0
+    cmd(0xC0, 0x3B, 0x00),                   // LNESET: 480 lines
+    cmd(0xC1, 0x0D, 0x02),                   // PORCTRL: vertical back and front porch
+    cmd(0xC2, 0x31, 0x05),                   // INVSEL: column inversion
+    cmd(0xCD, 0x08),                         // COLCTRL
+    // PVGAMCTRL: positive gamma
+    cmd(
+        0xB0, 0x00, 0x11, 0x18, 0x0E, 0x11, 0x06, 0x07, 0x08, 0x07, 0x22, 0x04, 0x12, 0x0F, 0xAA,
+        0x31, 0x18
+    ),
+    // NVGAMCTRL: negative gamma
+    cmd(
+        0xB1, 0x00, 0x11, 0x19, 0x0E, 0x12, 0x07, 0x08, 0x08, 0x08, 0x22, 0x04, 0x11, 0x11, 0xA9,
+        0x32, 0x18
+    ),
+];
+
+// Command bank 1: power and gate driver (GIP) timing settings.
+const BK1: &[SequenceEntry] = dcs_seq![
+    cmd(0xFF, 0x77, 0x01, 0x00, 0x00, 0x11), // CND2BKxSEL: select bank 1
+    cmd(0xB0, 0x60),                         // VRHS
+    cmd(0xB1, 0x32),                         // VCOMS
+    cmd(0xB2, 0x07),                         // VGHSS
+    cmd(0xB3, 0x80),                         // TESTCMD
+    cmd(0xB5, 0x49),                         // VGLS
+    cmd(0xB7, 0x85),                         // PWCTRL1
+    cmd(0xB8, 0x21),                         // PWCTRL2
+    cmd(0xC1, 0x78),                         // SPD1
+    cmd(0xC2, 0x78),                         // SPD2
+    delay_ms(20),
+    cmd(0xE0, 0x00, 0x1B, 0x02),
+    cmd(0xE1, 0x08, 0xA0, 0x00, 0x00, 0x07, 0xA0, 0x00, 0x00, 0x00, 0x44, 0x44),
+    cmd(0xE2, 0x11, 0x11, 0x44, 0x44, 0xED, 0xA0, 0x00, 0x00, 0xEC, 0xA0, 0x00, 0x00),
+    cmd(0xE3, 0x00, 0x00, 0x11, 0x11),
+    cmd(0xE4, 0x44, 0x44),
+    cmd(
+        0xE5, 0x0A, 0xE9, 0xD8, 0xA0, 0x0C, 0xEB, 0xD8, 0xA0, 0x0E, 0xED, 0xD8, 0xA0, 0x10, 0xEF,
+        0xD8, 0xA0
+    ),
+    cmd(0xE6, 0x00, 0x00, 0x11, 0x11),
+    cmd(0xE7, 0x44, 0x44),
+    cmd(
+        0xE8, 0x09, 0xE8, 0xD8, 0xA0, 0x0B, 0xEA, 0xD8, 0xA0, 0x0D, 0xEC, 0xD8, 0xA0, 0x0F, 0xEE,
+        0xD8, 0xA0
+    ),
+    cmd(0xEB, 0x02, 0x00, 0xE4, 0xE4, 0x88, 0x00, 0x40),
+    cmd(0xEC, 0x3C, 0x00),
+    cmd(
+        0xED, 0xAB, 0x89, 0x76, 0x54, 0x02, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x20, 0x45, 0x67,
+        0x98, 0xBA
+    ),
+    cmd(0xFF, 0x77, 0x01, 0x00, 0x00, 0x00), // CND2BKxSEL: back to the user command set
+];
+
+impl Model for ST7701S {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (480, 480);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        di.write_sequence(BK0, delay)?;
+        di.write_sequence(BK1, delay)?;
+
+        di.write_command(madctl)?;
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        di.write_command(EnterNormalMode)?;
+        di.write_command(SetDisplayOn)?;
+
+        // DISPON requires some time otherwise we risk SPI data issues
+        delay.delay_us(120_000);
+
+        Ok(madctl)
+    }
+}
@@ -8,16 +8,40 @@ use crate::{
     },
     interface::{Interface, InterfaceKind},
     models::{Model, ModelInitError},
-    options::ModelOptions,
+    options::{ColorInversion, ColorOrder, ModelOptions},
     ConfigurationError,
 };
 
 /// ST7735s display in Rgb565 color mode.
 pub struct ST7735s;
 
+/// ST7735s display in Rgb565 color mode, preconfigured for 0.96" 80x160 panels.
+///
+/// The 80x160 panels are mounted with an offset of (26, 1) in the 132x162
+/// framebuffer, use BGR subpixels and need color inversion. The offset is
+/// adjusted automatically for all orientations.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::prelude::*;
+/// use mipidsi::{models::ST7735sMini, options::Rotation, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let display = Builder::new(ST7735sMini, di)
+///     .orientation(mipidsi::options::Orientation::new().rotate(Rotation::Deg90))
+///     .init(&mut delay)
+///     .unwrap();
+///
+/// assert_eq!(display.size(), Size::new(160, 80));
+/// ```
+pub struct ST7735sMini;
+
 impl Model for ST7735s {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (132, 162);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -29,54 +53,127 @@ impl Model for ST7735s {
         DELAY: DelayNs,
         DI: Interface,
     {
-        if !matches!(
-            DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
-        ) {
-            return Err(ModelInitError::InvalidConfiguration(
-                ConfigurationError::UnsupportedInterface,
-            ));
-        }
-
-        let madctl = SetAddressMode::from(options);
-
-        delay.delay_us(200_000);
-
-        di.write_command(ExitSleepMode)?; // turn off sleep
-        delay.delay_us(120_000);
-
-        di.write_command(SetInvertMode::new(options.invert_colors))?; // set color inversion
-        di.write_raw(0xB1, &[0x05, 0x3A, 0x3A])?; // set frame rate
-        di.write_raw(0xB2, &[0x05, 0x3A, 0x3A])?; // set frame rate
-        di.write_raw(0xB3, &[0x05, 0x3A, 0x3A, 0x05, 0x3A, 0x3A])?; // set frame rate
-        di.write_raw(0xB4, &[0b0000_0011])?; // set inversion control
-        di.write_raw(0xC0, &[0x62, 0x02, 0x04])?; // set power control 1
-        di.write_raw(0xC1, &[0xC0])?; // set power control 2
-        di.write_raw(0xC2, &[0x0D, 0x00])?; // set power control 3
-        di.write_raw(0xC3, &[0x8D, 0x6A])?; // set power control 4
-        di.write_raw(0xC4, &[0x8D, 0xEE])?; // set power control 5
-        di.write_raw(0xC5, &[0x0E])?; // set VCOM control 1
-        di.write_raw(
-            0xE0,
-            &[
-                0x10, 0x0E, 0x02, 0x03, 0x0E, 0x07, 0x02, 0x07, 0x0A, 0x12, 0x27, 0x37, 0x00, 0x0D,
-                0x0E, 0x10,
-            ],
-        )?; // set GAMMA +Polarity characteristics
-        di.write_raw(
-            0xE1,
-            &[
-                0x10, 0x0E, 0x03, 0x03, 0x0F, 0x06, 0x02, 0x08, 0x0A, 0x13, 0x26, 0x36, 0x00, 0x0D,
-                0x0E, 0x10,
-            ],
-        )?; // set GAMMA -Polarity characteristics
-
-        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        di.write_command(SetPixelFormat::new(pf))?; // set interface pixel format, 16bit pixel into frame memory
-
-        di.write_command(madctl)?; // set memory data access control, Top -> Bottom, RGB, Left -> Right
-        di.write_command(SetDisplayOn)?; // turn on display
-
-        Ok(madctl)
+        init_st7735s(di, delay, options)
+    }
+
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_frame_rate_st7735s(di, frame_rate)
+    }
+}
+
+impl Model for ST7735sMini {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (132, 162);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
+
+    fn default_options(&self) -> ModelOptions {
+        let mut options = ModelOptions::with_all((80, 160), (26, 1));
+        options.color_order = ColorOrder::Bgr;
+        options.invert_colors = ColorInversion::Inverted;
+        options
     }
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        init_st7735s(di, delay, options)
+    }
+
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_frame_rate_st7735s(di, frame_rate)
+    }
+}
+
+fn init_st7735s<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    if !matches!(
+        DI::KIND,
+        InterfaceKind::Serial4Line
+            | InterfaceKind::Serial3Line
+            | InterfaceKind::Parallel8Bit
+            | InterfaceKind::Parallel16Bit
+    ) {
+        return Err(ModelInitError::InvalidConfiguration(
+            ConfigurationError::UnsupportedInterface,
+        ));
+    }
+
+    let madctl = SetAddressMode::from(options);
+
+    delay.delay_us(200_000);
+
+    di.write_command(ExitSleepMode)?; // turn off sleep
+    delay.delay_us(120_000);
+
+    di.write_command(SetInvertMode::new(options.invert_colors))?; // set color inversion
+    di.write_raw(0xB1, &[0x05, 0x3A, 0x3A])?; // set frame rate
+    di.write_raw(0xB2, &[0x05, 0x3A, 0x3A])?; // set frame rate
+    di.write_raw(0xB3, &[0x05, 0x3A, 0x3A, 0x05, 0x3A, 0x3A])?; // set frame rate
+    di.write_raw(0xB4, &[0b0000_0011])?; // set inversion control
+    di.write_raw(0xC0, &[0x62, 0x02, 0x04])?; // set power control 1
+    di.write_raw(0xC1, &[0xC0])?; // set power control 2
+    di.write_raw(0xC2, &[0x0D, 0x00])?; // set power control 3
+    di.write_raw(0xC3, &[0x8D, 0x6A])?; // set power control 4
+    di.write_raw(0xC4, &[0x8D, 0xEE])?; // set power control 5
+    di.write_raw(0xC5, &[0x0E])?; // set VCOM control 1
+    di.write_raw(
+        0xE0,
+        &[
+            0x10, 0x0E, 0x02, 0x03, 0x0E, 0x07, 0x02, 0x07, 0x0A, 0x12, 0x27, 0x37, 0x00, 0x0D,
+            0x0E, 0x10,
+        ],
+    )?; // set GAMMA +Polarity characteristics
+    di.write_raw(
+        0xE1,
+        &[
+            0x10, 0x0E, 0x03, 0x03, 0x0F, 0x06, 0x02, 0x08, 0x0A, 0x13, 0x26, 0x36, 0x00, 0x0D,
+            0x0E, 0x10,
+        ],
+    )?; // set GAMMA -Polarity characteristics
+
+    let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Rgb565>());
+    di.write_command(SetPixelFormat::new(pf))?; // set interface pixel format, 16bit pixel into frame memory
+
+    di.write_command(madctl)?; // set memory data access control, Top -> Bottom, RGB, Left -> Right
+    di.write_command(SetDisplayOn)?; // turn on display
+
+    Ok(madctl)
+}
+
+/// Writes `FRMCTR1` with the frame rate closest to `frame_rate`.
+///
+/// frame rate = fosc / ((RTNA * 2 + 40) * (LINE + FPA + BPA + 2)), with
+/// fosc = 850 kHz and the porch settings used by the init sequence.
+fn set_frame_rate_st7735s<DI>(di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    const FOSC: u32 = 850_000;
+    const PORCH: u8 = 0x3A;
+    const LINES: u32 = 160 + 2 * PORCH as u32 + 2;
+
+    let clocks = FOSC / (LINES * u32::from(frame_rate.max(1)));
+    let rtna = clocks.saturating_sub(40).div_ceil(2).min(15);
+
+    di.write_raw(0xB1, &[rtna as u8, PORCH, PORCH]) // set frame rate in normal mode
 }
@@ -7,17 +7,71 @@ use crate::{
         SetDisplayOn, SetInvertMode, SetPixelFormat,
     },
     interface::{Interface, InterfaceKind},
-    models::{Model, ModelInitError},
-    options::ModelOptions,
+    models::{Model, ModelConfig, ModelInitError},
+    options::{ColorInversion, ModelOptions},
     ConfigurationError,
 };
 
 /// ST7789 display in Rgb565 color mode.
+///
+/// Use [`ST7789Panel`] for panels which are smaller than the 240x320
+/// framebuffer.
 pub struct ST7789;
 
+/// ST7789 display in Rgb565 color mode with a known panel size.
+///
+/// Smaller ST7789 panels only use a part of the 240x320 framebuffer and need
+/// an offset, which also depends on the orientation. Each variant presets the
+/// display size, offset and color inversion of the panel, the offset is
+/// adjusted automatically for all orientations.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::prelude::*;
+/// use mipidsi::{
+///     models::ST7789Panel,
+///     options::{Orientation, Rotation},
+///     Builder,
+/// };
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let display = Builder::new(ST7789Panel::Size135x240, di)
+///     .orientation(Orientation::new().rotate(Rotation::Deg90))
+///     .init(&mut delay)
+///     .unwrap();
+///
+/// assert_eq!(display.size(), Size::new(240, 135));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ST7789Panel {
+    /// 1.3" and 1.54" 240x240 panels.
+    Size240x240,
+    /// 1.69" 240x280 panels with rounded corners.
+    Size240x280,
+    /// 1.47" 172x320 panels.
+    Size172x320,
+    /// 1.14" 135x240 panels, e.g. on the TTGO T-Display.
+    Size135x240,
+}
+
+impl ST7789Panel {
+    /// Returns the display size and offset in the default orientation.
+    pub const fn size_and_offset(self) -> ((u16, u16), (u16, u16)) {
+        match self {
+            Self::Size240x240 => ((240, 240), (0, 0)),
+            Self::Size240x280 => ((240, 280), (0, 20)),
+            Self::Size172x320 => ((172, 320), (34, 0)),
+            Self::Size135x240 => ((135, 240), (52, 40)),
+        }
+    }
+}
+
 impl Model for ST7789 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -29,37 +83,189 @@ impl Model for ST7789 {
         DELAY: DelayNs,
         DI: Interface,
     {
-        if !matches!(
-            DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
-        ) {
-            return Err(ModelInitError::InvalidConfiguration(
-                ConfigurationError::UnsupportedInterface,
-            ));
-        }
+        init_st7789(di, delay, options)
+    }
 
-        let madctl = SetAddressMode::from(options);
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_frame_rate_st7789(di, frame_rate)
+    }
+}
 
-        delay.delay_us(150_000);
+impl Model for ST7789Panel {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
-        di.write_command(ExitSleepMode)?;
-        delay.delay_us(10_000);
+    fn default_options(&self) -> ModelOptions {
+        let (size, offset) = self.size_and_offset();
+        let mut options = ModelOptions::with_all(size, offset);
+        // all common IPS panels need color inversion
+        options.invert_colors = ColorInversion::Inverted;
+        options
+    }
 
-        // set hw scroll area based on framebuffer size
-        di.write_command(madctl)?;
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        init_st7789(di, delay, options)
+    }
 
-        di.write_command(SetInvertMode::new(options.invert_colors))?;
+    fn set_frame_rate<DI>(&self, di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_frame_rate_st7789(di, frame_rate)
+    }
+}
 
-        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        di.write_command(SetPixelFormat::new(pf))?;
-        delay.delay_us(10_000);
-        di.write_command(EnterNormalMode)?;
-        delay.delay_us(10_000);
-        di.write_command(SetDisplayOn)?;
+/// Power and VCOM settings of the ST7789.
+///
+/// Settings which are `None` keep the controller defaults. The settings are
+/// applied by using the
+/// [`Configured`](crate::models::Configured) model.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::models::{Configured, St7789Config, ST7789};
+///
+/// let model = Configured::new(
+///     ST7789,
+///     St7789Config {
+///         vcom: Some(0x1A),
+///         ..St7789Config::default()
+///     },
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct St7789Config {
+    /// VCOM setting (`VCOMS`, `BBh`).
+    pub vcom: Option<u8>,
+    /// VGH and VGL voltages (`GCTRL`, `B7h`).
+    pub gate_control: Option<u8>,
+    /// VRH setting (`VRHS`, `C3h`).
+    pub vrh: Option<u8>,
+    /// VDV setting (`VDVS`, `C4h`).
+    pub vdv: Option<u8>,
+    /// AVDD, AVCL and VDS voltages (`PWCTRL1`, `D0h`).
+    pub power_control_1: Option<u8>,
+}
 
-        // DISPON requires some time otherwise we risk SPI data issues
-        delay.delay_us(120_000);
+impl ModelConfig<ST7789> for St7789Config {
+    fn write<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        write_config_st7789(di, self)
+    }
+}
+
+impl ModelConfig<ST7789Panel> for St7789Config {
+    fn write<DI>(&self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        write_config_st7789(di, self)
+    }
+}
+
+fn write_config_st7789<DI>(di: &mut DI, config: &St7789Config) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    if let Some(gate_control) = config.gate_control {
+        di.write_raw(0xB7, &[gate_control])?; // set gate control
+    }
+    if let Some(vcom) = config.vcom {
+        di.write_raw(0xBB, &[vcom])?; // set VCOM
+    }
+    if config.vrh.is_some() || config.vdv.is_some() {
+        di.write_raw(0xC2, &[0x01])?; // use VRH and VDV from the command values
+    }
+    if let Some(vrh) = config.vrh {
+        di.write_raw(0xC3, &[vrh])?; // set VRH
+    }
+    if let Some(vdv) = config.vdv {
+        di.write_raw(0xC4, &[vdv])?; // set VDV
+    }
+    if let Some(power_control_1) = config.power_control_1 {
+        di.write_raw(0xD0, &[0xA4, power_control_1])?; // set power control 1
+    }
+
+    Ok(())
+}
 
-        Ok(madctl)
+fn init_st7789<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    if !matches!(
+        DI::KIND,
+        InterfaceKind::Serial4Line
+            | InterfaceKind::Serial3Line
+            | InterfaceKind::Parallel8Bit
+            | InterfaceKind::Parallel16Bit
+    ) {
+        return Err(ModelInitError::InvalidConfiguration(
+            ConfigurationError::UnsupportedInterface,
+        ));
     }
+
+    let madctl = SetAddressMode::from(options);
+
+    delay.delay_us(150_000);
+
+    di.write_command(ExitSleepMode)?;
+    delay.delay_us(10_000);
+
+    // set hw scroll area based on framebuffer size
+    di.write_command(madctl)?;
+
+    di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+    let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Rgb565>());
+    di.write_command(SetPixelFormat::new(pf))?;
+    delay.delay_us(10_000);
+    di.write_command(EnterNormalMode)?;
+    delay.delay_us(10_000);
+    di.write_command(SetDisplayOn)?;
+
+    // DISPON requires some time otherwise we risk SPI data issues
+    delay.delay_us(120_000);
+
+    Ok(madctl)
+}
+
+/// Writes `PORCTRL` and `FRCTRL2` with the frame rate closest to `frame_rate`.
+///
+/// frame rate = 10 MHz / ((320 + FPA + BPA) * (250 + RTNA * 16)), with the
+/// default front and back porch of 12 lines each.
+fn set_frame_rate_st7789<DI>(di: &mut DI, frame_rate: u16) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    const FOSC: u32 = 10_000_000;
+    const PORCH: u8 = 0x0C;
+    const LINES: u32 = 320 + 2 * PORCH as u32;
+
+    let clocks = FOSC / (LINES * u32::from(frame_rate.max(1)));
+    let rtna = ((clocks.saturating_sub(250) + 8) / 16).min(31);
+
+    di.write_raw(0xB2, &[PORCH, PORCH, 0x00, 0x33, 0x33])?; // set porch control
+    di.write_raw(0xC6, &[rtna as u8]) // set frame rate control in normal mode
 }
@@ -1,8 +1,11 @@
-use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
 use embedded_hal::delay::DelayNs;
 
 use crate::{
-    dcs::SetAddressMode,
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetDisplayOn, SetInvertMode, SetPixelFormat,
+    },
     interface::{Interface, InterfaceKind},
     models::{Model, ModelInitError},
     options::ModelOptions,
@@ -12,9 +15,16 @@ use crate::{
 /// ST7796 display in Rgb565 color mode.
 pub struct ST7796;
 
+/// ST7796S display in Rgb565 color mode.
+pub struct ST7796SRgb565;
+
+/// ST7796S display in Rgb666 color mode.
+pub struct ST7796SRgb666;
+
 impl Model for ST7796 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
 
     fn init<DELAY, DI>(
         &mut self,
@@ -38,3 +48,114 @@ impl Model for ST7796 {
         super::ST7789.init(di, delay, options)
     }
 }
+
+impl Model for ST7796SRgb565 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        init_st7796s(di, delay, options, pf)
+    }
+}
+
+impl Model for ST7796SRgb666 {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLES: Option<(u8, u8)> = Some((0xE0, 0xE1));
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        init_st7796s(di, delay, options, pf)
+    }
+}
+
+// Init sequence for the ST7796S, which requires unlocking the manufacturer
+// command set to configure the power and gamma registers.
+fn init_st7796s<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    pixel_format: PixelFormat,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    if !matches!(
+        DI::KIND,
+        InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+    ) {
+        return Err(ModelInitError::InvalidConfiguration(
+            ConfigurationError::UnsupportedInterface,
+        ));
+    }
+
+    let madctl = SetAddressMode::from(options);
+
+    delay.delay_us(120_000);
+
+    di.write_command(ExitSleepMode)?;
+    delay.delay_us(120_000);
+
+    di.write_raw(0xF0, &[0xC3])?; // CSCON: enable command 2 part I
+    di.write_raw(0xF0, &[0x96])?; // CSCON: enable command 2 part II
+
+    di.write_command(madctl)?;
+    di.write_command(SetPixelFormat::new(pixel_format))?;
+
+    di.write_raw(0xB4, &[0x01])?; // DIC: 1-dot inversion
+    di.write_raw(0xB6, &[0x80, 0x02, 0x3B])?; // DFC
+    di.write_raw(0xE8, &[0x40, 0x8A, 0x00, 0x00, 0x29, 0x19, 0xA5, 0x33])?; // DOCA
+    di.write_raw(0xC1, &[0x06])?; // PWR2
+    di.write_raw(0xC2, &[0xA7])?; // PWR3
+    di.write_raw(0xC5, &[0x18])?; // VCMPCTL
+    delay.delay_us(120_000);
+
+    // PGC: positive gamma control
+    di.write_raw(
+        0xE0,
+        &[
+            0xF0, 0x09, 0x0B, 0x06, 0x04, 0x15, 0x2F, 0x54, 0x42, 0x3C, 0x17, 0x14, 0x18, 0x1B,
+        ],
+    )?;
+    // NGC: negative gamma control
+    di.write_raw(
+        0xE1,
+        &[
+            0xE0, 0x09, 0x0B, 0x06, 0x04, 0x03, 0x2B, 0x43, 0x42, 0x3B, 0x16, 0x14, 0x17, 0x1B,
+        ],
+    )?;
+    delay.delay_us(120_000);
+
+    di.write_raw(0xF0, &[0x3C])?; // CSCON: disable command 2 part I
+    di.write_raw(0xF0, &[0x69])?; // CSCON: disable command 2 part II
+
+    di.write_command(SetInvertMode::new(options.invert_colors))?;
+    di.write_command(EnterNormalMode)?;
+    di.write_command(SetDisplayOn)?;
+
+    // DISPON requires some time otherwise we risk SPI data issues
+    delay.delay_us(120_000);
+
+    Ok(madctl)
+}
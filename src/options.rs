@@ -1,6 +1,6 @@
 //! [ModelOptions] and other helper types.
 
-use crate::models::Model;
+use crate::{dcs::SequenceEntry, models::Model};
 
 mod orientation;
 pub(crate) use orientation::MemoryMapping;
@@ -23,19 +23,20 @@ pub struct ModelOptions {
     pub display_size: (u16, u16),
     /// Display offset (x, y) for given display.
     pub display_offset: (u16, u16),
+    /// Extra delays after commands sent during initialization.
+    pub command_delays: CommandDelays,
+    /// Overrides for the model's built-in init delays.
+    pub init_timings: InitTimings,
+    /// Additional commands which are sent after the model's init sequence.
+    pub init_sequence: &'static [SequenceEntry],
+    /// Tearing effect output which is enabled after initialization.
+    pub tearing_effect: TearingEffect,
 }
 
 impl ModelOptions {
     /// Creates model options for the entire framebuffer.
     pub fn full_size<M: Model>() -> Self {
-        Self {
-            color_order: ColorOrder::default(),
-            orientation: Orientation::default(),
-            invert_colors: ColorInversion::default(),
-            refresh_order: RefreshOrder::default(),
-            display_size: M::FRAMEBUFFER_SIZE,
-            display_offset: (0, 0),
-        }
+        Self::with_all(M::FRAMEBUFFER_SIZE, (0, 0))
     }
 
     /// Creates model options for the given size and offset.
@@ -47,6 +48,10 @@ impl ModelOptions {
             refresh_order: RefreshOrder::default(),
             display_size,
             display_offset,
+            command_delays: CommandDelays::default(),
+            init_timings: InitTimings::default(),
+            init_sequence: &[],
+            tearing_effect: TearingEffect::default(),
         }
     }
 
@@ -62,36 +67,266 @@ impl ModelOptions {
     }
 }
 
-/// Color inversion.
+/// Extra delays after commands.
+///
+/// Some clone controllers drop commands which are sent back-to-back at full
+/// interface speed. The delays are inserted after the commands that are sent
+/// by the model's init sequence.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::options::CommandDelays;
+///
+/// // wait 10 µs after every command and an additional 5 ms after COLMOD (0x3A)
+/// const DELAYS: CommandDelays = CommandDelays::new(10).after(&[(0x3A, 5_000)]);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandDelays {
+    /// Delay in µs after every command.
+    pub per_command: u32,
+    /// Additional delays in µs after specific instructions.
+    ///
+    /// Each entry contains the instruction code and the delay.
+    pub after: &'static [(u8, u32)],
+    /// Additional delays in µs after specific 16 bit commands or register
+    /// writes.
+    ///
+    /// Each entry contains the command address or register index and the
+    /// delay.
+    pub after_wide: &'static [(u16, u32)],
+}
+
+impl CommandDelays {
+    /// Creates a new command delay setting with the given delay in µs after every command.
+    pub const fn new(per_command: u32) -> Self {
+        Self {
+            per_command,
+            after: &[],
+            after_wide: &[],
+        }
+    }
+
+    /// Returns a command delay setting with additional delays after specific instructions.
+    #[must_use]
+    pub const fn after(self, after: &'static [(u8, u32)]) -> Self {
+        Self { after, ..self }
+    }
+
+    /// Returns a command delay setting with additional delays after specific
+    /// 16 bit commands or register writes.
+    #[must_use]
+    pub const fn after_wide(self, after_wide: &'static [(u16, u32)]) -> Self {
+        Self { after_wide, ..self }
+    }
+
+    /// Returns the total delay in µs after the given instruction.
+    pub fn delay_after(&self, instruction: u8) -> u32 {
+        self.after
+            .iter()
+            .filter(|(i, _)| *i == instruction)
+            .fold(self.per_command, |sum, (_, delay)| {
+                sum.saturating_add(*delay)
+            })
+    }
+
+    /// Returns the total delay in µs after the given 16 bit command or
+    /// register index.
+    pub fn delay_after_wide(&self, address: u16) -> u32 {
+        self.after_wide
+            .iter()
+            .filter(|(a, _)| *a == address)
+            .fold(self.per_command, |sum, (_, delay)| {
+                sum.saturating_add(*delay)
+            })
+    }
+
+    /// Returns `true` if no delays are configured.
+    pub fn is_empty(&self) -> bool {
+        self.per_command == 0 && self.after.is_empty() && self.after_wide.is_empty()
+    }
+}
+
+/// Overrides for the built-in init delays of a model.
+///
+/// Some clone panels need longer settling times than the original controller.
+/// An override replaces the delay the model waits at the given point of the
+/// init sequence. If the model doesn't wait at that point the delay is
+/// inserted instead.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::options::InitTimings;
+///
+/// // wait 300 ms after SLPOUT and 50 ms after DISPON
+/// const TIMINGS: InitTimings = InitTimings::new()
+///     .post_sleep_out_us(300_000)
+///     .post_display_on_us(50_000);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InitTimings {
+    /// Delay in µs after the reset, before the first command is sent.
+    pub post_reset_us: Option<u32>,
+    /// Delay in µs after the exit sleep mode (SLPOUT) command.
+    pub post_sleep_out_us: Option<u32>,
+    /// Delay in µs after the display on (DISPON) command.
+    pub post_display_on_us: Option<u32>,
+}
+
+impl InitTimings {
+    /// Creates a new init timing setting without any overrides.
+    pub const fn new() -> Self {
+        Self {
+            post_reset_us: None,
+            post_sleep_out_us: None,
+            post_display_on_us: None,
+        }
+    }
+
+    /// Returns an init timing setting with the given delay in µs after the reset.
+    #[must_use]
+    pub const fn post_reset_us(self, delay: u32) -> Self {
+        Self {
+            post_reset_us: Some(delay),
+            ..self
+        }
+    }
+
+    /// Returns an init timing setting with the given delay in µs after SLPOUT.
+    #[must_use]
+    pub const fn post_sleep_out_us(self, delay: u32) -> Self {
+        Self {
+            post_sleep_out_us: Some(delay),
+            ..self
+        }
+    }
+
+    /// Returns an init timing setting with the given delay in µs after DISPON.
+    #[must_use]
+    pub const fn post_display_on_us(self, delay: u32) -> Self {
+        Self {
+            post_display_on_us: Some(delay),
+            ..self
+        }
+    }
+
+    /// Returns `true` if no overrides are configured.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::new()
+    }
+}
+
+/// Always-on-display mode settings.
+///
+/// Used by [`Display::enter_aod`](crate::Display::enter_aod) to switch the
+/// display into a low power mode, which only shows a part of the screen.
+///
+/// The visible rows are specified in framebuffer rows and aren't affected by
+/// the display orientation. Additional vendor specific commands can be added
+/// by using [`sequences`](Self::sequences).
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::options::AodMode;
+///
+/// // show rows 100 to 139 in 8 color idle mode at 25% brightness and 40 Hz
+/// const AOD: AodMode = AodMode::new(100, 139)
+///     .idle(true)
+///     .brightness(64)
+///     .frame_rate(40);
+/// ```
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AodMode {
+    /// First and last visible framebuffer row.
+    pub rows: (u16, u16),
+    /// Enables idle mode, with a reduced color depth.
+    pub idle: bool,
+    /// Display brightness.
+    pub brightness: Option<u8>,
+    /// Frame rate in Hz, which is set by using
+    /// [`Model::set_frame_rate`].
+    pub frame_rate: Option<u16>,
+    /// Commands which are sent when the AOD mode is entered.
+    pub enter_sequence: &'static [SequenceEntry],
+    /// Commands which are sent when the AOD mode is exited.
+    pub exit_sequence: &'static [SequenceEntry],
+}
+
+impl AodMode {
+    /// Creates a new AOD mode setting, which shows the given rows.
+    pub const fn new(start_row: u16, end_row: u16) -> Self {
+        Self {
+            rows: (start_row, end_row),
+            idle: false,
+            brightness: None,
+            frame_rate: None,
+            enter_sequence: &[],
+            exit_sequence: &[],
+        }
+    }
+
+    /// Returns an AOD mode setting with idle mode enabled or disabled.
+    #[must_use]
+    pub const fn idle(self, idle: bool) -> Self {
+        Self { idle, ..self }
+    }
+
+    /// Returns an AOD mode setting with the given brightness.
+    #[must_use]
+    pub const fn brightness(self, brightness: u8) -> Self {
+        Self {
+            brightness: Some(brightness),
+            ..self
+        }
+    }
+
+    /// Returns an AOD mode setting with the given frame rate in Hz.
+    #[must_use]
+    pub const fn frame_rate(self, frame_rate: u16) -> Self {
+        Self {
+            frame_rate: Some(frame_rate),
+            ..self
+        }
+    }
+
+    /// Returns an AOD mode setting with commands which are sent when the AOD
+    /// mode is entered and exited.
+    #[must_use]
+    pub const fn sequences(
+        self,
+        enter_sequence: &'static [SequenceEntry],
+        exit_sequence: &'static [SequenceEntry],
+    ) -> Self {
+        Self {
+            enter_sequence,
+            exit_sequence,
+            ..self
+        }
+    }
+}
+
+/// Color inversion.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub enum ColorInversion {
     /// Normal colors.
+    #[default]
     Normal,
     /// Inverted colors.
     Inverted,
 }
 
-impl Default for ColorInversion {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 /// Vertical refresh order.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub enum VerticalRefreshOrder {
     /// Refresh from top to bottom.
+    #[default]
     TopToBottom,
     /// Refresh from bottom to top.
     BottomToTop,
 }
 
-impl Default for VerticalRefreshOrder {
-    fn default() -> Self {
-        Self::TopToBottom
-    }
-}
-
 impl VerticalRefreshOrder {
     /// Returns the opposite refresh order.
     #[must_use]
@@ -104,20 +339,15 @@ impl VerticalRefreshOrder {
 }
 
 /// Horizontal refresh order.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub enum HorizontalRefreshOrder {
     /// Refresh from left to right.
+    #[default]
     LeftToRight,
     /// Refresh from right to left.
     RightToLeft,
 }
 
-impl Default for HorizontalRefreshOrder {
-    fn default() -> Self {
-        Self::LeftToRight
-    }
-}
-
 impl HorizontalRefreshOrder {
     /// Returns the opposite refresh order.
     #[must_use]
@@ -169,9 +399,10 @@ impl RefreshOrder {
 }
 
 /// Tearing effect output setting.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum TearingEffect {
     /// Disable output.
+    #[default]
     Off,
     /// Output vertical blanking information.
     Vertical,
@@ -179,17 +410,63 @@ pub enum TearingEffect {
     HorizontalAndVertical,
 }
 
+/// Backlight control setting.
+///
+/// Used by [`Display::set_backlight_control`](crate::Display::set_backlight_control)
+/// to configure the brightness control block of the controller.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BacklightMode {
+    /// Brightness control and backlight off.
+    Off,
+    /// Brightness control and backlight on.
+    On,
+    /// Brightness control and backlight on, with smooth transitions
+    /// between brightness levels.
+    Dimmed,
+}
+
+/// Content adaptive brightness control setting.
+///
+/// The controller reduces the brightness depending on the displayed content
+/// to save power.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AdaptiveBrightness {
+    /// Adaptive brightness control off.
+    #[default]
+    Off = 0x00,
+    /// Optimized for user interface images.
+    UserInterface = 0x01,
+    /// Optimized for still pictures.
+    StillPicture = 0x02,
+    /// Optimized for moving images.
+    MovingImage = 0x03,
+}
+
+/// Predefined gamma curve.
+///
+/// The curves are defined by the controller, see the datasheet of the
+/// controller for the gamma values of each curve.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GammaCurve {
+    /// Gamma curve 0 (GC0).
+    #[default]
+    Curve0 = 0x01,
+    /// Gamma curve 1 (GC1).
+    Curve1 = 0x02,
+    /// Gamma curve 2 (GC2).
+    Curve2 = 0x04,
+    /// Gamma curve 3 (GC3).
+    Curve3 = 0x08,
+}
+
 /// Subpixel order.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ColorOrder {
     /// RGB subpixel order.
+    #[default]
     Rgb,
     /// BGR subpixel order.
     Bgr,
 }
-
-impl Default for ColorOrder {
-    fn default() -> Self {
-        Self::Rgb
-    }
-}
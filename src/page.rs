@@ -0,0 +1,254 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Size},
+    prelude::PointsIter,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::MemoryMapping,
+    Display,
+};
+
+/// Off-screen page of the framebuffer.
+///
+/// Controllers with a framebuffer that is larger than the panel can store
+/// additional full screen pages in the invisible part of the framebuffer. A
+/// page is drawn by using the [`DrawTarget`] implementation of this type and
+/// is revealed instantly by [`Display::show_page`], which uses the vertical
+/// scroll start address. This allows tear-free full screen transitions
+/// without a framebuffer in host memory.
+///
+/// Pages are created by [`Display::page`].
+pub struct Page<'a, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    pub(crate) display: &'a mut Display<DI, M, RST>,
+    pub(crate) offset: (u16, u16),
+    pub(crate) size: Size,
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Returns the number of full screen pages which fit into the framebuffer.
+    ///
+    /// Page flipping uses vertical scrolling, which is only possible if the
+    /// orientation doesn't swap rows and columns. In other orientations only
+    /// a single page is available.
+    pub fn page_count(&self) -> u16 {
+        let mapping = MemoryMapping::from(self.options.orientation);
+        if mapping.swap_rows_and_columns {
+            return 1;
+        }
+
+        let page_height = self.options.display_size.1;
+        let rows = self.model.framebuffer_size().1 - self.options.display_offset.1;
+        rows / page_height
+    }
+
+    /// Returns a draw target for the given page.
+    ///
+    /// Page 0 is the page which is visible if the vertical scroll offset is
+    /// zero. Returns `None` if the page doesn't fit into the framebuffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::{models::ST7789, Builder};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// // 240x320 framebuffer with a 240x135 panel
+    /// let mut display = Builder::new(ST7789, di)
+    ///     .display_size(240, 135)
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    /// assert_eq!(display.page_count(), 2);
+    ///
+    /// // render the next screen into the invisible page and reveal it
+    /// display.page(1).unwrap().clear(Rgb565::BLUE).unwrap();
+    /// display.show_page(1).unwrap();
+    /// ```
+    pub fn page(&mut self, page: u16) -> Option<Page<'_, DI, M, RST>> {
+        if page >= self.page_count() {
+            return None;
+        }
+
+        let shift = page * self.options.display_size.1;
+        let mut offset = self.address_offset();
+        if MemoryMapping::from(self.options.orientation).reverse_rows {
+            offset.1 -= shift;
+        } else {
+            offset.1 += shift;
+        }
+
+        let size = self.size();
+        Some(Page {
+            display: self,
+            offset,
+            size,
+        })
+    }
+
+    /// Shows the given page.
+    ///
+    /// This sets the vertical scroll region to the whole framebuffer and the
+    /// scroll offset to the start of the page. Pages which don't fit into the
+    /// framebuffer are ignored.
+    pub fn show_page(&mut self, page: u16) -> Result<(), DI::Error> {
+        if page >= self.page_count() {
+            return Ok(());
+        }
+
+        self.set_vertical_scroll_region(0, 0)?;
+        self.set_vertical_scroll_offset(page * self.options.display_size.1)
+    }
+}
+
+impl<DI, M, RST> Page<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    // Sets the address window and starts a memory write. The area must be
+    // inside the page.
+    fn start_write(&mut self, area: &Rectangle) -> Result<(), DI::Error> {
+        // Unchecked casting to u16 cannot fail here because the area is
+        // inside the page, which always fits in an u16.
+        let bottom_right = area.bottom_right().unwrap_or(area.top_left);
+        let (sx, sy) = (area.top_left.x as u16, area.top_left.y as u16);
+        let (ex, ey) = (bottom_right.x as u16, bottom_right.y as u16);
+        let (ox, oy) = self.offset;
+
+        M::update_address_window(
+            &mut self.display.di,
+            self.display.options.orientation.rotation,
+            sx + ox,
+            sy + oy,
+            ex + ox,
+            ey + oy,
+        )?;
+        M::write_memory_start(&mut self.display.di)
+    }
+}
+
+impl<DI, M, RST> DrawTarget for Page<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        for Pixel(point, color) in pixels {
+            if bounds.contains(point) {
+                self.start_write(&Rectangle::new(point, Size::new(1, 1)))?;
+                M::ColorFormat::send_pixels(&mut self.display.di, core::iter::once(color))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        if self.bounding_box().intersection(area) == *area {
+            let count = (area.size.width * area.size.height) as usize;
+            self.start_write(area)?;
+            M::ColorFormat::send_pixels(&mut self.display.di, colors.into_iter().take(count))
+        } else {
+            self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.start_write(&area)?;
+        M::ColorFormat::send_repeated_pixel(
+            &mut self.display.di,
+            color,
+            area.size.width * area.size.height,
+        )
+    }
+}
+
+impl<DI, M, RST> OriginDimensions for Page<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(all(test, feature = "st7789"))]
+mod tests {
+    use crate::{
+        _mock::{MockDelay, MockDisplayInterface},
+        models::ST7789,
+        options::{Orientation, Rotation},
+        Builder,
+    };
+
+    #[test]
+    fn page_count() {
+        let mut display = Builder::new(ST7789, MockDisplayInterface)
+            .display_size(240, 100)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(display.page_count(), 3);
+        assert!(display.page(2).is_some());
+        assert!(display.page(3).is_none());
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg180))
+            .unwrap();
+        assert_eq!(display.page_count(), 3);
+        assert_eq!(display.page(2).unwrap().offset, (0, 20));
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg90))
+            .unwrap();
+        assert_eq!(display.page_count(), 1);
+    }
+}
@@ -0,0 +1,445 @@
+//! Paletted drawing support.
+//!
+//! A [`PalettedCanvas`] is an embedded-graphics [`DrawTarget`] that stores one
+//! palette index per pixel in a caller provided buffer. This uses a third (or less)
+//! of the memory needed to buffer the same area in the display's native color
+//! format. The canvas is converted to the display color format on the fly when
+//! it is flushed to a [`Display`].
+//!
+//! A palette index can be reserved as transparent. Pixels with the transparent
+//! index are skipped during [`flush`](PalettedCanvas::flush) and the existing
+//! content of the display's framebuffer is preserved at their positions.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{
+//!     pixelcolor::Rgb565,
+//!     prelude::*,
+//!     primitives::{Circle, PrimitiveStyle},
+//! };
+//! use mipidsi::palette::{PaletteIndex, PalettedCanvas};
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! const PALETTE: [Rgb565; 3] = [Rgb565::BLACK, Rgb565::RED, Rgb565::WHITE];
+//!
+//! let mut buffer = [0u8; 32 * 32];
+//! let mut canvas = PalettedCanvas::new(&mut buffer, Size::new(32, 32), &PALETTE)
+//!     .with_transparent_index(PaletteIndex(0));
+//!
+//! Circle::new(Point::new(4, 4), 24)
+//!     .into_styled(PrimitiveStyle::with_fill(PaletteIndex(1)))
+//!     .draw(&mut canvas)
+//!     .unwrap();
+//!
+//! // Only the circle is sent to the display, the corners keep their content.
+//! canvas.flush(&mut display, Point::new(100, 100)).unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::{raw::RawU8, PixelColor},
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Palette index color.
+///
+/// The color type used by [`PalettedCanvas`]. The wrapped value is an index
+/// into the palette the canvas was created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct PaletteIndex(pub u8);
+
+impl PixelColor for PaletteIndex {
+    type Raw = RawU8;
+}
+
+/// Error returned by [`PalettedCanvas::set_palette`] for empty palettes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyPaletteError;
+
+/// Draw target which stores palette indices.
+///
+/// See the [module level documentation](self) for more information.
+pub struct PalettedCanvas<'a, C> {
+    buffer: &'a mut [u8],
+    size: Size,
+    palette: &'a [C],
+    transparent: Option<PaletteIndex>,
+}
+
+impl<'a, C> PalettedCanvas<'a, C>
+where
+    C: PixelColor,
+{
+    /// Creates a new paletted canvas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is smaller than `size.width * size.height` bytes or
+    /// if the palette is empty.
+    pub fn new(buffer: &'a mut [u8], size: Size, palette: &'a [C]) -> Self {
+        assert!(buffer.len() >= (size.width * size.height) as usize);
+        assert!(!palette.is_empty());
+
+        Self {
+            buffer,
+            size,
+            palette,
+            transparent: None,
+        }
+    }
+
+    /// Creates a new paletted canvas.
+    ///
+    /// Returns the buffer if it is smaller than `size.width * size.height`
+    /// bytes or if the palette is empty.
+    pub fn try_new(
+        buffer: &'a mut [u8],
+        size: Size,
+        palette: &'a [C],
+    ) -> Result<Self, &'a mut [u8]> {
+        if buffer.len() < (size.width * size.height) as usize || palette.is_empty() {
+            return Err(buffer);
+        }
+
+        Ok(Self {
+            buffer,
+            size,
+            palette,
+            transparent: None,
+        })
+    }
+
+    /// Reserves a palette index as transparent.
+    ///
+    /// Pixels set to this index aren't sent to the display by [`flush`](Self::flush).
+    #[must_use]
+    pub fn with_transparent_index(mut self, index: PaletteIndex) -> Self {
+        self.transparent = Some(index);
+        self
+    }
+
+    /// Returns the transparent palette index, if any.
+    pub fn transparent_index(&self) -> Option<PaletteIndex> {
+        self.transparent
+    }
+
+    /// Returns the palette.
+    pub fn palette(&self) -> &[C] {
+        self.palette
+    }
+
+    /// Replaces the palette.
+    ///
+    /// Changing the palette doesn't modify the stored indices, which makes
+    /// palette based color cycling effects possible.
+    ///
+    /// Returns an error and keeps the current palette if `palette` is empty.
+    pub fn set_palette(&mut self, palette: &'a [C]) -> Result<(), EmptyPaletteError> {
+        if palette.is_empty() {
+            return Err(EmptyPaletteError);
+        }
+
+        self.palette = palette;
+        Ok(())
+    }
+
+    /// Returns the palette index at the given position.
+    pub fn index(&self, point: Point) -> Option<PaletteIndex> {
+        self.offset(point).map(|i| PaletteIndex(self.buffer[i]))
+    }
+
+    /// Returns the color at the given position.
+    ///
+    /// Indices outside of the palette are clamped to the last palette entry.
+    pub fn color(&self, point: Point) -> Option<C> {
+        self.index(point).map(|index| self.lookup(index))
+    }
+
+    /// Flushes the canvas to the display.
+    ///
+    /// The top left corner of the canvas is placed at `origin` in display
+    /// coordinates. Parts of the canvas outside of the display are clipped.
+    pub fn flush<DI, M, RST>(
+        &self,
+        display: &mut Display<DI, M, RST>,
+        origin: Point,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model<ColorFormat = C>,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+    {
+        let area = Rectangle::new(origin, self.size).intersection(&display.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        // Offset of the visible area inside the canvas.
+        let skip_x = (area.top_left.x - origin.x) as usize;
+        let skip_y = (area.top_left.y - origin.y) as usize;
+        let width = area.size.width as usize;
+        let stride = self.size.width as usize;
+
+        let sx = area.top_left.x as u16;
+        let sy = area.top_left.y as u16;
+        let ex = bottom_right.x as u16;
+        let ey = bottom_right.y as u16;
+
+        let Some(transparent) = self.transparent else {
+            let colors = (sy..=ey).flat_map(|y| {
+                let start = (usize::from(y - sy) + skip_y) * stride + skip_x;
+                self.buffer[start..start + width]
+                    .iter()
+                    .map(|&i| self.lookup(PaletteIndex(i)))
+            });
+            return display.set_pixels(sx, sy, ex, ey, colors);
+        };
+
+        for y in sy..=ey {
+            let start = (usize::from(y - sy) + skip_y) * stride + skip_x;
+            let row = &self.buffer[start..start + width];
+
+            for (run_start, run_end) in OpaqueRuns::new(row, transparent.0) {
+                let colors = row[run_start..run_end]
+                    .iter()
+                    .map(|&i| self.lookup(PaletteIndex(i)));
+                display.set_pixels(sx + run_start as u16, y, sx + run_end as u16 - 1, y, colors)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn lookup(&self, index: PaletteIndex) -> C {
+        let i = usize::from(index.0).min(self.palette.len() - 1);
+        self.palette[i]
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        if point.x < 0
+            || point.y < 0
+            || point.x as u32 >= self.size.width
+            || point.y as u32 >= self.size.height
+        {
+            return None;
+        }
+
+        Some(point.y as usize * self.size.width as usize + point.x as usize)
+    }
+}
+
+impl<C> OriginDimensions for PalettedCanvas<'_, C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C> DrawTarget for PalettedCanvas<'_, C>
+where
+    C: PixelColor,
+{
+    type Color = PaletteIndex;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, index) in pixels {
+            if let Some(i) = self.offset(point) {
+                self.buffer[i] = index.0;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        let stride = self.size.width as usize;
+        for y in area.top_left.y..=bottom_right.y {
+            let start = y as usize * stride + area.top_left.x as usize;
+            self.buffer[start..=start + (bottom_right.x - area.top_left.x) as usize].fill(color.0);
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let len = (self.size.width * self.size.height) as usize;
+        self.buffer[..len].fill(color.0);
+        Ok(())
+    }
+}
+
+/// Iterator over the `(start, end)` ranges of a row which don't contain the
+/// transparent index.
+struct OpaqueRuns<'a> {
+    row: &'a [u8],
+    transparent: u8,
+    pos: usize,
+}
+
+impl<'a> OpaqueRuns<'a> {
+    fn new(row: &'a [u8], transparent: u8) -> Self {
+        Self {
+            row,
+            transparent,
+            pos: 0,
+        }
+    }
+}
+
+impl Iterator for OpaqueRuns<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.row[self.pos..];
+        let start = self.pos + rest.iter().position(|&i| i != self.transparent)?;
+        let end = self.row[start..]
+            .iter()
+            .position(|&i| i == self.transparent)
+            .map_or(self.row.len(), |len| start + len);
+
+        self.pos = end;
+        Some((start, end))
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use super::*;
+
+    #[test]
+    fn opaque_runs() {
+        let row = [0, 1, 1, 0, 0, 2, 0, 3, 3];
+        let runs: [_; 3] = core::array::from_fn({
+            let mut runs = OpaqueRuns::new(&row, 0);
+            move |_| runs.next().unwrap()
+        });
+        assert_eq!(runs, [(1, 3), (5, 6), (7, 9)]);
+
+        assert_eq!(OpaqueRuns::new(&[0, 0, 0], 0).next(), None);
+        assert_eq!(OpaqueRuns::new(&[1, 2, 3], 0).next(), Some((0, 3)));
+        assert_eq!(OpaqueRuns::new(&[], 0).next(), None);
+    }
+
+    #[test]
+    fn draw_and_lookup() {
+        let palette = [Rgb565::BLACK, Rgb565::RED];
+        let mut buffer = [0u8; 16];
+        let mut canvas = PalettedCanvas::new(&mut buffer, Size::new(4, 4), &palette);
+
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 1), Size::new(2, 2)),
+                PaletteIndex(1),
+            )
+            .unwrap();
+        canvas
+            .draw_iter([Pixel(Point::new(3, 3), PaletteIndex(7))])
+            .unwrap();
+
+        assert_eq!(canvas.color(Point::new(0, 0)), Some(Rgb565::BLACK));
+        assert_eq!(canvas.color(Point::new(2, 2)), Some(Rgb565::RED));
+        assert_eq!(canvas.index(Point::new(3, 3)), Some(PaletteIndex(7)));
+        // out of range indices are clamped to the last palette entry
+        assert_eq!(canvas.color(Point::new(3, 3)), Some(Rgb565::RED));
+        assert_eq!(canvas.index(Point::new(4, 0)), None);
+    }
+
+    #[test]
+    fn invalid_arguments_return_errors() {
+        let palette = [Rgb565::BLACK, Rgb565::RED];
+        let mut buffer = [0u8; 16];
+        assert!(PalettedCanvas::try_new(&mut buffer, Size::new(5, 4), &palette).is_err());
+        assert!(PalettedCanvas::<Rgb565>::try_new(&mut buffer, Size::new(4, 4), &[]).is_err());
+
+        let mut canvas = PalettedCanvas::try_new(&mut buffer, Size::new(4, 4), &palette).unwrap();
+        assert_eq!(canvas.set_palette(&[]), Err(EmptyPaletteError));
+        assert_eq!(canvas.palette(), &palette);
+    }
+
+    #[test]
+    fn flush_clips_to_display() {
+        use alloc::vec;
+
+        use crate::{
+            _mock::MockDelay,
+            interface::{RecordingInterface, Transfer},
+            models::ILI9341Rgb565,
+            Builder,
+        };
+
+        let palette = [Rgb565::BLACK, Rgb565::RED];
+        let mut buffer = [1u8; 16];
+        let canvas = PalettedCanvas::new(&mut buffer, Size::new(4, 4), &palette);
+
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        unsafe { display.dcs() }.clear();
+
+        let window = |sx: u16, sy: u16, ex: u16, ey: u16, pixels: usize| {
+            let [sx, ex, sy, ey] = [sx, ex, sy, ey].map(u16::to_be_bytes);
+            [
+                Transfer::Command {
+                    instruction: 0x2A,
+                    params: vec![sx[0], sx[1], ex[0], ex[1]],
+                },
+                Transfer::Command {
+                    instruction: 0x2B,
+                    params: vec![sy[0], sy[1], ey[0], ey[1]],
+                },
+                Transfer::Command {
+                    instruction: 0x2C,
+                    params: vec![],
+                },
+                Transfer::Pixels([0xF8, 0x00].repeat(pixels)),
+            ]
+        };
+
+        canvas.flush(&mut display, Point::new(-2, -2)).unwrap();
+        assert_eq!(
+            unsafe { display.dcs() }.take_transfers(),
+            window(0, 0, 1, 1, 4)
+        );
+
+        canvas.flush(&mut display, Point::new(238, 318)).unwrap();
+        assert_eq!(
+            unsafe { display.dcs() }.take_transfers(),
+            window(238, 318, 239, 319, 4)
+        );
+
+        canvas.flush(&mut display, Point::new(500, 500)).unwrap();
+        assert_eq!(unsafe { display.dcs() }.take_transfers(), []);
+
+        // transparent pixels split the visible row into two runs
+        buffer = [1; 16];
+        buffer[14] = 0;
+        let canvas = PalettedCanvas::new(&mut buffer, Size::new(4, 4), &palette)
+            .with_transparent_index(PaletteIndex(0));
+        canvas.flush(&mut display, Point::new(-1, -3)).unwrap();
+        assert_eq!(
+            unsafe { display.dcs() }.take_transfers(),
+            [window(0, 0, 0, 0, 1), window(2, 0, 2, 0, 1)].concat()
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Frame stream playback.
+//!
+//! This module contains a helper to play back a stream of pre-encoded
+//! frames, e.g. a simple animation or video stored on a SD card or in flash.
+//! See [`Display::play`] for more information.
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget, geometry::Dimensions, primitives::Rectangle,
+};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+};
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Frame in a frame stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame<I> {
+    /// Full frame which covers the whole display.
+    Full(I),
+    /// Partial frame which only updates the given area.
+    Delta(Rectangle, I),
+}
+
+/// Frame synchronization.
+///
+/// Used by [`Display::play`] to wait for the vertical blanking period
+/// before a frame is drawn.
+pub trait FrameSync {
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Blocks until the next vertical blanking period starts.
+    fn wait_for_vblank(&mut self) -> Result<(), Self::Error>;
+}
+
+/// No frame synchronization.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoSync;
+
+impl FrameSync for NoSync {
+    type Error = core::convert::Infallible;
+
+    fn wait_for_vblank(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Frame synchronization using the tearing effect output of the display.
+///
+/// The tearing effect output must be enabled by using
+/// [`Display::set_tearing_effect`] before the playback is started.
+#[derive(Debug)]
+pub struct TearingEffectPin<P>(pub P);
+
+impl<P: InputPin> FrameSync for TearingEffectPin<P> {
+    type Error = P::Error;
+
+    fn wait_for_vblank(&mut self) -> Result<(), Self::Error> {
+        // wait for the rising edge of the TE signal
+        while self.0.is_high()? {}
+        while self.0.is_low()? {}
+
+        Ok(())
+    }
+}
+
+/// Playback error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackError<DI, SYNC> {
+    /// Interface error
+    Interface(DI),
+    /// Frame synchronization error
+    Sync(SYNC),
+    /// The buffer is too small for the requested operation
+    BufferTooSmall,
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Plays back a stream of frames.
+    ///
+    /// Playback is paced by waiting `frame_delay_us` after each frame. Before
+    /// a frame is drawn the vertical blanking period is awaited by using
+    /// `sync`, use [`NoSync`] to disable synchronization. The pixels of a [`Frame::Full`] frame cover the whole
+    /// display and the pixels of a [`Frame::Delta`] frame only cover the given
+    /// area. Pixels are drawn row by row, starting at the top left corner.
+    ///
+    /// Returns the number of played frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    /// use mipidsi::playback::{Frame, NoSync};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let area = Rectangle::new(Point::new(10, 10), Size::new(2, 2));
+    /// let frames = [
+    ///     Frame::Full([Rgb565::BLACK; 240 * 320].as_slice()),
+    ///     Frame::Delta(area, [Rgb565::RED; 4].as_slice()),
+    ///     Frame::Delta(area, [Rgb565::GREEN; 4].as_slice()),
+    /// ];
+    ///
+    /// let frames = frames.iter().map(|frame| match frame {
+    ///     Frame::Full(pixels) => Frame::Full(pixels.iter().copied()),
+    ///     Frame::Delta(area, pixels) => Frame::Delta(*area, pixels.iter().copied()),
+    /// });
+    ///
+    /// // play back at ~25 FPS
+    /// let count = display.play(frames, &mut delay, &mut NoSync, 40_000).unwrap();
+    /// assert_eq!(count, 3);
+    /// ```
+    pub fn play<F, I, D, S>(
+        &mut self,
+        frames: F,
+        delay: &mut D,
+        sync: &mut S,
+        frame_delay_us: u32,
+    ) -> Result<usize, PlaybackError<DI::Error, S::Error>>
+    where
+        F: IntoIterator<Item = Frame<I>>,
+        I: IntoIterator<Item = M::ColorFormat>,
+        D: DelayNs,
+        S: FrameSync,
+    {
+        let mut count = 0;
+
+        for frame in frames {
+            if count > 0 && frame_delay_us > 0 {
+                delay.delay_us(frame_delay_us);
+            }
+            sync.wait_for_vblank().map_err(PlaybackError::Sync)?;
+
+            let (area, pixels) = match frame {
+                Frame::Full(pixels) => (self.bounding_box(), pixels),
+                Frame::Delta(area, pixels) => (area, pixels),
+            };
+            self.fill_contiguous(&area, pixels)
+                .map_err(PlaybackError::Interface)?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::{
+        geometry::{Point, Size},
+        pixelcolor::{Rgb565, RgbColor},
+    };
+    use embedded_hal::digital::ErrorType;
+
+    use crate::_mock::{new_mock_display, MockDelay};
+
+    use super::*;
+
+    /// TE pin which toggles on every read.
+    struct TogglingPin {
+        level: bool,
+        reads: usize,
+    }
+
+    impl ErrorType for TogglingPin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for TogglingPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            self.reads += 1;
+            self.level = !self.level;
+            Ok(self.level)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    #[test]
+    fn play_waits_for_te_before_every_frame() {
+        let mut display = new_mock_display();
+        let mut sync = TearingEffectPin(TogglingPin {
+            level: false,
+            reads: 0,
+        });
+
+        let area = Rectangle::new(Point::new(0, 0), Size::new(1, 1));
+        let frames = (0..3).map(|_| Frame::Delta(area, [Rgb565::RED]));
+        let count = display.play(frames, &mut MockDelay, &mut sync, 0).unwrap();
+
+        assert_eq!(count, 3);
+        // The first frame starts during a TE pulse and needs to wait for
+        // the next pulse, the other frames start right after a pulse.
+        assert_eq!(sync.0.reads, 3 + 2 + 2);
+    }
+}
@@ -0,0 +1,214 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Size},
+    prelude::PointsIter,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{interface::Interface, interface::InterfacePixelFormat, models::Model, Display};
+
+/// Scoped drawing region of a display.
+///
+/// A region is created by [`Display::region`] and restricts all drawing
+/// operations to a rectangular area of the display. Coordinates used to draw
+/// to the region are relative to the top left corner of the region and
+/// drawing outside of the region is clipped.
+///
+/// The region doesn't change any persistent display state, like the
+/// orientation, because the address window is set for every drawing
+/// operation. The display can be used normally again after the region is
+/// dropped.
+pub struct Region<'a, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    display: &'a mut Display<DI, M, RST>,
+    area: Rectangle,
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Returns a drawing region for the given area.
+    ///
+    /// The area is clipped to the display bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{
+    ///     pixelcolor::Rgb565,
+    ///     prelude::*,
+    ///     primitives::{Circle, PrimitiveStyle, Rectangle},
+    /// };
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let mut region = display.region(Rectangle::new(Point::new(10, 10), Size::new(50, 50)));
+    /// region.clear(Rgb565::BLACK).unwrap();
+    /// region
+    ///     .draw(|target| {
+    ///         Circle::new(Point::zero(), 50)
+    ///             .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+    ///             .draw(target)
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn region(&mut self, area: Rectangle) -> Region<'_, DI, M, RST> {
+        let area = area.intersection(&self.bounding_box());
+        Region {
+            display: self,
+            area,
+        }
+    }
+}
+
+impl<DI, M, RST> Region<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Returns the area of the region in display coordinates.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Fills the region with raw pixel data.
+    ///
+    /// The colors are drawn row by row, starting at the top left corner of
+    /// the region.
+    pub fn blit<I>(&mut self, colors: I) -> Result<(), DI::Error>
+    where
+        I: IntoIterator<Item = M::ColorFormat>,
+    {
+        self.display.fill_contiguous(&self.area, colors)
+    }
+
+    /// Draws to the region by using a closure.
+    ///
+    /// The closure gets access to the region as a [`DrawTarget`], which
+    /// uses coordinates relative to the region and clips everything outside.
+    pub fn draw<F, R>(&mut self, f: F) -> Result<R, DI::Error>
+    where
+        F: FnOnce(&mut Self) -> Result<R, DI::Error>,
+    {
+        f(self)
+    }
+
+    // Converts an area in region coordinates into display coordinates.
+    fn to_display(&self, area: &Rectangle) -> Rectangle {
+        Rectangle::new(area.top_left + self.area.top_left, area.size)
+    }
+}
+
+impl<DI, M, RST> DrawTarget for Region<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.area;
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point + area.top_left, color))
+                .filter(|Pixel(point, _)| area.contains(*point)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let display_area = self.to_display(area);
+        if self.area.intersection(&display_area) == display_area {
+            self.display.fill_contiguous(&display_area, colors)
+        } else {
+            self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.to_display(area).intersection(&self.area);
+        self.display.fill_solid(&area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.fill_solid(&self.area, color)
+    }
+}
+
+impl<DI, M, RST> OriginDimensions for Region<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::{
+        geometry::Point,
+        pixelcolor::{Rgb565, RgbColor},
+    };
+
+    use crate::_mock::new_mock_display;
+
+    use super::*;
+
+    #[test]
+    fn region_is_clipped_to_display() {
+        let mut display = new_mock_display();
+        let region = display.region(Rectangle::new(Point::new(200, 300), Size::new(100, 100)));
+
+        assert_eq!(
+            region.area(),
+            Rectangle::new(Point::new(200, 300), Size::new(40, 20))
+        );
+        assert_eq!(region.bounding_box().size, Size::new(40, 20));
+    }
+
+    #[test]
+    fn region_draws() {
+        let mut display = new_mock_display();
+        let mut region = display.region(Rectangle::new(Point::new(10, 10), Size::new(4, 4)));
+
+        region.clear(Rgb565::BLACK).unwrap();
+        region.blit([Rgb565::RED; 16]).unwrap();
+        region
+            .draw(|target| {
+                target.fill_contiguous(
+                    &Rectangle::new(Point::new(2, 2), Size::new(4, 4)),
+                    [Rgb565::GREEN; 16],
+                )
+            })
+            .unwrap();
+    }
+}
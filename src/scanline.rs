@@ -0,0 +1,193 @@
+//! Scanline rendering.
+//!
+//! Full screen animations usually need a framebuffer in RAM, which many
+//! small MCUs can't afford. A scanline renderer instead renders a few lines
+//! at a time into a small buffer and streams them to the display right
+//! behind the refresh of the panel ("racing the beam"). The refresh is
+//! tracked by using the tearing effect output or the `GSCAN` command, which
+//! prevents tearing as long as the lines are sent faster than the panel
+//! refreshes them.
+//!
+//! See [`Display::render_scanlines`] for more information.
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat, ReadInterface},
+    models::Model,
+    playback::{FrameSync, PlaybackError},
+    Display,
+};
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Renders a full screen frame line by line.
+    ///
+    /// The start of the frame is synchronized with the refresh of the panel
+    /// by using `sync`, e.g. a [`TearingEffectPin`](crate::playback::TearingEffectPin).
+    /// After that, `buffer` is repeatedly split into lines, `render` is
+    /// called with the row index and the pixels of each line, and the
+    /// rendered lines are sent to the display in a single transfer.
+    ///
+    /// The buffer needs to hold at least one line. Buffers which hold two or
+    /// more lines reduce the per transfer overhead. If `buffer` is smaller
+    /// than one line of the display [`PlaybackError::BufferTooSmall`] is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::pixelcolor::{Rgb565, RgbColor};
+    /// use mipidsi::playback::NoSync;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let mut lines = [Rgb565::BLACK; 240 * 2];
+    /// let frame = 0;
+    ///
+    /// display
+    ///     .render_scanlines(&mut lines, &mut NoSync, |y, line| {
+    ///         // horizontal stripes which move with the frame counter
+    ///         let color = if (y + frame) % 16 < 8 { Rgb565::RED } else { Rgb565::BLUE };
+    ///         line.fill(color);
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn render_scanlines<F, S>(
+        &mut self,
+        buffer: &mut [M::ColorFormat],
+        sync: &mut S,
+        render: F,
+    ) -> Result<(), PlaybackError<DI::Error, S::Error>>
+    where
+        F: FnMut(u16, &mut [M::ColorFormat]),
+        S: FrameSync,
+    {
+        self.check_scanline_buffer(buffer)?;
+
+        sync.wait_for_vblank().map_err(PlaybackError::Sync)?;
+        self.stream_scanlines(buffer, render)
+            .map_err(PlaybackError::Interface)
+    }
+
+    // Checks that the buffer holds at least one line.
+    fn check_scanline_buffer<E, SYNC>(
+        &self,
+        buffer: &[M::ColorFormat],
+    ) -> Result<(), PlaybackError<E, SYNC>> {
+        if buffer.len() < usize::from(self.options.display_size().0) {
+            return Err(PlaybackError::BufferTooSmall);
+        }
+
+        Ok(())
+    }
+
+    fn stream_scanlines<F>(
+        &mut self,
+        buffer: &mut [M::ColorFormat],
+        mut render: F,
+    ) -> Result<(), DI::Error>
+    where
+        F: FnMut(u16, &mut [M::ColorFormat]),
+    {
+        let (width, height) = self.options.display_size();
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let width = usize::from(width);
+        let lines = buffer.len() / width;
+        let buffer = &mut buffer[..lines * width];
+
+        let mut y = 0;
+        while y < height {
+            let count = (height - y).min(lines as u16);
+            let chunk = &mut buffer[..usize::from(count) * width];
+            for (i, line) in chunk.chunks_exact_mut(width).enumerate() {
+                render(y + i as u16, line);
+            }
+
+            self.set_pixels(0, y, width as u16 - 1, y + count - 1, chunk.iter().copied())?;
+            y += count;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: ReadInterface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Renders a full screen frame line by line, synchronized by `GSCAN`.
+    ///
+    /// Works like [`render_scanlines`](Self::render_scanlines), but waits for
+    /// the start of the next refresh by polling the current scanline, which
+    /// doesn't require a connected tearing effect pin. Errors while reading
+    /// the scanline are returned as [`PlaybackError::Sync`].
+    pub fn render_scanlines_gscan<F>(
+        &mut self,
+        buffer: &mut [M::ColorFormat],
+        render: F,
+    ) -> Result<(), PlaybackError<DI::Error, DI::Error>>
+    where
+        F: FnMut(u16, &mut [M::ColorFormat]),
+    {
+        self.check_scanline_buffer(buffer)?;
+
+        // the scanline decreases when the panel starts the next refresh
+        let mut previous = self.current_scanline().map_err(PlaybackError::Sync)?;
+        loop {
+            let scanline = self.current_scanline().map_err(PlaybackError::Sync)?;
+            if scanline < previous {
+                break;
+            }
+            previous = scanline;
+        }
+
+        self.stream_scanlines(buffer, render)
+            .map_err(PlaybackError::Interface)
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _mock::new_mock_display,
+        playback::{NoSync, PlaybackError},
+    };
+
+    #[test]
+    fn renders_every_line_once() {
+        let mut display = new_mock_display();
+        let mut buffer = [Rgb565::BLACK; 240 * 3];
+        let mut rows = [0u8; 320];
+
+        display
+            .render_scanlines(&mut buffer, &mut NoSync, |y, line| {
+                assert_eq!(line.len(), 240);
+                rows[usize::from(y)] += 1;
+            })
+            .unwrap();
+
+        assert!(rows.iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let mut buffer = [Rgb565::BLACK; 239];
+        assert!(matches!(
+            new_mock_display().render_scanlines(&mut buffer, &mut NoSync, |_, _| {}),
+            Err(PlaybackError::BufferTooSmall)
+        ));
+    }
+}
@@ -0,0 +1,296 @@
+//! Wiring self-test.
+//!
+//! This module contains a scripted test sequence which can be used to verify
+//! the wiring of a panel without custom test firmware, e.g. on a production
+//! line or as a first boot diagnostic. See [`Display::self_test`] for more
+//! information.
+
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Dimensions, pixelcolor::RgbColor};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    dcs::{self, InterfaceExt},
+    interface::{Interface, InterfacePixelFormat, ReadInterface},
+    models::Model,
+    test_image, Display,
+};
+
+/// Time each test pattern is shown in milliseconds.
+pub const PATTERN_DURATION_MS: u32 = 500;
+
+/// Self-test report.
+///
+/// Every step of the self-test is executed, even if a previous step failed,
+/// and the result of each step is recorded in the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestReport<E> {
+    /// Result of filling the whole display with white.
+    pub all_pixels_on: Result<(), E>,
+    /// Result of drawing red, green and blue color bars.
+    pub color_bars: Result<(), E>,
+    /// Result of drawing a one pixel wide white border.
+    pub border: Result<(), E>,
+    /// Power mode read back from the controller.
+    ///
+    /// `None` if the status wasn't read back.
+    pub power_mode: Option<Result<PowerMode, E>>,
+}
+
+impl<E> SelfTestReport<E> {
+    /// Returns `true` if all steps succeeded.
+    ///
+    /// If the power mode was read back, the controller must also report that
+    /// it is awake and the display is on.
+    pub fn passed(&self) -> bool {
+        let status_ok = match &self.power_mode {
+            Some(Ok(mode)) => mode.sleep_out() && mode.display_on(),
+            Some(Err(_)) => false,
+            None => true,
+        };
+
+        self.all_pixels_on.is_ok() && self.color_bars.is_ok() && self.border.is_ok() && status_ok
+    }
+}
+
+/// Power mode returned by the `RDDPM` (read display power mode) command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerMode(pub u8);
+
+impl PowerMode {
+    /// Returns `true` if the booster voltage is on.
+    pub const fn booster_on(self) -> bool {
+        self.0 & (1 << 7) != 0
+    }
+
+    /// Returns `true` if idle mode is on.
+    pub const fn idle_mode(self) -> bool {
+        self.0 & (1 << 6) != 0
+    }
+
+    /// Returns `true` if partial mode is on.
+    pub const fn partial_mode(self) -> bool {
+        self.0 & (1 << 5) != 0
+    }
+
+    /// Returns `true` if the controller isn't in sleep mode.
+    pub const fn sleep_out(self) -> bool {
+        self.0 & (1 << 4) != 0
+    }
+
+    /// Returns `true` if normal display mode is on.
+    pub const fn normal_mode(self) -> bool {
+        self.0 & (1 << 3) != 0
+    }
+
+    /// Returns `true` if the display is on.
+    pub const fn display_on(self) -> bool {
+        self.0 & (1 << 2) != 0
+    }
+}
+
+/// Self-diagnostic result returned by the `RDDSDR` (read display
+/// self-diagnostic result) command.
+///
+/// The controller verifies its registers and functionality after leaving the
+/// sleep mode and toggles the corresponding bits if the check succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfDiagnostic(pub u8);
+
+impl SelfDiagnostic {
+    /// Returns `true` if the register loading check succeeded.
+    pub const fn register_loading(self) -> bool {
+        self.0 & (1 << 7) != 0
+    }
+
+    /// Returns `true` if the functionality check succeeded.
+    pub const fn functionality(self) -> bool {
+        self.0 & (1 << 6) != 0
+    }
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Runs the wiring self-test.
+    ///
+    /// The display is filled with white, followed by red, green and blue
+    /// color bars and a one pixel wide white border on a black background.
+    /// Each pattern is shown for [`PATTERN_DURATION_MS`]. The border should
+    /// touch all edges of the panel and the colors of the bars should appear
+    /// from left to right in this order, otherwise the
+    /// [builder](crate::Builder) settings need to be adjusted.
+    ///
+    /// The status isn't read back, use
+    /// [`self_test_with_status`](Self::self_test_with_status) if the
+    /// interface supports reads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let report = display.self_test(&mut delay);
+    /// assert!(report.passed());
+    /// ```
+    pub fn self_test<D: DelayNs>(&mut self, delay: &mut D) -> SelfTestReport<DI::Error> {
+        let bounding_box = self.bounding_box();
+
+        let all_pixels_on = self.fill_solid(&bounding_box, M::ColorFormat::WHITE);
+        delay.delay_ms(PATTERN_DURATION_MS);
+
+        let color_bars = test_image::draw_color_bars(self, &bounding_box);
+        delay.delay_ms(PATTERN_DURATION_MS);
+
+        let border = test_image::draw_border(self, 1);
+        delay.delay_ms(PATTERN_DURATION_MS);
+
+        SelfTestReport {
+            all_pixels_on,
+            color_bars,
+            border,
+            power_mode: None,
+        }
+    }
+
+    /// Turns all pixels of the panel on, ignoring the framebuffer content.
+    ///
+    /// Uses the `ALLPON` (all pixels on) command, which shows a white screen
+    /// without writing any pixel data. Together with
+    /// [`test_all_pixels_off`](Self::test_all_pixels_off) this can be used to
+    /// check the panel for defective pixels and bonding issues. Use
+    /// [`end_all_pixels_test`](Self::end_all_pixels_test) to show the
+    /// framebuffer content again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.test_all_pixels_on().unwrap();
+    /// // check for dead pixels
+    /// display.test_all_pixels_off().unwrap();
+    /// // check for stuck pixels
+    /// display.end_all_pixels_test().unwrap();
+    /// ```
+    pub fn test_all_pixels_on(&mut self) -> Result<(), DI::Error> {
+        self.di.write_command(dcs::AllPixelsOn)
+    }
+
+    /// Turns all pixels of the panel off, ignoring the framebuffer content.
+    ///
+    /// Uses the `ALLPOFF` (all pixels off) command, which shows a black
+    /// screen without writing any pixel data.
+    pub fn test_all_pixels_off(&mut self) -> Result<(), DI::Error> {
+        self.di.write_command(dcs::AllPixelsOff)
+    }
+
+    /// Ends the all pixels on or off test and shows the framebuffer content again.
+    pub fn end_all_pixels_test(&mut self) -> Result<(), DI::Error> {
+        self.di.write_command(dcs::EnterNormalMode)
+    }
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: ReadInterface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Runs the wiring self-test and reads back the power mode.
+    ///
+    /// See [`self_test`](Self::self_test) for a description of the test
+    /// patterns. After the patterns are shown the power mode is read back
+    /// from the controller, which also verifies that the read path of the
+    /// interface is connected.
+    pub fn self_test_with_status<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> SelfTestReport<DI::Error> {
+        let mut report = self.self_test(delay);
+
+        report.power_mode = Some(self.power_mode());
+
+        report
+    }
+
+    /// Returns the power mode of the controller.
+    ///
+    /// Uses the `RDDPM` (read display power mode) command, which can be used
+    /// to verify that the initialization took effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let mode = display.power_mode().unwrap();
+    /// if !mode.sleep_out() || !mode.display_on() {
+    ///     // initialization failed
+    /// }
+    /// ```
+    pub fn power_mode(&mut self) -> Result<PowerMode, DI::Error> {
+        let mut params = [0];
+        self.read_command(dcs::GET_POWER_MODE, &mut params)?;
+        Ok(PowerMode(params[0]))
+    }
+
+    /// Returns the self-diagnostic result of the controller.
+    ///
+    /// Uses the `RDDSDR` (read display self-diagnostic result) command. The
+    /// result is only meaningful after the controller left the sleep mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let diagnostic = display.self_diagnostic().unwrap();
+    /// let ok = diagnostic.register_loading() && diagnostic.functionality();
+    /// ```
+    pub fn self_diagnostic(&mut self) -> Result<SelfDiagnostic, DI::Error> {
+        let mut params = [0];
+        self.read_command(dcs::GET_SELF_DIAGNOSTIC, &mut params)?;
+        Ok(SelfDiagnostic(params[0]))
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use crate::_mock::{new_mock_display, MockDelay};
+
+    use super::*;
+
+    #[test]
+    fn status_is_checked() {
+        let mut display = new_mock_display();
+
+        let report = display.self_test(&mut MockDelay);
+        assert!(report.passed());
+
+        // the mock interface reads all zeros, which means sleep in and
+        // display off
+        let report = display.self_test_with_status(&mut MockDelay);
+        assert_eq!(report.power_mode, Some(Ok(PowerMode(0))));
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn self_diagnostic_bits() {
+        let diagnostic = SelfDiagnostic(0b1000_0000);
+        assert!(diagnostic.register_loading());
+        assert!(!diagnostic.functionality());
+    }
+
+    #[test]
+    fn power_mode_bits() {
+        let mode = PowerMode(0b1001_0100);
+        assert!(mode.booster_on());
+        assert!(!mode.idle_mode());
+        assert!(!mode.partial_mode());
+        assert!(mode.sleep_out());
+        assert!(!mode.normal_mode());
+        assert!(mode.display_on());
+    }
+}
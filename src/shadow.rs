@@ -0,0 +1,306 @@
+//! Shadow framebuffer support.
+//!
+//! Most displays are connected write-only, which means that the current
+//! display content can't be read back. A [`Shadowed`] display mirrors every
+//! drawing operation into a framebuffer in RAM and sends it to the display
+//! immediately. The framebuffer can then be used to read pixels, which makes
+//! read-modify-write algorithms like alpha blending, XOR cursors or flood
+//! fills possible without `RAMRD` support.
+//!
+//! Unlike a [`Framebuffered`](crate::framebuffer::Framebuffered) display, no
+//! flush is required to make changes visible.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+//!
+//! # let display = mipidsi::_mock::new_mock_display();
+//! let mut buffer = [Rgb565::BLACK; 240 * 320];
+//! let mut display = display.with_shadow(&mut buffer);
+//! display.clear(Rgb565::BLACK).unwrap();
+//!
+//! // invert a 16x16 cursor
+//! let cursor = Rectangle::new(Point::new(100, 100), Size::new(16, 16));
+//! display
+//!     .modify(&cursor, |_, c| Rgb565::new(31 - c.r(), 63 - c.g(), 31 - c.b()))
+//!     .unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    prelude::PointsIter,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Display with a shadow framebuffer.
+///
+/// See the [module level documentation](self) for more information.
+pub struct Shadowed<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    display: Display<DI, M, RST>,
+    buffer: B,
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Mirrors all drawing operations into a shadow framebuffer.
+    ///
+    /// The buffer is assumed to match the current display content. Use
+    /// [`Shadowed::sync`] or clear the display to establish this state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is smaller than the number of pixels of the
+    /// display.
+    pub fn with_shadow<B>(self, buffer: B) -> Shadowed<DI, M, RST, B>
+    where
+        B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+    {
+        let size = self.size();
+        assert!(buffer.as_ref().len() >= (size.width * size.height) as usize);
+
+        Shadowed {
+            display: self,
+            buffer,
+        }
+    }
+
+    /// Mirrors all drawing operations into a shadow framebuffer.
+    ///
+    /// Returns the display and the buffer if the buffer is smaller than the
+    /// number of pixels of the display.
+    #[allow(clippy::type_complexity, clippy::result_large_err)]
+    pub fn try_with_shadow<B>(self, buffer: B) -> Result<Shadowed<DI, M, RST, B>, (Self, B)>
+    where
+        B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+    {
+        let size = self.size();
+        if buffer.as_ref().len() < (size.width * size.height) as usize {
+            return Err((self, buffer));
+        }
+
+        Ok(self.with_shadow(buffer))
+    }
+}
+
+impl<DI, M, RST, B> Shadowed<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+{
+    /// Returns the color at the given position.
+    pub fn get_pixel(&self, point: Point) -> Option<M::ColorFormat> {
+        self.offset(point).map(|i| self.buffer.as_ref()[i])
+    }
+
+    /// Returns the colors of an area in row-major order.
+    ///
+    /// The area is clipped to the display bounds.
+    pub fn read_area(&self, area: &Rectangle) -> impl Iterator<Item = M::ColorFormat> + '_ {
+        let area = area.intersection(&self.bounding_box());
+        area.points().filter_map(|point| self.get_pixel(point))
+    }
+
+    /// Replaces the colors of an area with the result of a closure.
+    ///
+    /// The closure is called with the position and the current color of each
+    /// pixel. The area is clipped to the display bounds and sent to the
+    /// display in a single transfer.
+    pub fn modify<F>(&mut self, area: &Rectangle, mut f: F) -> Result<(), DI::Error>
+    where
+        F: FnMut(Point, M::ColorFormat) -> M::ColorFormat,
+    {
+        let area = area.intersection(&self.bounding_box());
+        let stride = self.size().width as usize;
+        let buffer = self.buffer.as_mut();
+
+        self.display.fill_contiguous(
+            &area,
+            area.points().map(|point| {
+                let i = point.y as usize * stride + point.x as usize;
+                buffer[i] = f(point, buffer[i]);
+                buffer[i]
+            }),
+        )
+    }
+
+    /// Sends the whole shadow framebuffer to the display.
+    pub fn sync(&mut self) -> Result<(), DI::Error> {
+        let area = self.bounding_box();
+        let buffer = self.buffer.as_ref();
+        self.display.fill_contiguous(&area, buffer.iter().copied())
+    }
+
+    /// Returns a reference to the display.
+    pub fn display(&self) -> &Display<DI, M, RST> {
+        &self.display
+    }
+
+    /// Returns a mutable reference to the display.
+    ///
+    /// Drawing to the display directly bypasses the shadow framebuffer, which
+    /// will no longer match the display content.
+    pub fn display_mut(&mut self) -> &mut Display<DI, M, RST> {
+        &mut self.display
+    }
+
+    /// Releases the display and the shadow framebuffer.
+    pub fn release(self) -> (Display<DI, M, RST>, B) {
+        (self.display, self.buffer)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        if !self.bounding_box().contains(point) {
+            return None;
+        }
+
+        Some(point.y as usize * self.size().width as usize + point.x as usize)
+    }
+}
+
+impl<DI, M, RST, B> DrawTarget for Shadowed<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    B: AsRef<[M::ColorFormat]> + AsMut<[M::ColorFormat]>,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(i) = self.offset(point) {
+                self.buffer.as_mut()[i] = color;
+                self.display.draw_iter([Pixel(point, color)])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if self.bounding_box().intersection(area) != *area {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            );
+        }
+
+        let stride = self.size().width as usize;
+        let buffer = self.buffer.as_mut();
+        self.display.fill_contiguous(
+            area,
+            area.points().zip(colors).map(|(point, color)| {
+                buffer[point.y as usize * stride + point.x as usize] = color;
+                color
+            }),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        let stride = self.size().width as usize;
+        let buffer = self.buffer.as_mut();
+        for y in area.top_left.y..=bottom_right.y {
+            let start = y as usize * stride + area.top_left.x as usize;
+            buffer[start..=start + (bottom_right.x - area.top_left.x) as usize].fill(color);
+        }
+
+        self.display.fill_solid(&area, color)
+    }
+}
+
+impl<DI, M, RST, B> OriginDimensions for Shadowed<DI, M, RST, B>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.display.size()
+    }
+}
+
+#[cfg(all(test, feature = "ili9341"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::_mock::new_mock_display;
+
+    use super::*;
+
+    #[test]
+    fn reads_mirror_writes() {
+        let mut buffer = [Rgb565::BLACK; 240 * 320];
+        let mut display = new_mock_display().with_shadow(&mut buffer);
+
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 20), Size::new(5, 5)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        display
+            .fill_contiguous(
+                &Rectangle::new(Point::new(238, 0), Size::new(4, 1)),
+                [Rgb565::GREEN; 4],
+            )
+            .unwrap();
+        assert_eq!(display.get_pixel(Point::new(12, 22)), Some(Rgb565::RED));
+        assert_eq!(display.get_pixel(Point::new(239, 0)), Some(Rgb565::GREEN));
+        assert_eq!(display.get_pixel(Point::new(240, 0)), None);
+
+        display
+            .modify(
+                &Rectangle::new(Point::new(8, 20), Size::new(4, 1)),
+                |_, c| if c == Rgb565::RED { Rgb565::BLUE } else { c },
+            )
+            .unwrap();
+        let mut row = display.read_area(&Rectangle::new(Point::new(8, 20), Size::new(4, 1)));
+        assert!(row.by_ref().take(2).all(|c| c == Rgb565::BLACK));
+        assert!(row.all(|c| c == Rgb565::BLUE));
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let buffer = [Rgb565::BLACK; 100];
+        assert!(new_mock_display().try_with_shadow(buffer).is_err());
+    }
+}
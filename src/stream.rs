@@ -0,0 +1,206 @@
+use embedded_graphics_core::{geometry::Dimensions, primitives::Rectangle};
+use embedded_hal::digital::OutputPin;
+
+use crate::{interface::Interface, interface::InterfacePixelFormat, models::Model, Display};
+
+/// Streamed write of a frame in multiple batches.
+///
+/// A frame stream is created by [`Display::begin_frame`], which sets the
+/// address window once. The first batch of pixels is written with the memory
+/// write command (`RAMWR`) and all following batches with memory write
+/// continue (`WRMEMC`), which continues at the pixel following the last
+/// batch. This avoids resending the address window for every batch, which
+/// reduces the overhead for renderers that produce the frame in horizontal
+/// bands.
+///
+/// Batches are written row by row, starting at the top left corner of the
+/// area. Writing more pixels than the area contains wraps around to the top
+/// left corner.
+pub struct FrameStream<'a, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    display: &'a mut Display<DI, M, RST>,
+    area: Rectangle,
+    started: bool,
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Begins a streamed write to the given area.
+    ///
+    /// The area is clipped to the display bounds. Writing to an empty area
+    /// doesn't send any data to the display.
+    ///
+    /// The stream must not be interleaved with other drawing operations,
+    /// because they change the address window. This is enforced by the
+    /// mutable borrow of the display.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let mut frame = display
+    ///     .begin_frame(Rectangle::new(Point::zero(), Size::new(240, 320)))
+    ///     .unwrap();
+    ///
+    /// // render and write the frame in bands of 16 rows
+    /// for band in 0..20 {
+    ///     let color = if band % 2 == 0 { Rgb565::RED } else { Rgb565::BLUE };
+    ///     frame
+    ///         .write_pixels(core::iter::repeat(color).take(240 * 16))
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn begin_frame(
+        &mut self,
+        area: Rectangle,
+    ) -> Result<FrameStream<'_, DI, M, RST>, DI::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if let Some(bottom_right) = area.bottom_right() {
+            self.set_address_window(
+                area.top_left.x as u16,
+                area.top_left.y as u16,
+                bottom_right.x as u16,
+                bottom_right.y as u16,
+            )?;
+        }
+
+        Ok(FrameStream {
+            display: self,
+            area,
+            started: false,
+        })
+    }
+}
+
+impl<DI, M, RST> FrameStream<'_, DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Returns the area of the frame in display coordinates.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Writes the next batch of pixels.
+    pub fn write_pixels<I>(&mut self, colors: I) -> Result<(), DI::Error>
+    where
+        I: IntoIterator<Item = M::ColorFormat>,
+    {
+        if self.area.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.write_memory()?;
+        M::ColorFormat::send_pixels(&mut self.display.di, colors)
+    }
+
+    /// Writes the next batch of pixel data which is already in the format of
+    /// the display interface.
+    ///
+    /// See [`Display::set_pixels_raw`] for the format of the data.
+    pub fn write_pixels_raw(&mut self, data: &[DI::Word]) -> Result<(), DI::Error> {
+        if self.area.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.write_memory()?;
+        self.display.di.send_raw_pixels(data)
+    }
+
+    // Sends the memory write command for the next batch.
+    fn write_memory(&mut self) -> Result<(), DI::Error> {
+        if self.started {
+            M::write_memory_continue(&mut self.display.di)
+        } else {
+            self.started = true;
+            M::write_memory_start(&mut self.display.di)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "st7789"))]
+mod tests {
+    use alloc::vec;
+
+    use embedded_graphics_core::{
+        geometry::{Point, Size},
+        pixelcolor::{Rgb565, RgbColor},
+    };
+
+    use crate::{
+        _mock::MockDelay,
+        interface::{RecordingInterface, Transfer},
+        models::ST7789,
+        Builder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn batches_use_write_memory_continue() {
+        let mut display = Builder::new(ST7789, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        unsafe { display.dcs() }.take_transfers();
+
+        let mut frame = display
+            .begin_frame(Rectangle::new(Point::new(0, 0), Size::new(2, 2)))
+            .unwrap();
+        frame.write_pixels([Rgb565::RED; 2]).unwrap();
+        frame.write_pixels([Rgb565::BLUE; 2]).unwrap();
+
+        assert_eq!(
+            unsafe { display.dcs() }.take_transfers(),
+            [
+                Transfer::Command {
+                    instruction: 0x2A,
+                    params: vec![0, 0, 0, 1],
+                },
+                Transfer::Command {
+                    instruction: 0x2B,
+                    params: vec![0, 0, 0, 1],
+                },
+                Transfer::Command {
+                    instruction: 0x2C,
+                    params: vec![],
+                },
+                Transfer::Pixels(vec![0xF8, 0x00, 0xF8, 0x00]),
+                Transfer::Command {
+                    instruction: 0x3C,
+                    params: vec![],
+                },
+                Transfer::Pixels(vec![0x00, 0x1F, 0x00, 0x1F]),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_frame_writes_nothing() {
+        let mut display = Builder::new(ST7789, RecordingInterface::new())
+            .init(&mut MockDelay)
+            .unwrap();
+        unsafe { display.dcs() }.take_transfers();
+
+        let mut frame = display
+            .begin_frame(Rectangle::new(Point::new(300, 0), Size::new(2, 2)))
+            .unwrap();
+        frame.write_pixels([Rgb565::RED; 2]).unwrap();
+
+        assert_eq!(unsafe { display.dcs() }.take_transfers(), []);
+    }
+}
@@ -73,7 +73,7 @@ impl<C: RgbColor> Drawable for TestImage<C> {
 }
 
 /// Draws a white border around the draw target.
-fn draw_border<D>(target: &mut D, width: u32) -> Result<(), D::Error>
+pub(crate) fn draw_border<D>(target: &mut D, width: u32) -> Result<(), D::Error>
 where
     D: DrawTarget,
     D::Color: RgbColor,
@@ -94,7 +94,7 @@ where
 }
 
 /// Draws RGB color bars and labels.
-fn draw_color_bars<D>(target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+pub(crate) fn draw_color_bars<D>(target: &mut D, area: &Rectangle) -> Result<(), D::Error>
 where
     D: DrawTarget,
     D::Color: RgbColor,
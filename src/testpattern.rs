@@ -0,0 +1,251 @@
+//! Test pattern generator.
+//!
+//! The patterns in this module help with panel bring-up. They are drawn with
+//! the `fill_solid` and `fill_contiguous` fast paths of the draw target, which
+//! means that they also work on slow interfaces without noticeable delay.
+//!
+//! - [`TestPattern::ColorBars`] checks the color order and inversion settings.
+//! - [`TestPattern::GrayscaleRamp`] and [`TestPattern::Gradient`] show banding
+//!   and help to choose a [gamma correction](crate::gamma).
+//! - [`TestPattern::Checkerboard`] makes scaling and addressing errors visible.
+//! - [`TestPattern::Crosshair`] verifies the display size and offset settings.
+//!
+//! # Examples
+//!
+//! ```
+//! use mipidsi::testpattern::TestPattern;
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! TestPattern::Crosshair.draw(&mut display).unwrap();
+//! TestPattern::Checkerboard { size: 8 }.draw(&mut display).unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    pixelcolor::RgbColor,
+    prelude::PointsIter,
+    primitives::Rectangle,
+};
+
+use crate::{gamma::FromRgb, test_image};
+
+/// Test pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPattern {
+    /// Eight vertical color bars.
+    ///
+    /// From left to right: white, yellow, cyan, green, magenta, red, blue and
+    /// black.
+    ColorBars,
+    /// Vertical bands of gray from black on the left to white on the right.
+    GrayscaleRamp {
+        /// Number of bands.
+        steps: u8,
+    },
+    /// Horizontal red, green and blue gradients, from black on the left to the
+    /// full intensity on the right.
+    Gradient,
+    /// Black and white checkerboard.
+    Checkerboard {
+        /// Size of each square in pixels.
+        size: u32,
+    },
+    /// One pixel wide white border and a crosshair through the center.
+    Crosshair,
+}
+
+impl TestPattern {
+    /// Draws the test pattern to the whole draw target.
+    pub fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+        D::Color: FromRgb,
+    {
+        let area = target.bounding_box();
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        match *self {
+            Self::ColorBars => {
+                const BARS: [(u8, u8, u8); 8] = [
+                    (255, 255, 255),
+                    (255, 255, 0),
+                    (0, 255, 255),
+                    (0, 255, 0),
+                    (255, 0, 255),
+                    (255, 0, 0),
+                    (0, 0, 255),
+                    (0, 0, 0),
+                ];
+
+                for (i, (r, g, b)) in BARS.into_iter().enumerate() {
+                    let color = from_rgb888::<D::Color>(r, g, b);
+                    target.fill_solid(&band(&area, i as u32, BARS.len() as u32), color)?;
+                }
+            }
+            Self::GrayscaleRamp { steps } => {
+                let steps = u32::from(steps.max(2));
+                for i in 0..steps {
+                    let gray = (i * 255 / (steps - 1)) as u8;
+                    let color = from_rgb888::<D::Color>(gray, gray, gray);
+                    target.fill_solid(&band(&area, i, steps), color)?;
+                }
+            }
+            Self::Gradient => {
+                let width = area.size.width;
+                let height = area.size.height;
+                target.fill_contiguous(
+                    &area,
+                    area.points().map(|p| {
+                        let p = p - area.top_left;
+                        let value = ramp(p.x as u32, width);
+                        match p.y as u32 * 3 / height {
+                            0 => from_rgb888(value, 0, 0),
+                            1 => from_rgb888(0, value, 0),
+                            _ => from_rgb888(0, 0, value),
+                        }
+                    }),
+                )?;
+            }
+            Self::Checkerboard { size } => {
+                let size = size.max(1);
+                target.fill_contiguous(
+                    &area,
+                    area.points().map(|p| {
+                        let p = p - area.top_left;
+                        if (p.x as u32 / size + p.y as u32 / size) % 2 == 0 {
+                            D::Color::WHITE
+                        } else {
+                            D::Color::BLACK
+                        }
+                    }),
+                )?;
+            }
+            Self::Crosshair => {
+                test_image::draw_border(target, 1)?;
+
+                let center = area.center();
+                target.fill_solid(
+                    &Rectangle::new(
+                        Point::new(area.top_left.x, center.y),
+                        Size::new(area.size.width, 1),
+                    ),
+                    D::Color::WHITE,
+                )?;
+                target.fill_solid(
+                    &Rectangle::new(
+                        Point::new(center.x, area.top_left.y),
+                        Size::new(1, area.size.height),
+                    ),
+                    D::Color::WHITE,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the `index`th of `count` vertical bands of `area`.
+fn band(area: &Rectangle, index: u32, count: u32) -> Rectangle {
+    let start = area.size.width * index / count;
+    let end = area.size.width * (index + 1) / count;
+
+    Rectangle::new(
+        area.top_left + Point::new(start as i32, 0),
+        Size::new(end - start, area.size.height),
+    )
+}
+
+// Returns the 8 bit intensity at `x` of a ramp from 0 to 255 over `width` pixels.
+fn ramp(x: u32, width: u32) -> u8 {
+    if width <= 1 {
+        255
+    } else {
+        (x * 255 / (width - 1)) as u8
+    }
+}
+
+// Creates a color from 8 bit channel values.
+fn from_rgb888<C: FromRgb>(r: u8, g: u8, b: u8) -> C {
+    let scale = |value: u8, max: u8| ((u16::from(value) * u16::from(max) + 127) / 255) as u8;
+    C::from_rgb(scale(r, C::MAX_R), scale(g, C::MAX_G), scale(b, C::MAX_B))
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::{
+        geometry::{Dimensions, OriginDimensions},
+        pixelcolor::{Rgb565, Rgb888},
+        Pixel,
+    };
+
+    use super::*;
+
+    /// Small in-memory draw target.
+    struct Canvas([Rgb888; 8 * 6]);
+
+    impl Canvas {
+        fn pixel(&self, x: usize, y: usize) -> Rgb888 {
+            self.0[y * 8 + x]
+        }
+    }
+
+    impl DrawTarget for Canvas {
+        type Color = Rgb888;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(p, color) in pixels {
+                if self.bounding_box().contains(p) {
+                    self.0[p.y as usize * 8 + p.x as usize] = color;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl OriginDimensions for Canvas {
+        fn size(&self) -> Size {
+            Size::new(8, 6)
+        }
+    }
+
+    #[test]
+    fn color_bars() {
+        let mut canvas = Canvas([Rgb888::BLACK; 48]);
+        TestPattern::ColorBars.draw(&mut canvas).unwrap();
+
+        assert_eq!(canvas.pixel(0, 0), Rgb888::WHITE);
+        assert_eq!(canvas.pixel(1, 5), Rgb888::YELLOW);
+        assert_eq!(canvas.pixel(5, 3), Rgb888::RED);
+        assert_eq!(canvas.pixel(7, 0), Rgb888::BLACK);
+    }
+
+    #[test]
+    fn gradient_and_checkerboard() {
+        let mut canvas = Canvas([Rgb888::BLACK; 48]);
+        TestPattern::Gradient.draw(&mut canvas).unwrap();
+        assert_eq!(canvas.pixel(0, 0), Rgb888::BLACK);
+        assert_eq!(canvas.pixel(7, 0), Rgb888::RED);
+        assert_eq!(canvas.pixel(7, 2), Rgb888::GREEN);
+        assert_eq!(canvas.pixel(7, 5), Rgb888::BLUE);
+
+        TestPattern::Checkerboard { size: 2 }
+            .draw(&mut canvas)
+            .unwrap();
+        assert_eq!(canvas.pixel(0, 0), Rgb888::WHITE);
+        assert_eq!(canvas.pixel(2, 1), Rgb888::BLACK);
+        assert_eq!(canvas.pixel(2, 2), Rgb888::WHITE);
+    }
+
+    #[test]
+    fn channel_scaling() {
+        assert_eq!(from_rgb888::<Rgb565>(255, 128, 0), Rgb565::new(31, 32, 0));
+    }
+}
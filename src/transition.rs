@@ -0,0 +1,296 @@
+//! Screen transitions.
+//!
+//! This module contains simple transition effects, which can be used to
+//! replace the current display contents by a new screen. See
+//! [`Display::transition`] for more information.
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    prelude::PointsIter,
+    primitives::Rectangle,
+    Drawable, Pixel,
+};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Transition effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Fades out the display by reducing the brightness, draws the new
+    /// screen and fades the display back in.
+    ///
+    /// Requires a controller which supports brightness control.
+    Fade,
+    /// Reveals the new screen column by column from left to right.
+    Wipe,
+    /// Slides the new screen in from the bottom by using hardware scrolling.
+    ///
+    /// The new screen is drawn into an off-screen [page](crate::Page) first.
+    /// If the framebuffer can't hold a second page the new screen is drawn
+    /// directly.
+    Slide,
+}
+
+/// Transition settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// Transition effect.
+    pub effect: Effect,
+    /// Number of animation steps.
+    pub steps: u16,
+    /// Delay after each step in microseconds.
+    pub step_delay_us: u32,
+}
+
+impl Transition {
+    /// Creates new transition settings.
+    ///
+    /// The transition uses 16 steps with a delay of 10 ms by default.
+    pub const fn new(effect: Effect) -> Self {
+        Self {
+            effect,
+            steps: 16,
+            step_delay_us: 10_000,
+        }
+    }
+
+    /// Sets the number of animation steps.
+    #[must_use]
+    pub const fn steps(self, steps: u16) -> Self {
+        Self { steps, ..self }
+    }
+
+    /// Sets the delay after each step in microseconds.
+    #[must_use]
+    pub const fn step_delay_us(self, step_delay_us: u32) -> Self {
+        Self {
+            step_delay_us,
+            ..self
+        }
+    }
+}
+
+impl<DI, M, RST> Display<DI, M, RST>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+{
+    /// Replaces the display contents by a new screen using a transition
+    /// effect.
+    ///
+    /// The `screen` is drawn to the whole display and might be drawn multiple
+    /// times, depending on the effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{
+    ///     pixelcolor::Rgb565,
+    ///     prelude::*,
+    ///     primitives::{PrimitiveStyle, Rectangle},
+    /// };
+    /// use mipidsi::transition::{Effect, Transition};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let screen = Rectangle::new(Point::zero(), Size::new(240, 320))
+    ///     .into_styled(PrimitiveStyle::with_fill(Rgb565::BLUE));
+    ///
+    /// let transition = Transition::new(Effect::Wipe).steps(8);
+    /// display.transition(&screen, transition, &mut delay).unwrap();
+    /// ```
+    pub fn transition<S, D>(
+        &mut self,
+        screen: &S,
+        transition: Transition,
+        delay: &mut D,
+    ) -> Result<(), DI::Error>
+    where
+        S: Drawable<Color = M::ColorFormat>,
+        D: DelayNs,
+    {
+        let steps = transition.steps.max(1);
+        let step_delay_us = transition.step_delay_us;
+
+        match transition.effect {
+            Effect::Fade => {
+                let brightness = self.brightness.unwrap_or(u8::MAX);
+                let level =
+                    |step: u16| (u32::from(brightness) * u32::from(step) / u32::from(steps)) as u8;
+
+                for step in (0..steps).rev() {
+                    self.set_brightness(level(step))?;
+                    delay.delay_us(step_delay_us);
+                }
+                screen.draw(self)?;
+                for step in 1..=steps {
+                    self.set_brightness(level(step))?;
+                    delay.delay_us(step_delay_us);
+                }
+            }
+            Effect::Wipe => {
+                let size = self.bounding_box().size;
+                let width = u32::from(steps).min(size.width.max(1));
+                let column_width = size.width.div_ceil(width);
+
+                let mut x = 0;
+                while x < size.width {
+                    let area = Rectangle::new(
+                        Point::new(x as i32, 0),
+                        Size::new(column_width.min(size.width - x), size.height),
+                    );
+                    screen.draw(&mut Clipped { target: self, area })?;
+                    delay.delay_us(step_delay_us);
+                    x += column_width;
+                }
+            }
+            Effect::Slide => {
+                let Some(mut page) = self.page(1) else {
+                    screen.draw(self)?;
+                    return Ok(());
+                };
+                screen.draw(&mut page)?;
+
+                let height = u32::from(self.options.display_size.1);
+                self.set_vertical_scroll_region(0, 0)?;
+                for step in 1..=steps {
+                    let offset = height * u32::from(step) / u32::from(steps);
+                    self.set_vertical_scroll_offset(offset as u16)?;
+                    delay.delay_us(step_delay_us);
+                }
+
+                // Move the new screen back into the first page to keep the
+                // address mapping for other drawing operations unchanged.
+                screen.draw(self)?;
+                self.show_page(0)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Draw target wrapper which only draws inside the given area.
+///
+/// Unlike [`Region`](crate::Region) the coordinates aren't translated.
+struct Clipped<'a, T> {
+    target: &'a mut T,
+    area: Rectangle,
+}
+
+impl<T: DrawTarget> DrawTarget for Clipped<'_, T> {
+    type Color = T::Color;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.area;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| area.contains(*point)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clipped = self.area.intersection(area);
+        if clipped == *area {
+            self.target.fill_contiguous(area, colors)
+        } else if clipped.is_zero_sized() {
+            Ok(())
+        } else {
+            // only send the pixels inside the clipped area in a single
+            // window instead of drawing them one by one
+            self.target.fill_contiguous(
+                &clipped,
+                area.points()
+                    .zip(colors)
+                    .filter(|(point, _)| clipped.contains(*point))
+                    .map(|(_, color)| color),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(&self.area.intersection(area), color)
+    }
+}
+
+impl<T: Dimensions> Dimensions for Clipped<'_, T> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+#[cfg(all(test, feature = "ili9341", feature = "st7789"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _mock::{MockDelay, MockDisplayInterface},
+        models::ST7789,
+        Builder,
+    };
+
+    use super::*;
+
+    /// Screen which fills the whole draw target.
+    struct Fill(Rgb565);
+
+    impl Drawable for Fill {
+        type Color = Rgb565;
+        type Output = ();
+
+        fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+        where
+            D: DrawTarget<Color = Self::Color>,
+        {
+            let area = target.bounding_box();
+            target.fill_contiguous(&area, area.points().map(|_| self.0))
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        let mut display = Builder::new(ST7789, MockDisplayInterface)
+            .display_size(240, 135)
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_brightness(100).unwrap();
+
+        for effect in [Effect::Fade, Effect::Wipe, Effect::Slide] {
+            let transition = Transition::new(effect).steps(7);
+            display
+                .transition(&Fill(Rgb565::RED), transition, &mut MockDelay)
+                .unwrap();
+        }
+        assert_eq!(display.brightness, Some(100));
+        assert_eq!(display.scroll_region, Some((0, 0)));
+        assert_eq!(display.scroll_offset, Some(0));
+    }
+
+    #[test]
+    fn clipped_fill_contiguous() {
+        let mut display = crate::_mock::new_mock_display();
+        let mut clipped = Clipped {
+            target: &mut display,
+            area: Rectangle::new(Point::new(10, 0), Size::new(10, 320)),
+        };
+
+        Fill(Rgb565::GREEN).draw(&mut clipped).unwrap();
+        assert_eq!(clipped.bounding_box().size, Size::new(240, 320));
+    }
+}